@@ -1,3 +1,9 @@
-fn main() {
+use std::path::Path;
+
+#[path = "build_fonts.rs"]
+mod build_fonts;
+
+fn main() -> anyhow::Result<()> {
     embuild::espidf::sysenv::output();
+    build_fonts::generate(Path::new("."))
 }
@@ -0,0 +1,32 @@
+// BDF fonts converted to const font::StaticFont tables
+//
+// Shared by build.rs and host/build.rs
+use std::env;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/utils/bdf.rs"]
+mod bdf;
+
+// (const name, BDF file, char ranges to include - keep small to save flash)
+const BDF_FONTS: &[(&str, &str, &[RangeInclusive<char>])] = &[(
+    "FONT_BDF_5X7",
+    "fonts/c3zero-5x7.bdf",
+    &[' '..='~', '£'..='£', '°'..='±', 'é'..='é', '€'..='€'],
+)];
+
+/// Write OUT_DIR/bdf_fonts.rs (font paths are relative to root)
+pub fn generate(root: &Path) -> anyhow::Result<()> {
+    let mut fonts = String::new();
+    for (name, path, subset) in BDF_FONTS {
+        let path = root.join(path);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let font = bdf::BdfFont::parse(&fs::read(&path)?, subset)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        fonts.push_str(&font.to_rust(name));
+    }
+    fs::write(Path::new(&env::var("OUT_DIR")?).join("bdf_fonts.rs"), fonts)?;
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use std::path::Path;
+
+#[path = "../build_fonts.rs"]
+mod build_fonts;
+
+fn main() -> anyhow::Result<()> {
+    build_fonts::generate(Path::new(".."))
+}
@@ -0,0 +1,30 @@
+// Preview message on terminal and write PNG
+//
+//   cargo run --example preview -- "Hello" [out.png]
+use std::env;
+use std::fs;
+
+use c3zero_host::font::FONT_8X8;
+use c3zero_host::matrix_1d::{Matrix1D, TextStyle};
+use c3zero_host::render::{self, Frame};
+use c3zero_host::rgb::Rgb;
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().collect();
+    let msg = args.get(1).map(|s| s.as_str()).unwrap_or("Hello");
+    let out = args.get(2).map(|s| s.as_str()).unwrap_or("preview.png");
+
+    let mut matrix = Matrix1D::<4>::new();
+    matrix.set_text_style(TextStyle {
+        proportional: true,
+        spacing: 1,
+        ..Default::default()
+    });
+    matrix.draw_str(msg, &FONT_8X8, Rgb::new(255, 64, 0), (0, 0));
+
+    let frame = Frame::from_canvas(&matrix);
+    print!("{}", render::to_ansi(&frame));
+    fs::write(out, render::to_png(&frame, 8)?)?;
+    println!("Wrote {}", out);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+// Display modules from the firmware crate that don't depend on ESP-IDF
+#[path = "../../src/utils/animation.rs"]
+pub mod animation;
+#[path = "../../src/utils/bdf.rs"]
+pub mod bdf;
+#[path = "../../src/utils/chart.rs"]
+pub mod chart;
+#[path = "../../src/utils/clock.rs"]
+pub mod clock;
+#[path = "../../src/utils/draw.rs"]
+pub mod draw;
+#[path = "../../src/utils/easing.rs"]
+pub mod easing;
+#[path = "../../src/utils/effect.rs"]
+pub mod effect;
+#[path = "../../src/utils/font.rs"]
+pub mod font;
+#[path = "../../src/utils/game.rs"]
+pub mod game;
+#[path = "../../src/utils/hash.rs"]
+pub mod hash;
+#[path = "../../src/utils/image.rs"]
+pub mod image;
+#[path = "../../src/utils/layer.rs"]
+pub mod layer;
+#[path = "../../src/utils/layout.rs"]
+pub mod layout;
+#[path = "../../src/utils/matrix.rs"]
+pub mod matrix;
+#[path = "../../src/utils/matrix_1d.rs"]
+pub mod matrix_1d;
+#[path = "../../src/utils/pattern.rs"]
+pub mod pattern;
+#[path = "../../src/utils/playlist.rs"]
+pub mod playlist;
+#[path = "../../src/utils/render.rs"]
+pub mod render;
+#[path = "../../src/utils/rgb.rs"]
+pub mod rgb;
+#[path = "../../src/utils/schedule.rs"]
+pub mod schedule;
+#[path = "../../src/utils/scroll.rs"]
+pub mod scroll;
+#[path = "../../src/utils/sprite.rs"]
+pub mod sprite;
+#[path = "../../src/utils/timer.rs"]
+pub mod timer;
+#[path = "../../src/utils/transition.rs"]
+pub mod transition;
+#[path = "../../src/utils/tween.rs"]
+pub mod tween;
+#[path = "../../src/utils/widget.rs"]
+pub mod widget;
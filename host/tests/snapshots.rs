@@ -0,0 +1,2477 @@
+// Golden frame snapshot tests
+//
+// Frames are compared with tests/golden/<name>.ppm - on mismatch the
+// rendered frame is written to <name>.actual.ppm and both are printed
+// to the terminal. Run with UPDATE_GOLDEN=1 to regenerate golden files.
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use c3zero_host::animation::{self, Player};
+use c3zero_host::bdf::BdfFont;
+use c3zero_host::chart::{Chart, ChartKind, Range, Samples};
+use c3zero_host::clock::{self, ClockOptions, DateTime};
+use c3zero_host::draw::{Canvas, Geometry, Paint};
+use c3zero_host::easing::Easing;
+use c3zero_host::effect::{self, Effect, EffectParams, EffectRegistry, Palette};
+use c3zero_host::font::{
+    Bold, Font, Font8x8, Glyph, Scaled, FONT_3X5, FONT_4X6, FONT_5X7, FONT_8X8, FONT_BDF_5X7,
+    FONT_TALL_DIGITS, REPLACEMENT_GLYPH,
+};
+use c3zero_host::game::{Game, GameKind, GameRunner, Input, Phase, Pong, Snake, Tetris};
+use c3zero_host::image;
+use c3zero_host::layer::{Layer, LayerStack};
+use c3zero_host::layout::{PageMode, TextLayout};
+use c3zero_host::matrix::{self, Matrix};
+use c3zero_host::matrix_1d::{Align, Matrix1D, Orientation, Panel, TextStyle};
+use c3zero_host::pattern::{self, Pattern, Pixel, Program, Vm};
+use c3zero_host::playlist::{Entry, Playlist, PlaylistPlayer, SceneSpec};
+use c3zero_host::render::{self, Frame};
+use c3zero_host::rgb::{self, BlendMode, Rgb};
+use c3zero_host::schedule::{Action, Cron, Schedule};
+use c3zero_host::scroll::{ScrollMode, ScrollOptions, SmoothScroll};
+use c3zero_host::sprite::{Blit, Sprite, Transparency};
+use c3zero_host::timer::{self, Alert, Countdown, Stopwatch, TimerControl};
+use c3zero_host::transition::{Direction, Transition, TransitionKind};
+use c3zero_host::tween::{Animator, FakeClock, Props, Repeat, Tween};
+use c3zero_host::widget::{self, Widget};
+use unicode_segmentation::UnicodeSegmentation;
+
+const ORIENTATIONS: [Orientation; 4] = [
+    Orientation::North,
+    Orientation::East,
+    Orientation::South,
+    Orientation::West,
+];
+
+fn golden(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.ppm", name))
+}
+
+fn assert_snapshot(name: &str, frame: &Frame) {
+    let path = golden(name);
+    let actual = render::to_ppm(frame, 1);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing golden file {} (run with UPDATE_GOLDEN=1)",
+            path.display()
+        )
+    });
+    if expected != actual {
+        fs::write(path.with_extension("actual.ppm"), &actual).unwrap();
+        let expected = image::decode_ppm(&expected).unwrap();
+        let mut expected_frame = Frame::new(expected.size());
+        expected.blit(&mut expected_frame, (0, 0), Default::default());
+        panic!(
+            "Snapshot {} differs\nexpected:\n{}actual:\n{}",
+            name,
+            render::to_ansi(&expected_frame),
+            render::to_ansi(frame)
+        );
+    }
+}
+
+fn proportional() -> TextStyle {
+    TextStyle {
+        proportional: true,
+        spacing: 1,
+        ..Default::default()
+    }
+}
+
+// Pixel map (one char per pixel) - R/G/B/W for the primary colours,
+// '.' for off and '#' for anything else
+const MAP_COLOURS: [(char, Rgb); 5] = [
+    ('.', rgb::OFF),
+    ('R', rgb::RED),
+    ('G', rgb::GREEN),
+    ('B', rgb::BLUE),
+    ('W', rgb::WHITE),
+];
+
+fn from_map(rows: &[&str]) -> Frame {
+    let mut frame = Frame::new((rows[0].len() as i32, rows.len() as i32));
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            let (_, rgb) = MAP_COLOURS.iter().find(|(k, _)| *k == c).unwrap();
+            frame.set((x as i32, y as i32), *rgb);
+        }
+    }
+    frame
+}
+
+fn to_map(canvas: &impl Canvas) -> Vec<String> {
+    let (w, h) = canvas.size();
+    (0..h)
+        .map(|y| {
+            (0..w)
+                .map(|x| {
+                    let rgb = canvas.get((x, y)).unwrap();
+                    MAP_COLOURS
+                        .iter()
+                        .find(|(_, c)| *c == rgb)
+                        .map_or('#', |(k, _)| *k)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+const TEST_BDF: &str = "STARTFONT 2.1
+FONT test
+FONTBOUNDINGBOX 4 6 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 4
+FONT_DESCENT 2
+ENDPROPERTIES
+CHARS 5
+STARTCHAR g
+ENCODING 103
+BBX 2 3 0 -2
+BITMAP
+C0
+40
+C0
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+DWIDTH 5 0
+BBX 3 3 1 0
+BITMAP
+E0
+A0
+E0
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+DWIDTH 4 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+DWIDTH 4 0
+BBX 1 2 0 4
+BITMAP
+80
+80
+ENDCHAR
+ENDFONT
+";
+
+#[test]
+fn bdf() {
+    let font = BdfFont::parse(TEST_BDF.as_bytes(), &[]).unwrap();
+    assert_eq!(
+        (font.name.as_str(), font.height, font.baseline),
+        ("test", 6, 4)
+    );
+    // Sorted by char and unencoded glyphs skipped
+    let chars: String = font.glyphs.iter().map(|g| g.c).collect();
+    assert_eq!(chars, "ABg");
+    // BBX offset shifts columns and rows are placed relative to baseline
+    let a = font.get('A').unwrap();
+    assert_eq!(
+        (a.width, a.rows.as_slice()),
+        (5, &[0, 0xe, 0xa, 0xe, 0, 0][..])
+    );
+    // Descender below baseline and width from BBX when no DWIDTH
+    let g = font.get('g').unwrap();
+    assert_eq!(
+        (g.width, g.rows.as_slice()),
+        (2, &[0, 0, 0, 0x3, 0x2, 0x3][..])
+    );
+    // Rows above the cell are clipped
+    assert_eq!(font.get('B').unwrap().rows, [0; 6]);
+
+    // Subset
+    let font = BdfFont::parse(TEST_BDF.as_bytes(), &['a'..='z', 'A'..='A']).unwrap();
+    let chars: String = font.glyphs.iter().map(|g| g.c).collect();
+    assert_eq!(chars, "Ag");
+    assert!(font.get('B').is_none());
+
+    // Generated source
+    let font = BdfFont::parse(TEST_BDF.as_bytes(), &['0'..='9', 'A'..='A']).unwrap();
+    assert_eq!(
+        font.to_rust("FONT_TEST"),
+        "// Generated from BDF font: test
+#[rustfmt::skip]
+pub const FONT_TEST: StaticFont = StaticFont {
+    height: 6,
+    baseline: 4,
+    glyphs: &[
+        StaticGlyph { c: 'A', width: 5, rows: &[0x0, 0xe, 0xa, 0xe, 0x0, 0x0] },
+    ],
+};
+"
+    );
+
+    // Build-time table matches the parsed font
+    let data = fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../fonts/c3zero-5x7.bdf"));
+    let font = BdfFont::parse(
+        &data.unwrap(),
+        &[' '..='~', '£'..='£', '°'..='±', 'é'..='é', '€'..='€'],
+    )
+    .unwrap();
+    assert_eq!(
+        (FONT_BDF_5X7.height, FONT_BDF_5X7.baseline),
+        (font.height, font.baseline)
+    );
+    assert_eq!(FONT_BDF_5X7.glyphs.len(), font.glyphs.len());
+    for (a, b) in FONT_BDF_5X7.glyphs.iter().zip(&font.glyphs) {
+        assert_eq!((a.c, a.width, a.rows), (b.c, b.width, b.rows.as_slice()));
+    }
+
+    // Malformed input is an error (not a panic)
+    let malformed = [
+        ("FONTBOUNDINGBOX 4 6 0 -2\n", ""),
+        ("FONT_ASCENT 4\n", "FONT_ASCENT 40\n"),
+        ("FONT_ASCENT 4\n", "FONT_ASCENT -2\n"),
+        ("FONT_ASCENT 4\n", "FONT_ASCENT x\n"),
+        ("FONT_ASCENT 4\n", "FONT_ASCENT 2147483647\n"),
+        ("BBX 3 3 1 0\n", "BBX 3 3\n"),
+        ("BBX 3 3 1 0\n", ""),
+        ("E0\nA0\nE0\n", "E0\nZZ\nE0\n"),
+        ("E0\nA0\nE0\n", "E0\nA\u{e9}\nE0\n"),
+        ("ENDCHAR\nSTARTCHAR A", "STARTCHAR A"),
+        ("C0\n40\nC0\nENDCHAR\n", "C0\n"),
+        ("BBX 1 2 0 4\n", "BBX 1 2000000000 0 4\n"),
+    ];
+    for (from, to) in malformed {
+        assert!(TEST_BDF.contains(from), "{:?}", from);
+        let bdf = TEST_BDF.replacen(from, to, 1);
+        assert!(BdfFont::parse(bdf.as_bytes(), &[]).is_err(), "{:?}", to);
+    }
+    assert!(BdfFont::parse(&[0xff, 0xfe], &[]).is_err());
+    // Out of range values are clipped
+    for (from, to) in [
+        ("BBX 3 3 1 0\n", "BBX 3 3 2147483647 2147483647\n"),
+        ("BBX 3 3 1 0\n", "BBX 3 3 -2147483648 -2147483648\n"),
+        ("BBX 3 3 1 0\n", "BBX -3 -3 1 0\n"),
+        ("DWIDTH 5 0\n", "DWIDTH 2147483647 0\n"),
+        (
+            "DWIDTH 5 0\nBBX 3 3 1 0\n",
+            "BBX 2147483647 3 2147483647 0\n",
+        ),
+    ] {
+        let bdf = TEST_BDF.replacen(from, to, 1);
+        let font = BdfFont::parse(bdf.as_bytes(), &[]).unwrap();
+        assert!(font.get('A').unwrap().width <= 32, "{:?}", to);
+    }
+}
+
+#[test]
+fn text_fixed() {
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.draw_str("Hi!", &FONT_8X8, rgb::RED, (0, 0));
+    assert_snapshot("text_fixed", &Frame::from_canvas(&matrix));
+}
+
+#[test]
+fn text_proportional() {
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.set_text_style(proportional());
+    matrix.draw_str("Hello", &FONT_8X8, rgb::GREEN, (0, 0));
+    assert_snapshot("text_proportional", &Frame::from_canvas(&matrix));
+}
+
+#[test]
+fn text_fonts() {
+    let mut matrix = Matrix1D::<4>::new();
+    matrix.set_text_style(proportional());
+    matrix.draw_str("57", &FONT_5X7, rgb::BLUE, (0, 0));
+    let x = matrix.measure_str("57", &FONT_5X7) + 2;
+    matrix.draw_str("Bé£", &FONT_BDF_5X7, Rgb::new(255, 128, 0), (x, 0));
+    assert_snapshot("text_fonts", &Frame::from_canvas(&matrix));
+}
+
+#[test]
+fn font_fallback() {
+    let replacement = Glyph::from_rows(8, REPLACEMENT_GLYPH.map(|row| row as u32));
+    // Latin-1, Greek, box drawing, block and hiragana sets
+    for c in ['A', 'é', '£', 'λ', 'Ω', '┼', '═', '█', '▀', 'あ', 'の'] {
+        let glyph = FONT_8X8.glyph(c);
+        assert!(
+            glyph.is_some_and(|g| g != replacement && g.columns().is_some()),
+            "{c}"
+        );
+        assert_eq!(glyph.map(|g| (g.width, g.height)), Some((8, 8)));
+    }
+    let block = FONT_8X8.glyph('█').unwrap();
+    assert!(block.rows[..8].iter().all(|&row| row == 0xff));
+    let upper = FONT_8X8.glyph('▀').unwrap();
+    assert_eq!(upper.rows[..8], [0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+
+    // Unmapped chars use the replacement glyph (or nothing if disabled)
+    assert_eq!(FONT_8X8.glyph('中'), Some(replacement));
+    assert_eq!(FONT_8X8.glyph('😀'), Some(replacement));
+    assert_eq!(Font8x8 { replacement: None }.glyph('中'), None);
+    assert_eq!(
+        Font8x8 { replacement: None }.glyph('é'),
+        FONT_8X8.glyph('é')
+    );
+
+    // Widths are measured per grapheme - combining marks and emoji
+    // modifiers don't add columns
+    let inked = |c| FONT_8X8.glyph(c).unwrap().columns().unwrap().1;
+    let mut matrix = Matrix1D::<2>::new();
+    for (s, width) in [
+        ("", 0),
+        ("±£", 16),
+        ("e\u{301}", 8),
+        ("e\u{301}x\u{308}\u{323}", 16),
+        ("👍🏽", 8),
+        ("a 中", 24),
+    ] {
+        assert_eq!(matrix.measure_str(s, &FONT_8X8), width, "{s}");
+    }
+    matrix.set_text_style(proportional());
+    for (s, width) in [
+        ("", 0),
+        ("±£", inked('±') + 1 + inked('£')),
+        ("e\u{301}", inked('e')),
+        ("e\u{301}x\u{308}\u{323}", inked('e') + 1 + inked('x')),
+        ("👍🏽", inked('中')),
+        ("a 中", inked('a') + 1 + 3 + 1 + inked('中')),
+    ] {
+        assert_eq!(matrix.measure_str(s, &FONT_8X8), width, "{s}");
+    }
+}
+
+#[test]
+fn bitmap_fonts() {
+    // Glyph pixels (advance x height) - '#' for set pixels
+    let pixels = |font: &dyn Font, c| -> Vec<String> {
+        let glyph = font.glyph(c).unwrap();
+        (0..glyph.height)
+            .map(|y| {
+                (0..glyph.width)
+                    .map(|x| if glyph.pixel(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    };
+    // 3x5 is uppercase only (symbols after 'z' still map)
+    let a = [".#..", "#.#.", "###.", "#.#.", "#.#."];
+    assert_eq!(pixels(&FONT_3X5, 'A'), a);
+    assert_eq!(pixels(&FONT_3X5, 'a'), a);
+    assert_eq!(
+        pixels(&FONT_3X5, '{'),
+        [".##.", ".#..", "#...", ".#..", ".##."]
+    );
+    assert_eq!(
+        pixels(&FONT_3X5, '~'),
+        ["....", "##..", ".##.", "....", "...."]
+    );
+    assert_eq!(FONT_3X5.glyph('\u{7f}'), None);
+    assert_eq!(FONT_3X5.glyph('é'), None);
+
+    // 4x6 has lowercase with descenders
+    assert_eq!(
+        pixels(&FONT_4X6, 'g'),
+        [".....", ".....", ".##..", "#.#..", ".##..", "##..."]
+    );
+    assert_ne!(FONT_4X6.glyph('g'), FONT_4X6.glyph('G'));
+    assert_eq!((FONT_4X6.height(), FONT_4X6.baseline()), (6, 5));
+
+    // Tall digits only has digits, ':', '.', '-' and ' '
+    assert_eq!(
+        pixels(&FONT_TALL_DIGITS, '1'),
+        [".#..", "##..", ".#..", ".#..", ".#..", ".#..", ".#..", "###."]
+    );
+    for c in " -.0123456789:".chars() {
+        assert!(FONT_TALL_DIGITS.glyph(c).is_some(), "{c}");
+    }
+    for c in "!/+,;A".chars() {
+        assert_eq!(FONT_TALL_DIGITS.glyph(c), None, "{c}");
+    }
+
+    // Scaled repeats each pixel
+    let scaled = Scaled::new(FONT_3X5, 2);
+    assert_eq!(
+        pixels(&scaled, 'A'),
+        [
+            "..##....", "..##....", "##..##..", "##..##..", "######..", "######..", "##..##..",
+            "##..##..", "##..##..", "##..##.."
+        ]
+    );
+    assert_eq!((scaled.height(), scaled.baseline()), (10, 10));
+    // Scale is clamped so glyphs fit in MAX_GLYPH_SIZE rows
+    for (font, scale, height) in [
+        (Scaled::new(&FONT_8X8 as &dyn Font, 5), 5, 32),
+        (Scaled::new(&FONT_5X7 as &dyn Font, 5), 5, 28),
+        (Scaled::new(&FONT_TALL_DIGITS as &dyn Font, 9), 9, 32),
+        (Scaled::new(&FONT_3X5 as &dyn Font, 0), 0, 5),
+    ] {
+        assert_eq!(font.height(), height, "scale {scale}");
+        assert_eq!(font.glyph('0').unwrap().height, height, "scale {scale}");
+    }
+
+    // Bold doubles pixels to the right and widens the advance
+    assert_eq!(
+        pixels(&Bold(FONT_3X5), 'A'),
+        [".##..", "####.", "####.", "####.", "####."]
+    );
+    assert_eq!(Bold(FONT_3X5).height(), 5);
+}
+
+#[test]
+fn text_aligned() {
+    for (name, align) in [
+        ("text_align_left", Align::Left),
+        ("text_align_center", Align::Center),
+        ("text_align_right", Align::Right),
+    ] {
+        let mut matrix = Matrix1D::<2>::new();
+        matrix.set_text_style(proportional());
+        matrix.draw_str_aligned("ab", &FONT_8X8, rgb::WHITE, 0, align);
+        assert_snapshot(name, &Frame::from_canvas(&matrix));
+    }
+}
+
+#[test]
+fn text_layout() {
+    // One pixel per grapheme so widths are easy to follow
+    let layout =
+        |s: &str, size| TextLayout::new(s, size, 8, |s: &str| s.graphemes(true).count() as i32);
+    let lines = |s: &str, width| layout(s, (width, 8)).lines;
+
+    // Wrap at word boundaries, keeping paragraphs and blank lines
+    assert_eq!(lines("the quick brown fox", 9), ["the quick", "brown fox"]);
+    assert_eq!(
+        lines("the quick brown fox", 8),
+        ["the", "quick", "brown", "fox"]
+    );
+    assert_eq!(lines("a  b\n\nc", 9), ["a b", "", "c"]);
+    // Long words are broken (last chunk continues with next word)
+    assert_eq!(lines("abcdefghij xy", 4), ["abcd", "efgh", "ij", "xy"]);
+    assert_eq!(lines("abcdefghi x", 4), ["abcd", "efgh", "i x"]);
+    // ...at grapheme boundaries
+    assert_eq!(
+        lines("e\u{301}e\u{301}e\u{301}", 2),
+        ["e\u{301}e\u{301}", "e\u{301}"]
+    );
+    assert_eq!(lines("abc", 0), ["a", "b", "c"]);
+
+    // Pages
+    let text = layout("one two three four five", (5, 16));
+    assert_eq!(
+        (text.lines_per_page, text.page_height(), text.pages()),
+        (2, 16, 3)
+    );
+    assert_eq!(text.page(0), ["one", "two"]);
+    assert_eq!(text.page(2), ["five"]);
+    assert!(text.page(3).is_empty());
+    assert_eq!(layout("", (5, 16)).pages(), 1);
+    assert_eq!(layout("a b", (5, 4)).lines_per_page, 1);
+
+    // Flip holds each page
+    let offsets = |text: &TextLayout, mode, hold| text.page_offsets(mode, hold).collect::<Vec<_>>();
+    assert_eq!(offsets(&text, PageMode::Flip, 2), [0, 0, 16, 16, 32, 32]);
+    assert_eq!(offsets(&text, PageMode::Flip, 0), [0, 16, 32]);
+    // Scroll moves up a row every step ticks and stops at the last page
+    let scroll = offsets(&text, PageMode::Scroll(2), 3);
+    assert_eq!(scroll[..6], [0, 0, 0, 1, 1, 2]);
+    assert_eq!(scroll.len(), 3 * 3 + 2 * 15 * 2);
+    assert!(scroll.windows(2).all(|w| w[1] >= w[0]));
+    assert_eq!(scroll[scroll.len() - 4..], [31, 32, 32, 32]);
+    assert_eq!(offsets(&text, PageMode::Scroll(0), 1).len(), 3 + 2 * 15);
+    let single = layout("one", (5, 16));
+    assert_eq!(offsets(&single, PageMode::Scroll(1), 2), [0, 0]);
+}
+
+#[test]
+fn scroll_positions() {
+    let msg = "Scroll";
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.set_text_style(proportional());
+    let positions: Vec<i32> = matrix.scroll_iter(msg, &FONT_8X8).collect();
+    // Start (off right), part way in, fully visible, leaving
+    for i in [0, 8, 16, 40] {
+        let x = positions[i];
+        matrix.clear();
+        matrix.draw_str(msg, &FONT_8X8, rgb::RED, (x, 0));
+        assert_snapshot(&format!("scroll_{:02}", i), &Frame::from_canvas(&matrix));
+    }
+    assert_eq!(positions.last(), Some(&-matrix.measure_str(msg, &FONT_8X8)));
+}
+
+#[test]
+fn orientations() {
+    for o in ORIENTATIONS {
+        let mut matrix = Matrix1D::<2>::from_panels([Panel::new(o), Panel::new(o)]);
+        // Asymmetric pattern so each orientation maps differently
+        matrix.draw_str("F", &FONT_8X8, rgb::GREEN, (0, 0));
+        matrix.set((0, 0), rgb::RED);
+        matrix.set((15, 7), rgb::BLUE);
+        let frame = Frame::from_leds(matrix.iter(), 2);
+        assert_snapshot(&format!("orientation_{:?}", o).to_lowercase(), &frame);
+    }
+}
+
+#[test]
+fn scroll_modes() {
+    let msg = "Hi";
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.set_text_style(proportional());
+    let width = matrix.measure_str(msg, &FONT_8X8);
+    let xs = |mode, lead, trail, pause| -> Vec<(i32, i32)> {
+        let options = ScrollOptions {
+            mode,
+            lead,
+            trail,
+            pause,
+        };
+        matrix.scroll_positions(msg, &FONT_8X8, options).collect()
+    };
+    // Default matches scroll_iter
+    let left = xs(ScrollMode::Left, 0, 0, 0);
+    let expected: Vec<_> = matrix.scroll_iter(msg, &FONT_8X8).map(|x| (x, 0)).collect();
+    assert_eq!(left, expected);
+    // Gaps and pauses
+    let left = xs(ScrollMode::Left, 3, 2, 4);
+    assert_eq!(left[..5], [(18, 0); 5]);
+    assert_eq!(left[left.len() - 5..], [(-width - 2, 0); 5]);
+    let right = xs(ScrollMode::Right, 0, 0, 0);
+    assert_eq!(right.first(), Some(&(1 - width, 0)));
+    assert_eq!(right.last(), Some(&(16, 0)));
+    // Vertical scroll is centred
+    let up = xs(ScrollMode::Up, 0, 0, 0);
+    let x = (16 - width) / 2;
+    assert_eq!(up.first(), Some(&(x, 7)));
+    assert_eq!(up.last(), Some(&(x, -8)));
+    let down = xs(ScrollMode::Down, 1, 0, 0);
+    assert_eq!(down.first(), Some(&(x, -8)));
+    assert_eq!(down.last(), Some(&(x, 8)));
+    // Text fits so bounce holds still
+    assert_eq!(xs(ScrollMode::Bounce, 0, 0, 2), [(0, 0); 5]);
+}
+
+#[test]
+fn scroll_bounce() {
+    let msg = "Bounce";
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.set_text_style(proportional());
+    let width = matrix.measure_str(msg, &FONT_8X8);
+    let options = ScrollOptions {
+        mode: ScrollMode::Bounce,
+        lead: 1,
+        trail: 1,
+        pause: 2,
+    };
+    let xs: Vec<i32> = matrix
+        .scroll_positions(msg, &FONT_8X8, options)
+        .map(|(x, _)| x)
+        .collect();
+    let end = 16 - width - 1;
+    let mut expected = vec![1, 1];
+    expected.extend((end..=1).rev());
+    expected.extend([end, end]);
+    expected.extend(end + 1..1);
+    assert_eq!(xs, expected);
+    // Return leg stops short of start so repeating doesn't repeat a position
+    assert_eq!(xs.last(), Some(&0));
+}
+
+#[test]
+fn smooth_scroll() {
+    let msg = "Hi";
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.set_text_style(proportional());
+    // Whole pixel positions match draw_str
+    let mut expected = Matrix1D::<2>::new();
+    expected.set_text_style(proportional());
+    expected.draw_str(msg, &FONT_8X8, rgb::WHITE, (3, 0));
+    matrix.draw_str_smooth(msg, &FONT_8X8, rgb::WHITE, (3.0, 0.0));
+    assert_eq!(Frame::from_canvas(&matrix), Frame::from_canvas(&expected));
+    // Fractional positions blend between neighbouring pixels
+    for (name, p) in [
+        ("smooth_x_25", (3.25, 0.0)),
+        ("smooth_x_50", (3.5, 0.0)),
+        ("smooth_xy_50", (3.5, 0.5)),
+    ] {
+        matrix.clear();
+        matrix.draw_str_smooth(msg, &FONT_8X8, rgb::WHITE, p);
+        assert_snapshot(name, &Frame::from_canvas(&matrix));
+    }
+    // 40 px/s with 25ms ticks moves one pixel per tick
+    let path = matrix.scroll_positions(msg, &FONT_8X8, ScrollOptions::default());
+    let steps = path.len();
+    let mut scroll = SmoothScroll::new(path, 40.0);
+    assert_eq!(scroll.position(), Some((15.0, 0.0)));
+    assert_eq!(scroll.tick(25), Some((14.0, 0.0)));
+    // 10 px/s with 25ms ticks moves a quarter pixel per tick
+    let path = matrix.scroll_positions(msg, &FONT_8X8, ScrollOptions::default());
+    let mut scroll = SmoothScroll::new(path, 10.0);
+    assert_eq!(scroll.tick(25), Some((14.75, 0.0)));
+    assert_eq!(scroll.tick(50), Some((14.25, 0.0)));
+    // Finishes after last step (quarter pixel steps so far)
+    let mut quarters = 3;
+    while scroll.tick(25).is_some() {
+        quarters += 1;
+    }
+    assert_eq!(quarters, (steps - 1) * 4);
+    assert!(scroll.is_finished());
+}
+
+#[test]
+fn dirty_regions() {
+    let mut matrix = Matrix1D::<2>::new();
+    assert_eq!(matrix.dirty(), None);
+    let blank = matrix.frame_hash();
+    matrix.set((3, 2), rgb::RED);
+    matrix.set((9, 5), rgb::RED);
+    assert_eq!(matrix.take_dirty(), Some(((3, 2), (10, 6))));
+    assert_eq!(matrix.dirty(), None);
+    let hash = matrix.frame_hash();
+    assert_ne!(hash, blank);
+    // Writing same value isn't a change
+    matrix.set((3, 2), rgb::RED);
+    assert_eq!(matrix.dirty(), None);
+    // Clear only marks panels that had pixels set
+    matrix.clear();
+    assert_eq!(matrix.take_dirty(), Some(((0, 0), (16, 8))));
+    assert_eq!(matrix.frame_hash(), blank);
+    matrix.set((12, 1), rgb::BLUE);
+    matrix.take_dirty();
+    matrix.clear();
+    assert_eq!(matrix.take_dirty(), Some(((8, 0), (16, 8))));
+    // Redrawing identical frame gives same hash
+    matrix.draw_str("Hi", &FONT_8X8, rgb::GREEN, (0, 0));
+    let hash = matrix.frame_hash();
+    matrix.clear();
+    matrix.draw_str("Hi", &FONT_8X8, rgb::GREEN, (0, 0));
+    assert_eq!(matrix.frame_hash(), hash);
+}
+
+#[test]
+fn effects() {
+    let registry = EffectRegistry::default();
+    for name in registry.names() {
+        let mut effect = registry.create(name).unwrap();
+        assert_eq!(effect.name(), name);
+        let params = effect.defaults();
+        let mut frame = Frame::new((16, 8));
+        // 2s at message thread tick rate
+        for t in (0..=2000).step_by(25) {
+            effect.render(t, &params, &mut frame);
+        }
+        assert_snapshot(&format!("effect_{}", name.replace('-', "_")), &frame);
+    }
+    assert!(registry.create("Fire").is_some());
+    assert!(registry.create("unknown").is_none());
+}
+
+#[test]
+fn effect_params() {
+    let mut params = EffectParams::default();
+    params.set("speed", "2.5").unwrap();
+    params.set("density", "1.5").unwrap();
+    params.set("palette", "#00ff80").unwrap();
+    assert_eq!(params.speed, 2.5);
+    assert_eq!(params.density, 1.0);
+    assert_eq!(params.palette, Palette::Mono(Rgb::new(0, 255, 128)));
+    params.set("palette", "Fire").unwrap();
+    assert_eq!(params.palette, Palette::Fire);
+    assert!(params.set("palette", "plaid").is_err());
+    // Non-hex or non-ASCII colours are errors (not a slicing panic)
+    for palette in ["#aébbb", "#+12345", "#12345", "#gg0000"] {
+        assert!(params.set("palette", palette).is_err(), "{}", palette);
+    }
+    assert!(params.set("colour", "1").is_err());
+    assert!(params.set("speed", "fast").is_err());
+    // Gradient ends
+    assert_eq!(Palette::Fire.colour(0.0), rgb::OFF);
+    assert_eq!(Palette::Mono(rgb::RED).colour(1.0), rgb::RED);
+    assert_eq!(Palette::Ocean.cycle(0.25), Palette::Ocean.colour(0.5));
+}
+
+#[test]
+fn sprites() {
+    let colourmap = [('R', rgb::RED), ('G', rgb::GREEN), ('B', rgb::BLUE)];
+    let blit = |sprite: &Sprite, background: &[&str], p, opts| {
+        let mut frame = from_map(background);
+        sprite.blit(&mut frame, p, opts);
+        to_map(&frame)
+    };
+
+    // Char-art - unmapped chars and short rows are transparent
+    let art = Sprite::from_char_art(&["RG", "Bx", "R"], &colourmap);
+    assert_eq!(art.size(), (2, 3));
+    assert_eq!(art.pixel((0, 0)), Some((rgb::RED, 255)));
+    assert_eq!(art.pixel((1, 1)).map(|(_, a)| a), Some(0));
+    assert_eq!(art.pixel((1, 2)).map(|(_, a)| a), Some(0));
+    assert_eq!(art.pixel((2, 0)), None);
+    assert_eq!(Sprite::from_char_art(&[], &colourmap).size(), (0, 0));
+    assert_eq!(
+        *Sprite::from_char_art(&["RGB"], &colourmap).transparency(),
+        Transparency::Opaque
+    );
+    // Pixel data must match size
+    let pixels = |n| vec![rgb::RED; n];
+    assert!(Sprite::new((2, 2), pixels(3), Transparency::Opaque).is_err());
+    assert!(Sprite::new((2, 2), pixels(4), Transparency::Alpha(vec![255; 3])).is_err());
+    assert!(Sprite::new((-2, 2), pixels(0), Transparency::Opaque).is_ok());
+    assert!(Sprite::new((2, 2), pixels(4), Transparency::Key(rgb::OFF)).is_ok());
+
+    // Transparency modes
+    let pair = |t| Sprite::new((2, 1), vec![rgb::OFF, rgb::RED], t).unwrap();
+    let background = ["WWW"];
+    assert_eq!(
+        blit(
+            &pair(Transparency::Opaque),
+            &background,
+            (0, 0),
+            Blit::default()
+        ),
+        [".RW"]
+    );
+    assert_eq!(
+        blit(
+            &pair(Transparency::Key(rgb::OFF)),
+            &background,
+            (0, 0),
+            Blit::default()
+        ),
+        ["WRW"]
+    );
+    assert_eq!(
+        blit(
+            &pair(Transparency::Key(rgb::RED)),
+            &background,
+            (0, 0),
+            Blit::default()
+        ),
+        [".WW"]
+    );
+    let alpha = pair(Transparency::Alpha(vec![0, 128]));
+    let mut frame = from_map(&background);
+    alpha.blit(&mut frame, (1, 0), Blit::default());
+    assert_eq!(frame.get((1, 0)), Some(rgb::WHITE));
+    assert_eq!(
+        frame.get((2, 0)),
+        Some(rgb::WHITE.lerp(rgb::RED, 128.0 / 255.0))
+    );
+
+    // Flips, offset and clipping
+    let art = Sprite::from_char_art(&["RG", "B."], &colourmap);
+    let flip = |flip_x, flip_y| Blit {
+        flip_x,
+        flip_y,
+        ..Default::default()
+    };
+    let background = ["WWWW", "WWWW", "WWWW"];
+    assert_eq!(
+        blit(&art, &background, (1, 1), flip(false, false)),
+        ["WWWW", "WRGW", "WBWW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (1, 1), flip(true, false)),
+        ["WWWW", "WGRW", "WWBW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (1, 1), flip(false, true)),
+        ["WWWW", "WBWW", "WRGW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (1, 1), flip(true, true)),
+        ["WWWW", "WWBW", "WGRW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (-1, -1), flip(false, false)),
+        ["WWWW", "WWWW", "WWWW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (-1, 2), flip(false, false)),
+        ["WWWW", "WWWW", "GWWW"]
+    );
+    assert_eq!(
+        blit(&art, &background, (3, -1), flip(true, true)),
+        ["WWWG", "WWWW", "WWWW"]
+    );
+    for p in [
+        (i32::MIN, 0),
+        (0, i32::MIN),
+        (i32::MAX, 0),
+        (0, i32::MAX),
+        (i32::MIN, i32::MAX),
+    ] {
+        assert_eq!(blit(&art, &background, p, Blit::default()), background);
+    }
+
+    // Tint is multiplied with sprite colour
+    let white = Sprite::from_char_art(&["WR"], &[('W', rgb::WHITE), ('R', rgb::RED)]);
+    let mut frame = Frame::new((2, 1));
+    let tint = Rgb::new(255, 128, 0);
+    white.blit(
+        &mut frame,
+        (0, 0),
+        Blit {
+            tint: Some(tint),
+            ..Default::default()
+        },
+    );
+    assert_eq!(frame.get((0, 0)), Some(tint));
+    assert_eq!(frame.get((1, 0)), Some(rgb::RED));
+
+    // Pixels can be changed in place (switching to per pixel alpha)
+    let mut sprite = Sprite::from_char_art(&["RG"], &colourmap);
+    sprite.set_pixel((1, 0), (rgb::BLUE, 255));
+    assert_eq!(*sprite.transparency(), Transparency::Opaque);
+    sprite.set_pixel((0, 0), (rgb::RED, 64));
+    sprite.set_pixel((5, 0), (rgb::RED, 0));
+    assert_eq!(*sprite.transparency(), Transparency::Alpha(vec![64, 255]));
+    assert_eq!(sprite.pixel((1, 0)), Some((rgb::BLUE, 255)));
+    let mut keyed = pair(Transparency::Key(rgb::OFF));
+    keyed.set_pixel((1, 0), (rgb::OFF, 255));
+    assert_eq!(keyed.pixel((0, 0)), Some((rgb::OFF, 0)));
+    assert_eq!(keyed.pixel((1, 0)), Some((rgb::OFF, 255)));
+
+    // Matrix bitmap leaves unmapped pixels unchanged
+    let mut led_matrix = Matrix::new(matrix::Orientation::North);
+    led_matrix.fill(rgb::WHITE);
+    led_matrix.draw_bitmap(&["R.G"], &colourmap, 1);
+    let row: Vec<_> = (0..4).map(|x| Canvas::get(&led_matrix, (x, 0))).collect();
+    assert_eq!(
+        row,
+        [rgb::WHITE, rgb::RED, rgb::WHITE, rgb::GREEN].map(Some)
+    );
+}
+
+#[test]
+fn layers() {
+    let mut stack = LayerStack::<2>::new();
+    let mut bottom = Layer::new(0);
+    bottom
+        .matrix
+        .fill_rect((0, 0), (16, 8), Paint::Solid(rgb::RED));
+    let bottom = stack.push(bottom);
+    // Layers are opaque by default (black covers layers below)
+    let top = stack.push(Layer::new(1));
+    let mut out = Matrix1D::<2>::new();
+    stack.flatten(&mut out);
+    assert_eq!(out.get((5, 5)), Some(rgb::OFF));
+
+    // Key colour is transparent
+    let layer = stack.get_mut(top).unwrap();
+    layer.key = Some(rgb::OFF);
+    layer.matrix.set((1, 1), rgb::BLUE);
+    layer.offset = (2, 0);
+    stack.flatten(&mut out);
+    assert_eq!(out.get((5, 5)), Some(rgb::RED));
+    assert_eq!(out.get((1, 1)), Some(rgb::RED));
+    assert_eq!(out.get((3, 1)), Some(rgb::BLUE));
+
+    // Opacity blends with layers below and hidden layers are skipped
+    stack.get_mut(top).unwrap().opacity = 0.5;
+    stack.flatten(&mut out);
+    assert_eq!(out.get((3, 1)), Some(rgb::RED.lerp(rgb::BLUE, 0.5)));
+    stack.get_mut(bottom).unwrap().visible = false;
+    stack.flatten(&mut out);
+    assert_eq!(out.get((5, 5)), Some(rgb::OFF));
+    stack.get_mut(bottom).unwrap().visible = true;
+
+    // Unchanged frame leaves output clean
+    stack.flatten(&mut out);
+    out.take_dirty();
+    stack.flatten(&mut out);
+    assert_eq!(out.take_dirty(), None);
+
+    // Kept in z order (same z in insertion order) and reordered by set_z
+    let middle = stack.push(Layer::new(0));
+    let order = |stack: &LayerStack<2>| stack.iter().map(|(id, _)| id).collect::<Vec<_>>();
+    assert_eq!(order(&stack), [bottom, middle, top]);
+    stack.set_z(bottom, 5);
+    assert_eq!(order(&stack), [middle, top, bottom]);
+    assert_eq!(stack.get(bottom).map(|l| l.z()), Some(5));
+    stack.flatten(&mut out);
+    assert_eq!(out.get((5, 5)), Some(rgb::RED));
+    assert!(stack.remove(middle).is_some());
+    assert!(stack.remove(middle).is_none());
+    assert_eq!(stack.len(), 2);
+}
+
+#[test]
+fn animations() {
+    let read = |name: &str| {
+        fs::read(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../images")
+                .join(name),
+        )
+        .unwrap()
+    };
+    let decode = |data: Vec<u8>| {
+        let mut source = animation::open(Cursor::new(data)).unwrap();
+        let mut frames = vec![];
+        while let Some(delay_ms) = source.next_frame().unwrap() {
+            let image = source.screen().clone();
+            frames.push(animation::Frame { image, delay_ms });
+        }
+        (frames, source.loops())
+    };
+
+    // Sample C3A is the encoded GIF
+    let (frames, loops) = decode(read("spinner.gif"));
+    assert_eq!((frames.len(), loops), (17, None));
+    let c3a = animation::encode_c3a(&frames, 0).unwrap();
+    assert_eq!(c3a, read("spinner.c3a"));
+    assert_eq!(decode(c3a), (frames, None));
+
+    // Round trip with pixels becoming transparent (full frame + Background
+    // dispose), unchanged frames and loop count
+    let art = |rows: &[&str], delay_ms| animation::Frame {
+        image: Sprite::from_char_art(rows, &[('R', rgb::RED), ('B', rgb::BLUE)]),
+        delay_ms,
+    };
+    let frames = [
+        art(&["R...", ".B.."], 10),
+        art(&["RR..", ".B.."], 20),
+        art(&["....", ".BB."], 30),
+        art(&["....", ".BB."], 40),
+        art(&["...R", "BBBB"], 50),
+    ];
+    let c3a = animation::encode_c3a(&frames, 3).unwrap();
+    assert_eq!(decode(c3a.clone()), (frames.to_vec(), Some(3)));
+    // Rewind restarts from first frame
+    let mut source = animation::open(Cursor::new(c3a.clone())).unwrap();
+    for _ in 0..2 {
+        for frame in &frames {
+            assert_eq!(source.next_frame().unwrap(), Some(frame.delay_ms));
+            assert_eq!(source.screen(), &frame.image);
+        }
+        assert_eq!(source.next_frame().unwrap(), None);
+        source.rewind().unwrap();
+    }
+    // Truncated or corrupt data is an error (not a panic)
+    for len in 0..c3a.len() {
+        if let Ok(mut source) = animation::open(Cursor::new(c3a[..len].to_vec())) {
+            while let Ok(Some(_)) = source.next_frame() {}
+        }
+    }
+    assert!(animation::encode_c3a(&[], 0).is_err());
+    assert!(animation::encode_c3a(&[frames[0].clone(), art(&["R"], 0)], 0).is_err());
+
+    // Player follows frame delays and stops after loop count
+    let mut player = Player::new(animation::open(Cursor::new(c3a)).unwrap());
+    assert_eq!(player.tick(0).unwrap(), Some(&frames[0].image));
+    assert_eq!(player.tick(9).unwrap(), None);
+    assert_eq!(player.tick(1).unwrap(), Some(&frames[1].image));
+    // Late ticks carry over (but don't skip frames)
+    assert_eq!(player.tick(25).unwrap(), Some(&frames[2].image));
+    assert_eq!(player.tick(25).unwrap(), Some(&frames[3].image));
+    let mut shown = 4;
+    while !player.is_finished() {
+        if player.tick(1000).unwrap().is_some() {
+            shown += 1;
+        }
+    }
+    assert_eq!((shown, player.played()), (15, 3));
+}
+
+#[test]
+fn images() {
+    use image::{Filter, Quantise, ScaleMode};
+    let read = |name: &str| {
+        fs::read(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../images")
+                .join(name),
+        )
+        .unwrap()
+    };
+
+    // Sample images are the same logo (PNG/GIF have a transparent background)
+    let logo = image::decode(&read("logo.ppm")).unwrap();
+    assert_eq!(logo.size(), (16, 8));
+    for name in ["logo.bmp", "logo.png", "logo.gif"] {
+        let data = read(name);
+        let decoded = image::decode(&data).unwrap();
+        assert_eq!(decoded.size(), logo.size(), "{}", name);
+        for y in 0..8 {
+            for x in 0..16 {
+                let (rgb, alpha) = decoded.pixel((x, y)).unwrap();
+                let opaque = name == "logo.bmp" || rgb != rgb::OFF;
+                assert_eq!(Some(rgb), logo.pixel((x, y)).map(|(rgb, _)| rgb));
+                assert_eq!(alpha, if opaque { 255 } else { 0 }, "{} {:?}", name, (x, y));
+            }
+        }
+        // Truncated files are an error (not a panic)
+        for len in 0..data.len() {
+            let _ = image::decode(&data[..len]);
+        }
+    }
+    let mut frame = Frame::new((16, 8));
+    image::draw_image(&mut frame, &logo, ScaleMode::Fit, Filter::Nearest, None);
+    assert_snapshot("image_logo", &frame);
+
+    // Built-in images are embedded by name
+    assert_eq!(image::builtin("logo"), Some(&read("logo.png")[..]));
+    assert_eq!(image::builtin("missing"), None);
+
+    // Scale modes (logo is 16x8 so fill/crop to 8x8 take the middle columns)
+    let scaled = |mode, filter| image::scale_to(&logo, (8, 8), mode, filter);
+    let fit = scaled(ScaleMode::Fit, Filter::Box);
+    for y in 0..8 {
+        let alpha = if (2..6).contains(&y) { 255 } else { 0 };
+        assert_eq!(fit.pixel((0, y)).map(|(_, a)| a), Some(alpha));
+    }
+    let stretch = scaled(ScaleMode::Stretch, Filter::Nearest);
+    assert_eq!(stretch.size(), (8, 8));
+    assert!((0..8).all(|y| stretch.pixel((0, y)).is_some_and(|(_, a)| a == 255)));
+    for mode in [ScaleMode::Fill, ScaleMode::Crop] {
+        let cropped = scaled(mode, Filter::Nearest);
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(cropped.pixel((x, y)), logo.pixel((x + 4, y)), "{:?}", mode);
+            }
+        }
+    }
+    for (name, mode, filter) in [
+        ("image_fit", ScaleMode::Fit, Filter::Nearest),
+        ("image_fit_box", ScaleMode::Fit, Filter::Box),
+        ("image_stretch", ScaleMode::Stretch, Filter::Nearest),
+    ] {
+        let mut frame = Frame::new((8, 8));
+        image::draw_image(&mut frame, &logo, mode, filter, None);
+        assert_snapshot(name, &frame);
+    }
+    // Box filter averages (weighted by alpha)
+    let pair = |a| {
+        Sprite::from_fn((2, 1), |(x, _)| {
+            if x == 0 {
+                (rgb::RED, 255)
+            } else {
+                (rgb::BLUE, a)
+            }
+        })
+    };
+    let boxed = |a| image::resize(&pair(a), (1, 1), Filter::Box).pixel((0, 0));
+    assert_eq!(boxed(255), Some((Rgb::new(127, 0, 127), 255)));
+    assert_eq!(boxed(0), Some((rgb::RED, 127)));
+    assert_eq!(
+        image::resize(&pair(255), (1, 1), Filter::Nearest).pixel((0, 0)),
+        Some((rgb::BLUE, 255))
+    );
+
+    // Quantise snaps to levels after scaling by brightness
+    let q = |brightness, levels| Quantise { brightness, levels };
+    assert_eq!(q(1.0, 2).apply(Rgb::new(200, 100, 0)), Rgb::new(255, 0, 0));
+    assert_eq!(
+        q(1.0, 3).apply(Rgb::new(200, 100, 0)),
+        Rgb::new(255, 128, 0)
+    );
+    assert_eq!(
+        q(0.1, 255).apply(Rgb::new(255, 128, 1)),
+        Rgb::new(26, 13, 0)
+    );
+    assert_eq!(q(0.0, 255).apply(rgb::WHITE), rgb::OFF);
+    let quantised = image::quantise(&logo, q(0.5, 2));
+    for y in 0..8 {
+        for x in 0..16 {
+            let (rgb, alpha) = quantised.pixel((x, y)).unwrap();
+            assert!([0, 128].contains(&rgb.r()) && [0, 128].contains(&rgb.g()));
+            assert_eq!(alpha, 255);
+        }
+    }
+
+    // Sizes and offsets in headers are checked
+    assert!(image::decode(b"P6\n4294967296 4294967296\n255\n").is_err());
+    assert!(image::decode(b"P3\n99999999999999999999 1\n255\n").is_err());
+    assert!(image::decode(b"P3 2 1 255 255 0 0 0 0 255").is_ok());
+    let bmp = read("logo.bmp");
+    let patch = |at: usize, value: u32| {
+        let mut bmp = bmp.clone();
+        bmp[at..at + 4].copy_from_slice(&value.to_le_bytes());
+        image::decode(&bmp)
+    };
+    assert!(patch(10, u32::MAX).is_err()); // Pixel data offset
+    assert!(patch(18, 0x8000_0000).is_err()); // Width
+    assert!(patch(22, 0x8000_0000).is_err()); // Height
+    assert!(patch(22, (-8_i32) as u32).is_ok()); // Top down
+    let mut paletted = bmp.clone();
+    paletted[28] = 8;
+    for header in [40, u32::MAX - 13, u32::MAX] {
+        paletted[14..18].copy_from_slice(&header.to_le_bytes());
+        assert!(image::decode(&paletted).is_err());
+    }
+}
+
+#[test]
+fn primitives() {
+    let draw = |size, f: &dyn Fn(&mut Frame)| {
+        let mut frame = Frame::new(size);
+        f(&mut frame);
+        to_map(&frame)
+    };
+    let white = Paint::Solid(rgb::WHITE);
+    let rgb_palette = [rgb::RED, rgb::GREEN, rgb::BLUE];
+    let red_blue = [rgb::RED, rgb::BLUE];
+
+    // Lines (palette position is kept when clipped)
+    assert_eq!(
+        draw((6, 4), &|f| f.line((-2, -2), (3, 3), white)),
+        ["W.....", ".W....", "..W...", "...W.."]
+    );
+    assert_eq!(
+        draw((6, 1), &|f| f.line((-5, -1), (10, -1), white)),
+        ["......"]
+    );
+    assert_eq!(
+        draw((6, 1), &|f| f.line(
+            (0, 0),
+            (4, 0),
+            Paint::Palette(&red_blue)
+        )),
+        ["RRBBB."]
+    );
+    assert_eq!(
+        draw((6, 1), &|f| f.line(
+            (-4, 0),
+            (4, 0),
+            Paint::Palette(&red_blue)
+        )),
+        ["BBBBB."]
+    );
+    // Anti-aliased end points cover half a pixel
+    assert_eq!(
+        draw((6, 3), &|f| f.line_aa((0.0, 1.0), (4.0, 1.0), white)),
+        ["......", "#WWW#.", "......"]
+    );
+    assert_eq!(
+        draw((6, 3), &|f| f.line_aa((-10.0, 1.0), (20.0, 1.0), white)),
+        ["......", "WWWWWW", "......"]
+    );
+    assert_eq!(
+        draw((4, 4), &|f| f.line_aa((1.0, -3.0), (1.0, 9.0), white)),
+        [".W..", ".W..", ".W..", ".W.."]
+    );
+
+    // Rectangles
+    assert_eq!(
+        draw((6, 5), &|f| f.rect((1, 1), (5, 4), white)),
+        ["......", ".WWWW.", ".W..W.", ".WWWW.", "......"]
+    );
+    assert_eq!(
+        draw((6, 3), &|f| f.rect((-1, -1), (3, 3), white)),
+        ["..W...", "..W...", "WWW..."]
+    );
+    assert_eq!(
+        draw((3, 3), &|f| f.rect((-100, -100), (-50, 50), white)),
+        ["...", "...", "..."]
+    );
+    assert_eq!(
+        draw((3, 3), &|f| f.fill_rect(
+            (0, -1),
+            (2, 2),
+            Paint::Palette(&rgb_palette)
+        )),
+        ["GG.", "BB.", "..."]
+    );
+
+    // Circles and ellipses
+    assert_eq!(
+        draw((7, 7), &|f| f.circle((3, 3), 2, white)),
+        [".......", "..WWW..", ".W...W.", ".W...W.", ".W...W.", "..WWW..", "......."]
+    );
+    assert_eq!(
+        draw((4, 4), &|f| f.circle((0, 0), 3, white)),
+        ["...W", "...W", "..W.", "WW.."]
+    );
+    assert_eq!(
+        draw((7, 7), &|f| f.fill_circle((3, 3), 2, white)),
+        [".......", "..WWW..", ".WWWWW.", ".WWWWW.", ".WWWWW.", "..WWW..", "......."]
+    );
+    assert_eq!(
+        draw((9, 5), &|f| f.ellipse((4, 2), (3, 1), white)),
+        [
+            ".........",
+            "..WWWWW..",
+            ".W.....W.",
+            "..WWWWW..",
+            "........."
+        ]
+    );
+    assert_eq!(
+        draw((9, 5), &|f| f.fill_ellipse(
+            (4, 2),
+            (3, 2),
+            Paint::Palette(&rgb_palette)
+        )),
+        [
+            "...RRR...",
+            "..GGGGG..",
+            ".GGGGGGG.",
+            "..BBBBB..",
+            "...BBB..."
+        ]
+    );
+
+    // Polygons
+    let triangle = [(0, 0), (5, 0), (0, 5)];
+    assert_eq!(
+        draw((7, 6), &|f| f.polygon(&triangle, white)),
+        ["WWWWWW.", "W...W..", "W..W...", "W.W....", "WW.....", "W......"]
+    );
+    assert_eq!(
+        draw((7, 6), &|f| f.fill_polygon(&triangle, white)),
+        ["WWWWWW.", "WWWWW..", "WWWW...", "WWW....", "WW.....", "W......"]
+    );
+    assert_eq!(
+        draw((5, 3), &|f| f
+            .fill_polygon(&[(-3, 2), (2, -3), (7, 2), (2, 7)], white)),
+        ["WWWWW", "WWWWW", "WWWWW"]
+    );
+
+    // Flood fill stops at boundary
+    let flood = |seed, rgb| {
+        draw((6, 5), &move |f| {
+            f.rect((0, 0), (4, 4), white);
+            f.flood_fill(seed, Paint::Solid(rgb));
+        })
+    };
+    assert_eq!(
+        flood((1, 1), rgb::RED),
+        ["WWWW..", "WRRW..", "WRRW..", "WWWW..", "......"]
+    );
+    assert_eq!(
+        flood((5, 0), rgb::BLUE),
+        ["WWWWBB", "W..WBB", "W..WBB", "WWWWBB", "BBBBBB"]
+    );
+    assert_eq!(flood((-1, 0), rgb::BLUE), flood((9, 9), rgb::RED));
+
+    // Extreme co-ordinates are clipped without overflowing
+    let (min, max) = (i32::MIN, i32::MAX);
+    let empty = ["....", "....", "...."];
+    let full = ["WWWW", "WWWW", "WWWW"];
+    assert_eq!(
+        draw((4, 3), &|f| f.line((min, 1), (max, 1), white)),
+        ["....", "WWWW", "...."]
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.line(
+            (0, min),
+            (0, max),
+            Paint::Palette(&red_blue)
+        )),
+        ["B...", "B...", "B..."]
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.line((min, min), (max, max), white)),
+        ["W...", ".W..", "..W."]
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.rect((min, min), (max, max), white)),
+        empty
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.rect((1, min), (3, max), white)),
+        [".WW.", ".WW.", ".WW."]
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.fill_rect((min, min), (max, max), white)),
+        full
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.fill_rect((2, 0), (min, max), white)),
+        empty
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.rect((max, max), (min, min), white)),
+        empty
+    );
+    assert_eq!(draw((4, 3), &|f| f.ellipse((max, 1), (3, 0), white)), empty);
+    assert_eq!(
+        draw((4, 3), &|f| f.fill_ellipse((1, max), (2, 2), white)),
+        empty
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.fill_circle((min, min), 3, white)),
+        empty
+    );
+    assert_eq!(
+        draw((4, 3), &|f| f.fill_polygon(
+            &[(min, min), (max, min), (max, max), (min, max)],
+            white
+        )),
+        full
+    );
+    let shifted = |g| {
+        let mut frame = from_map(&["RG", "BW"]);
+        frame.geometry((0, 0), (2, 2), &[g]);
+        to_map(&frame)
+    };
+    assert_eq!(shifted(Geometry::Shift(min, max)), ["..", ".."]);
+    assert_eq!(shifted(Geometry::Roll(max, min)), ["GR", "WB"]);
+
+    // Blend paints each pixel once (outlines overlap at vertices and fills)
+    let dim = Rgb::new(100, 0, 0);
+    let add = Paint::Blend(dim, BlendMode::Add);
+    let shapes: [&dyn Fn(&mut Frame); 5] = [
+        &|f| f.polygon(&triangle, add),
+        &|f| f.fill_polygon(&triangle, add),
+        &|f| f.rect((0, 0), (1, 6), add),
+        &|f| f.fill_circle((2, 2), 3, add),
+        &|f| f.flood_fill((0, 0), add),
+    ];
+    for shape in shapes {
+        let mut frame = Frame::new((7, 6));
+        shape(&mut frame);
+        for y in 0..6 {
+            for x in 0..7 {
+                let rgb = frame.get((x, y)).unwrap();
+                assert!(rgb == rgb::OFF || rgb == dim, "{:?} at {:?}", rgb, (x, y));
+            }
+        }
+    }
+    // Blend mixes with existing pixels
+    let mut frame = from_map(&["RGB."]);
+    frame.fill_rect((0, 0), (4, 1), Paint::Blend(rgb::BLUE, BlendMode::Add));
+    assert_eq!(frame.get((0, 0)), Some(Rgb::new(255, 0, 255)));
+    assert_eq!(to_map(&frame), ["##BB"]);
+}
+
+#[test]
+fn geometry() {
+    let apply = |rows: &[&str], p1, p2, t: &[Geometry]| {
+        let mut frame = from_map(rows);
+        frame.geometry(p1, p2, t);
+        to_map(&frame)
+    };
+    // 3x2 region at (1,1) - pixels outside the region are left alone
+    let rect = ["B....", ".RGB.", ".W.R.", "....."];
+    let region = |t: &[Geometry]| apply(&rect, (1, 1), (4, 3), t);
+    assert_eq!(region(&[]), rect);
+    assert_eq!(
+        region(&[Geometry::FlipH]),
+        ["B....", ".BGR.", ".R.W.", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::FlipV]),
+        ["B....", ".W.R.", ".RGB.", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Rotate180]),
+        ["B....", ".R.W.", ".BGR.", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Shift(1, 0)]),
+        ["B....", "..RG.", "..W..", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Shift(0, -1)]),
+        ["B....", ".W.R.", ".....", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Roll(1, 0)]),
+        ["B....", ".BRG.", ".RW..", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Roll(-1, 1)]),
+        ["B....", "..RW.", ".GBR.", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Scale(2.0, 1.0)]),
+        ["B....", ".RRG.", ".WW..", "....."]
+    );
+    assert_eq!(
+        region(&[Geometry::Scale(0.0, 1.0)]),
+        ["B....", ".....", ".....", "....."]
+    );
+    assert_eq!(region(&[Geometry::FlipH, Geometry::FlipH]), rect);
+
+    // Square region
+    let square = ["RGB", "W.R", "..G"];
+    let turn = |t: &[Geometry]| apply(&square, (0, 0), (3, 3), t);
+    assert_eq!(turn(&[Geometry::Rotate90]), [".WR", "..G", "GRB"]);
+    assert_eq!(turn(&[Geometry::Rotate270]), ["BRG", "G..", "RW."]);
+    assert_eq!(turn(&[Geometry::Transpose]), ["RW.", "G..", "BRG"]);
+    assert_eq!(turn(&[Geometry::Rotate90; 4]), square);
+    assert_eq!(turn(&[Geometry::Rotate90, Geometry::Rotate270]), square);
+    assert_eq!(
+        turn(&[Geometry::Rotate90, Geometry::Rotate90]),
+        turn(&[Geometry::Rotate180])
+    );
+    assert_eq!(
+        turn(&[Geometry::Transpose]),
+        turn(&[Geometry::Rotate90, Geometry::FlipH])
+    );
+
+    // Non-square quarter turns are centred in the region and clipped
+    let wide = ["RGBW", "W..R"];
+    let turn = |t: &[Geometry]| apply(&wide, (0, 0), (4, 2), t);
+    assert_eq!(turn(&[Geometry::Rotate90]), ["..G.", "..B."]);
+    assert_eq!(turn(&[Geometry::Rotate270]), [".B..", ".G.."]);
+    assert_eq!(turn(&[Geometry::Transpose]), [".G..", ".B.."]);
+    // Region clipped to the canvas
+    assert_eq!(
+        apply(&wide, (2, -1), (9, 1), &[Geometry::FlipH]),
+        ["RGWB", "W..R"]
+    );
+}
+
+#[test]
+fn transitions() {
+    let mut from = Matrix1D::<2>::new();
+    from.set_text_style(proportional());
+    from.draw_str("Old", &FONT_8X8, rgb::RED, (0, 0));
+    let mut to = Matrix1D::<2>::new();
+    to.fill_rect((0, 0), (16, 8), Rgb::new(0, 0, 64).into());
+    to.draw_str_aligned("New", &FONT_5X7, rgb::GREEN, 1, Align::Center);
+    let (from_frame, to_frame) = (Frame::from_canvas(&from), Frame::from_canvas(&to));
+    for (name, kind) in [
+        ("cut", TransitionKind::Cut),
+        ("crossfade", TransitionKind::Crossfade),
+        ("wipe_left", TransitionKind::Wipe(Direction::Left)),
+        ("wipe_down", TransitionKind::Wipe(Direction::Down)),
+        ("slide_right", TransitionKind::Slide(Direction::Right)),
+        ("slide_up", TransitionKind::Slide(Direction::Up)),
+        ("dissolve", TransitionKind::Dissolve),
+        ("pixelate", TransitionKind::Pixelate),
+    ] {
+        let transition = Transition::new(kind, 500, Easing::Linear);
+        let mut out = Frame::new((16, 8));
+        transition.render(&from, &to, 0, &mut out);
+        assert_eq!(out, from_frame, "{} start", name);
+        transition.render(&from, &to, 500, &mut out);
+        assert_eq!(out, to_frame, "{} end", name);
+        assert!(transition.is_finished(500));
+        transition.render(&from, &to, 250, &mut out);
+        assert_snapshot(&format!("transition_{}", name), &out);
+    }
+    assert_eq!(
+        "slide-up".parse::<TransitionKind>().unwrap(),
+        TransitionKind::Slide(Direction::Up)
+    );
+    assert_eq!(
+        "wipe".parse::<TransitionKind>().unwrap(),
+        TransitionKind::Wipe(Direction::Left)
+    );
+    assert!("spin".parse::<TransitionKind>().is_err());
+
+    // Images transition like any other content (both directions)
+    let logo = image::decode(image::builtin("logo").unwrap()).unwrap();
+    let mut picture = Matrix1D::<2>::new();
+    image::draw_image(
+        &mut picture,
+        &logo,
+        image::ScaleMode::Fit,
+        image::Filter::Nearest,
+        None,
+    );
+    let picture_frame = Frame::from_canvas(&picture);
+    for (name, kind, from, to) in [
+        (
+            "image_crossfade",
+            TransitionKind::Crossfade,
+            &from,
+            &picture,
+        ),
+        (
+            "image_wipe_right",
+            TransitionKind::Wipe(Direction::Right),
+            &picture,
+            &to,
+        ),
+        (
+            "image_slide_down",
+            TransitionKind::Slide(Direction::Down),
+            &to,
+            &picture,
+        ),
+    ] {
+        let transition = Transition::new(kind, 400, Easing::Linear);
+        let mut out = Frame::new((16, 8));
+        transition.render(from, to, 0, &mut out);
+        assert_eq!(out, Frame::from_canvas(from), "{} start", name);
+        transition.render(from, to, 400, &mut out);
+        assert_eq!(out, Frame::from_canvas(to), "{} end", name);
+        transition.render(from, to, 200, &mut out);
+        assert_ne!(out, picture_frame, "{}", name);
+        assert_snapshot(&format!("transition_{}", name), &out);
+    }
+}
+
+#[test]
+fn easing() {
+    let easings = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::Back,
+        Easing::Elastic,
+        Easing::Bounce,
+    ];
+    for easing in easings {
+        assert!(easing.apply(0.0).abs() < 1e-6, "{:?}", easing);
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{:?}", easing);
+    }
+    // Monotonic curves
+    for easing in &easings[..10] {
+        let values: Vec<f32> = (0..=20).map(|i| easing.apply(i as f32 / 20.0)).collect();
+        assert!(values.windows(2).all(|w| w[1] >= w[0]), "{:?}", easing);
+    }
+    assert!(Easing::EaseIn.apply(0.25) < 0.25);
+    assert!(Easing::EaseOut.apply(0.25) > 0.25);
+    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    assert!((0..20).any(|i| Easing::Back.apply(i as f32 / 20.0) > 1.0));
+    assert_eq!("ease-in-out".parse::<Easing>().unwrap(), Easing::EaseInOut);
+}
+
+#[test]
+fn tweens() {
+    let tween = Tween::new(0.0_f32)
+        .to(8.0, 400, Easing::Linear)
+        .hold(100)
+        .to(0.0, 100, Easing::QuadIn);
+    assert_eq!(tween.duration_ms(), 600);
+    assert_eq!(tween.total_ms(), Some(600));
+    assert_eq!(tween.value_at(0), 0.0);
+    assert_eq!(tween.value_at(100), 2.0);
+    assert_eq!(tween.value_at(450), 8.0);
+    assert_eq!(tween.value_at(550), 6.0);
+    assert_eq!(tween.value_at(10_000), 0.0);
+
+    // Driven by clock
+    let clock = FakeClock::new(5000);
+    let animator = Animator::start(Tween::between((0, 0), (8, 4), 100, Easing::Linear), &clock);
+    assert_eq!(animator.value(&clock), (0, 0));
+    clock.advance(50);
+    assert_eq!(animator.value(&clock), (4, 2));
+    assert!(!animator.is_finished(&clock));
+    clock.advance(50);
+    assert_eq!(animator.value(&clock), (8, 4));
+    assert!(animator.is_finished(&clock));
+
+    // Loop restarts, Yoyo reverses
+    let looped = Tween::between(0.0_f32, 10.0, 100, Easing::Linear).repeat(Repeat::Loop);
+    assert_eq!(looped.value_at(125), 2.5);
+    assert_eq!(looped.total_ms(), None);
+    assert!(!looped.is_finished(1_000_000));
+    let yoyo = Tween::between(0.0_f32, 10.0, 100, Easing::Linear)
+        .repeat(Repeat::Yoyo)
+        .cycles(3);
+    assert_eq!(yoyo.value_at(125), 7.5);
+    assert_eq!(yoyo.value_at(250), 5.0);
+    assert_eq!(yoyo.value_at(300), 10.0);
+    assert_eq!(yoyo.clone().cycles(2).value_at(300), 0.0);
+
+    // Eased colour and brightness
+    let start = Props {
+        colour: Rgb::new(200, 0, 0),
+        brightness: 0.0,
+        ..Props::default()
+    };
+    let end = Props {
+        position: (8.0, 0.0),
+        colour: Rgb::new(0, 0, 200),
+        brightness: 1.0,
+        scale: 2.0,
+    };
+    let tween = Tween::between(start, end, 1000, Easing::EaseOut);
+    let mid = tween.value_at(500);
+    assert_eq!(mid.point(), (7, 0));
+    assert_eq!(mid.colour, Rgb::new(25, 0, 175));
+    assert_eq!(mid.scale, 1.875);
+    assert_eq!(tween.value_at(0).rgb(), rgb::OFF);
+    assert_eq!(tween.value_at(1000).rgb(), Rgb::new(0, 0, 200));
+    // Overshoot doesn't give negative brightness
+    let back = Tween::between(end, start, 1000, Easing::Back);
+    assert!((0..=1000)
+        .step_by(50)
+        .all(|t| back.value_at(t).brightness >= 0.0));
+
+    // Sprites are drawn scaled (from top left) and tinted
+    let sprite = Sprite::from_char_art(&["W.", ".W"], &MAP_COLOURS);
+    let draw = |position, scale| {
+        let props = Props {
+            position,
+            colour: rgb::RED,
+            scale,
+            ..Props::default()
+        };
+        let mut frame = Frame::new((6, 4));
+        props.draw_sprite(&mut frame, &sprite);
+        to_map(&frame)
+    };
+    assert_eq!(
+        draw((1.0, 0.0), 1.0),
+        [".R....", "..R...", "......", "......"]
+    );
+    assert_eq!(
+        draw((1.0, 0.0), 2.0),
+        [".RR...", ".RR...", "...RR.", "...RR."]
+    );
+    assert_eq!(
+        draw((0.0, 0.0), 1.5),
+        ["R.....", ".RR...", ".RR...", "......"]
+    );
+    assert_eq!(
+        draw((-1.0, -1.0), 2.0),
+        ["R.....", ".RR...", ".RR...", "......"]
+    );
+    assert_eq!(draw((0.0, 0.0), 1e9), ["RRRRRR"; 4]);
+    assert_eq!(draw((0.0, 0.0), 0.0), ["......"; 4]);
+    assert_eq!(draw((-1e12, 1e12), 1e12), ["......"; 4]);
+
+    // Keyframe times saturate rather than overflow
+    let long = Tween::new(0.0_f32)
+        .to(1.0, u32::MAX, Easing::Linear)
+        .hold(10);
+    assert_eq!(long.duration_ms(), u32::MAX);
+}
+
+#[test]
+fn clock() {
+    // 2026-10-19 21:07:05.250 (Monday)
+    let dt = DateTime::from_unix_ms(1_792_444_025_250);
+    assert_eq!((dt.year, dt.month, dt.day, dt.weekday), (2026, 10, 19, 1));
+    assert_eq!((dt.hour, dt.minute, dt.second, dt.millis), (21, 7, 5, 250));
+    assert_eq!(
+        DateTime::from_unix_ms(951_782_400_000).format("%Y-%m-%d %a"),
+        "2000-02-29 Tue"
+    );
+    assert_eq!(
+        DateTime::from_unix_ms(-1000).format("%Y %H:%M:%S"),
+        "1969 23:59:59"
+    );
+    assert_eq!(
+        dt.format("%-I:%M%p %e %b %y 100%%"),
+        "9:07PM 19 Oct 26 100%"
+    );
+
+    // Options
+    let mut options = ClockOptions::default();
+    assert_eq!(options.text(Some(&dt)), ("21:07".to_string(), true));
+    options.hour12 = true;
+    options.seconds = true;
+    assert_eq!(options.text(Some(&dt)), ("9:07:05".to_string(), true));
+    let later = DateTime::from_unix_ms(1_792_444_025_750);
+    assert_eq!(options.text(Some(&later)), ("9:07:05".to_string(), false));
+    options.date = true;
+    let date = DateTime::from_unix_ms(1_792_444_033_000);
+    assert_eq!(options.text(Some(&date)), ("19.10".to_string(), true));
+    assert_eq!(options.text(None), ("--:--".to_string(), true));
+
+    // HH:MM fits two panels
+    let mut matrix = Matrix1D::<2>::new();
+    assert_eq!(
+        clock::draw_clock(&mut matrix, "23:58", rgb::GREEN, true),
+        16
+    );
+    assert_snapshot("clock_hhmm", &Frame::from_canvas(&matrix));
+    // Digits don't move when separator is hidden
+    let mut blink = Matrix1D::<2>::new();
+    clock::draw_clock(&mut blink, "23:58", rgb::GREEN, false);
+    for x in 0..16 {
+        let column = |m: &Matrix1D<2>| (0..8).map(|y| m.get((x, y))).collect::<Vec<_>>();
+        if x != 8 {
+            assert_eq!(column(&matrix), column(&blink), "column {}", x);
+        }
+    }
+    // Short text is centred, non-digits use small font
+    let mut matrix = Matrix1D::<2>::new();
+    assert_eq!(clock::draw_clock(&mut matrix, "9:41", rgb::BLUE, true), 13);
+    assert_snapshot("clock_short", &Frame::from_canvas(&matrix));
+    let mut matrix = Matrix1D::<4>::new();
+    clock::draw_clock(&mut matrix, "9:41 PM", rgb::RED, true);
+    assert_snapshot("clock_ampm", &Frame::from_canvas(&matrix));
+}
+
+#[test]
+fn timers() {
+    // Pausable stopwatch
+    let mut stopwatch = Stopwatch::started(1000);
+    assert_eq!(stopwatch.elapsed_ms(3500), 2500);
+    stopwatch.pause(4000);
+    assert!(!stopwatch.is_running());
+    assert_eq!(stopwatch.elapsed_ms(9000), 3000);
+    stopwatch.start(10_000);
+    stopwatch.start(11_000); // Already running
+    assert_eq!(stopwatch.elapsed_ms(12_000), 5000);
+    stopwatch.reset();
+    assert_eq!(stopwatch.elapsed_ms(20_000), 0);
+
+    // Countdowns
+    let stopwatch = Stopwatch::started(0);
+    let countdown = Countdown::Duration(90_000);
+    assert_eq!(countdown.remaining_ms(&stopwatch, 500, None), Some(89_500));
+    assert_eq!(countdown.remaining_ms(&stopwatch, 100_000, None), Some(0));
+    let until = Countdown::Until(1_000_000);
+    assert_eq!(until.remaining_ms(&stopwatch, 0, None), None);
+    assert_eq!(
+        until.remaining_ms(&stopwatch, 0, Some(940_000)),
+        Some(60_000)
+    );
+    assert_eq!(until.remaining_ms(&stopwatch, 0, Some(2_000_000)), Some(0));
+
+    // 2026-10-19 21:07:05 UTC
+    let now = 1_792_444_025_000;
+    let target = timer::next_time_of_day(now, 60, (23, 30));
+    assert_eq!(target - now, (60 + 23) * 60_000 - 5000);
+    let tomorrow = timer::next_time_of_day(now, 0, (9, 0));
+    assert_eq!(
+        DateTime::from_unix_ms(tomorrow).format("%d %H:%M"),
+        "20 09:00"
+    );
+    assert_eq!(timer::parse_time_of_day("17:30").unwrap(), (17, 30));
+    assert_eq!(timer::parse_time_of_day(" 0 : 05 ").unwrap(), (0, 5));
+    assert_eq!(timer::parse_time_of_day("23:59").unwrap(), (23, 59));
+    for s in ["24:00", "12:60", "99:99", "-1:00", "1730", "", "a:b"] {
+        assert!(timer::parse_time_of_day(s).is_err(), "{s}");
+    }
+
+    // Display text
+    assert_eq!(timer::format_duration(89_500, true), "1:30");
+    assert_eq!(timer::format_duration(89_500, false), "1:29");
+    assert_eq!(timer::format_duration(1, true), "0:01");
+    assert_eq!(timer::format_duration(3_723_000, false), "1:02:03");
+    assert_eq!(
+        timer::format_duration(3 * 86_400_000 + 4 * 3_600_000, false),
+        "3d04h"
+    );
+
+    // Alert
+    let alert = Alert::default();
+    assert_eq!(alert.colour(rgb::GREEN, 100), Some(rgb::RED));
+    assert_eq!(alert.colour(rgb::GREEN, 600), None);
+    let steady = Alert {
+        colour: None,
+        flash: false,
+    };
+    assert_eq!(steady.colour(rgb::GREEN, 600), Some(rgb::GREEN));
+
+    // Controls
+    assert_eq!(
+        "start".parse::<TimerControl>().unwrap(),
+        TimerControl::Start
+    );
+    assert_eq!(
+        "increment:5".parse::<TimerControl>().unwrap(),
+        TimerControl::Increment(5)
+    );
+    assert_eq!(
+        "Dec".parse::<TimerControl>().unwrap(),
+        TimerControl::Decrement(1)
+    );
+    assert!("jump".parse::<TimerControl>().is_err());
+    assert!("inc:x".parse::<TimerControl>().is_err());
+
+    // MM:SS and negative counters fit two panels
+    for text in ["59:59", "-123"] {
+        let mut matrix = Matrix1D::<2>::new();
+        assert!(
+            clock::draw_clock(&mut matrix, text, rgb::GREEN, true) <= 16,
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn charts() {
+    // Ring buffer drops oldest
+    let mut samples = Samples::new(4);
+    samples.extend([1.0, 2.0, f32::NAN, 3.0, 4.0, 5.0]);
+    assert_eq!(samples.iter().collect::<Vec<_>>(), [2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(samples.latest(), Some(5.0));
+    assert_eq!(samples.min_max(), Some((2.0, 5.0)));
+
+    let mut samples = Samples::new(32);
+    samples.extend((0..20).map(|i| (i as f32 * 0.6).sin() * 10.0 + 20.0));
+    for (name, kind) in [
+        ("chart_bars", ChartKind::Bars),
+        ("chart_sparkline", ChartKind::Sparkline),
+        ("chart_meter", ChartKind::Meter),
+    ] {
+        let mut matrix = Matrix1D::<2>::new();
+        Chart::new(kind).draw(&samples, &mut matrix, (0, 0), (16, 8));
+        assert_snapshot(name, &Frame::from_canvas(&matrix));
+    }
+
+    // Region only - fixed range clamps and other pixels are untouched
+    let mut matrix = Matrix1D::<2>::new();
+    matrix.fill_rect((0, 0), (16, 8), Paint::Solid(rgb::BLUE));
+    let chart = Chart {
+        kind: ChartKind::Bars,
+        range: Range::Fixed(0.0, 4.0),
+        palette: Palette::Mono(rgb::WHITE),
+    };
+    let mut samples = Samples::new(8);
+    samples.extend([0.0, 1.0, 2.0, 4.0, 8.0, -1.0]);
+    chart.draw(&samples, &mut matrix, (8, 2), (8, 4));
+    assert_snapshot("chart_region", &Frame::from_canvas(&matrix));
+    assert_eq!(matrix.get((7, 3)), Some(rgb::BLUE));
+    assert_eq!(matrix.get((8, 3)), Some(rgb::OFF));
+    // Full height for value at top of range (and above)
+    assert!((2..6).all(|y| matrix.get((13, y)) != Some(rgb::OFF)));
+    assert!((2..6).all(|y| matrix.get((14, y)) != Some(rgb::OFF)));
+    assert_eq!("vu".parse::<ChartKind>().unwrap(), ChartKind::Meter);
+}
+
+#[test]
+fn widgets() {
+    // Progress, spinner and wifi icons side by side
+    let mut matrix = Matrix1D::<4>::new();
+    Widget::Progress(0.45, rgb::GREEN).draw(&mut matrix, (0, 3), (8, 2), 0);
+    Widget::Spinner(rgb::BLUE).draw(&mut matrix, (9, 1), (6, 6), 400);
+    Widget::Wifi(Some(-70), rgb::GREEN).draw(&mut matrix, (16, 0), (7, 8), 0);
+    Widget::Wifi(None, rgb::GREEN).draw(&mut matrix, (24, 0), (4, 8), 0);
+    Widget::Wifi(Some(-40), rgb::WHITE).draw(&mut matrix, (28, 0), (4, 8), 0);
+    assert_snapshot("widgets", &Frame::from_canvas(&matrix));
+
+    // Partial column is blended
+    let (full, partial, empty) = (matrix.get((2, 3)), matrix.get((3, 3)), matrix.get((4, 3)));
+    assert_eq!(full, Some(rgb::GREEN));
+    assert!(partial != full && partial != empty);
+    assert!(empty != Some(rgb::OFF)); // Unlit parts still show over other layers
+
+    // Spinner head moves round edge and wraps
+    let head = |now_ms: u32| {
+        let mut matrix = Matrix1D::<1>::new();
+        Widget::Spinner(rgb::RED).draw(&mut matrix, (0, 0), (3, 3), now_ms);
+        (0..3)
+            .flat_map(|y| (0..3).map(move |x| (x, y)))
+            .find(|&p| matrix.get(p) == Some(rgb::RED))
+    };
+    assert_eq!(head(0), Some((0, 0)));
+    assert_eq!(head(160), Some((2, 0)));
+    assert_eq!(head(320), Some((2, 2)));
+    assert_eq!(head(8 * 80), Some((0, 0)));
+    assert!(Widget::Spinner(rgb::RED).is_animated());
+    assert!(!Widget::Progress(0.5, rgb::RED).is_animated());
+
+    assert_eq!(
+        [-50, -60, -70, -80, -90].map(widget::wifi_bars),
+        [4, 3, 2, 1, 0]
+    );
+
+    // Wifi bars stay inside narrow regions (fewer bars)
+    let wifi = |rssi, w| {
+        let mut frame = Frame::new((4, 4));
+        Widget::Wifi(rssi, rgb::WHITE).draw(&mut frame, (1, 0), (w, 4), 0);
+        to_map(&frame)
+    };
+    assert_eq!(wifi(Some(-40), 1), [".W..", ".W..", ".W..", ".W.."]);
+    assert_eq!(wifi(Some(-40), 2), ["..W.", "..W.", ".WW.", ".WW."]);
+    assert_eq!(wifi(Some(-80), 2), ["..#.", "..#.", ".W#.", ".W#."]);
+    assert_eq!(wifi(None, 3), ["...#", "...#", "..##", ".###"]);
+    assert_eq!(wifi(Some(-40), 0), ["...."; 4]);
+}
+
+#[test]
+fn playlists() {
+    let json = r##"{
+        "name": "day",
+        "entries": [
+            {"scene": {"type": "message", "text": "Hi", "colour": "#ff0000"}, "duration_s": 5},
+            {"scene": {"type": "scroll", "text": "News", "colour": "#00ff00"}, "repeat": 2},
+            {"scene": {"type": "clock", "colour": "#ffffff", "hour12": true}},
+            {"scene": {"type": "effect", "name": "fire"}, "duration_s": 60},
+            {"scene": {"type": "scroll", "text": "Later", "colour": "#0000ff"}, "repeat": 3, "duration_s": 30}
+        ]
+    }"##;
+    let playlist: Playlist = serde_json::from_str(json).unwrap();
+    assert!(playlist.looping);
+    assert!(!playlist.shuffle);
+    assert_eq!(
+        playlist.entries[1].scene,
+        SceneSpec::Scroll {
+            text: "News".into(),
+            colour: "#00ff00".into(),
+            speed: 40.0
+        }
+    );
+    let round_trip: Playlist =
+        serde_json::from_str(&serde_json::to_string(&playlist).unwrap()).unwrap();
+    assert_eq!(round_trip, playlist);
+    assert_eq!("#ff8000".parse::<Rgb>().unwrap(), Rgb::new(255, 128, 0));
+    assert!("#ff80".parse::<Rgb>().is_err());
+    // Images are built-in by name (no file system for paths)
+    let image = r#"{"type": "image", "name": "logo"}"#;
+    let spec = serde_json::from_str::<SceneSpec>(image).unwrap();
+    assert_eq!(
+        spec,
+        SceneSpec::Image {
+            name: "logo".into()
+        }
+    );
+    assert!(!spec.is_cyclic());
+    let path = r#"{"type": "image", "path": "/spiffs/cat.gif"}"#;
+    assert!(serde_json::from_str::<SceneSpec>(path).is_err());
+
+    // Entry limits (whichever is reached first - default 10s)
+    let [message, scroll, clock, _, capped] = &playlist.entries[..] else {
+        panic!("Expected 5 entries");
+    };
+    assert!(!message.is_done(4999, 0));
+    assert!(message.is_done(5000, 0));
+    assert!(!scroll.is_done(60_000, 1));
+    assert!(scroll.is_done(0, 2));
+    assert!(!clock.is_done(9999, 5));
+    assert!(clock.is_done(10_000, 0));
+    assert!(capped.is_done(1000, 3));
+    assert!(capped.is_done(30_000, 0));
+    // Repeat count ignored for scenes without passes
+    let repeat_clock = Entry {
+        repeat: Some(1),
+        ..clock.clone()
+    };
+    assert!(!repeat_clock.is_done(0, 1));
+    assert!(repeat_clock.is_done(10_000, 0));
+
+    // Advance through entries and loop
+    let mut player = PlaylistPlayer::new(playlist.clone(), 1);
+    assert_eq!(player.index(), Some(0));
+    assert!(player.tick(4000, 0).is_none());
+    assert_eq!(player.tick(1000, 0), Some(&playlist.entries[1]));
+    assert!(player.tick(1000, 1).is_none());
+    assert!(player.tick(1000, 2).is_some());
+    assert_eq!(player.index(), Some(2));
+    assert!(player.tick(10_000, 0).is_some());
+    assert!(player.tick(60_000, 0).is_some());
+    assert_eq!(player.tick(30_000, 0), Some(&playlist.entries[0]));
+
+    // Hold last entry when not looping
+    let once = Playlist {
+        looping: false,
+        entries: playlist.entries[..2].to_vec(),
+        ..playlist.clone()
+    };
+    let mut player = PlaylistPlayer::new(once, 1);
+    assert!(player.tick(5000, 0).is_some());
+    assert!(player.tick(0, 2).is_none());
+    assert!(player.is_finished());
+    assert_eq!(player.index(), None);
+
+    // Shuffle plays every entry once per pass without immediate repeats
+    let shuffled = Playlist {
+        shuffle: true,
+        ..playlist.clone()
+    };
+    let mut player = PlaylistPlayer::new(shuffled, 1234);
+    let mut seen = vec![player.index().unwrap()];
+    for _ in 0..14 {
+        while player.tick(60_000, u32::MAX).is_none() {}
+        let index = player.index().unwrap();
+        assert_ne!(Some(&index), seen.last());
+        seen.push(index);
+    }
+    for pass in seen.chunks(5) {
+        let mut pass = pass.to_vec();
+        pass.sort();
+        assert_eq!(pass, [0, 1, 2, 3, 4]);
+    }
+    assert_ne!(seen[..5], [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn schedule() {
+    // 2026-10-19 21:07:05 UTC (Monday)
+    let now = 1_792_444_025_000;
+    let t = DateTime::from_unix_ms(now);
+
+    let cron = |s: &str| s.parse::<Cron>().unwrap();
+    assert!(cron("* * * * *").matches(&t));
+    assert!(cron("7 21 19 10 1").matches(&t));
+    assert!(cron("*/7 20-22 * oct mon-fri").matches(&t));
+    assert!(cron("0,7,30 21 * * 1,3,5").matches(&t));
+    assert!(!cron("5/15 * * * *").matches(&t));
+    assert!(cron("7/15 * * * *").matches(&t));
+    assert!(!cron("* * * * sat,sun").matches(&t));
+    assert!(cron("* * * * 7").matches(&DateTime::from_unix_ms(now - 86_400_000)));
+    // Day or weekday when both restricted
+    assert!(cron("7 21 1 * mon").matches(&t));
+    assert!(cron("7 21 19 * sun").matches(&t));
+    assert!(!cron("7 21 1 * sun").matches(&t));
+    for bad in [
+        "* * * *",
+        "60 * * * *",
+        "* 5-2 * * *",
+        "*/0 * * * *",
+        "* * * foo *",
+    ] {
+        assert!(bad.parse::<Cron>().is_err(), "{}", bad);
+    }
+
+    let json = r#"{
+        "utc_offset_min": 60,
+        "rules": [
+            {"cron": "0 7 * * mon-fri", "action": {"playlist": "day"}},
+            {"cron": "0 9 * * sat,sun", "action": {"playlist": "weekend"}},
+            {"cron": "30 22 * * *", "action": "off"}
+        ]
+    }"#;
+    let schedule: Schedule = serde_json::from_str(json).unwrap();
+    let day = Action::Playlist("day".into());
+    // 22:07 local - day playlist started at 07:00
+    assert_eq!(schedule.active_at(now), Some(&day));
+    // 22:30 local
+    assert_eq!(schedule.active_at(now + 23 * 60_000), Some(&Action::Off));
+    // Saturday 10:00 local
+    let saturday = now + (4 * 24 * 60 + 11 * 60 + 53) * 60_000;
+    assert_eq!(
+        DateTime::from_unix_ms(saturday + 3_600_000).format("%a %H:%M"),
+        "Sat 10:00"
+    );
+    assert_eq!(
+        schedule.active_at(saturday),
+        Some(&Action::Playlist("weekend".into()))
+    );
+    // Sunday 08:00 local - still off from Saturday night
+    assert_eq!(
+        schedule.active_at(saturday + 22 * 3_600_000),
+        Some(&Action::Off)
+    );
+    assert_eq!(Schedule::default().active_at(now), None);
+
+    // Latest match agrees with checking every minute of the past week
+    let exprs = [
+        "* * * * *",
+        "0 7 * * mon-fri",
+        "*/20 9-17 * * *",
+        "45 23 * * sun",
+        "7 21 * * *",
+        "8 21 * * *",
+        "0 0 1 * *",
+        "30 12 15 * fri",
+        "0 12 * feb *",
+    ];
+    for offset in [0, 53, 173, 4 * 1440 + 1] {
+        let local_min = now / 60_000 + offset;
+        for expr in exprs {
+            let cron = cron(expr);
+            let expected = (0..7 * 24 * 60)
+                .map(|back| local_min - back)
+                .find(|&m| cron.matches(&DateTime::from_unix_ms(m * 60_000)));
+            assert_eq!(cron.latest(local_min), expected, "{} at {}", expr, offset);
+        }
+    }
+
+    let round_trip: Schedule =
+        serde_json::from_str(&serde_json::to_string(&schedule).unwrap()).unwrap();
+    assert_eq!(round_trip, schedule);
+    assert!(
+        serde_json::from_str::<Schedule>(r#"{"rules": [{"cron": "* *", "action": "off"}]}"#)
+            .is_err()
+    );
+}
+
+// Run pattern for one pixel and return (colour, named variable)
+fn eval_pattern(source: &str, var: &str) -> (Rgb, f32) {
+    let mut vm = Vm::new(Program::compile(source).unwrap());
+    let pixel = Pixel {
+        x: 3,
+        y: 2,
+        t: 1.5,
+        size: (16, 8),
+    };
+    let mut steps = pattern::FRAME_STEP_LIMIT;
+    let rgb = vm.pixel(pixel, &Palette::Fire, &mut steps).unwrap();
+    (rgb, vm.get(var).unwrap())
+}
+
+#[test]
+fn patterns() {
+    // Arithmetic, precedence and inputs
+    assert_eq!(eval_pattern("a = 1 + 2 * 3 - 4 / 2", "a").1, 5.0);
+    assert_eq!(eval_pattern("a = (1 + 2) * -3", "a").1, -9.0);
+    assert_eq!(eval_pattern("a = -1 % 4; b = 7 % 4", "a").1, 3.0);
+    assert_eq!(eval_pattern("a = x * 10 + y + t", "a").1, 33.5);
+    assert_eq!(
+        eval_pattern("a = u * 15 + v * 7 + i", "a").1,
+        3.0 + 2.0 + 35.0
+    );
+    assert_eq!(eval_pattern("a = w * h", "a").1, 128.0);
+    assert_eq!(eval_pattern("a = 1 < 2 && 2 <= 2 && !(3 == 4)", "a").1, 1.0);
+    assert_eq!(eval_pattern("a = 0 || 0 || 5", "a").1, 1.0);
+    assert_eq!(eval_pattern("a = 0 && 1 / 0", "a").1, 0.0);
+    assert_eq!(eval_pattern("a = x > 2 ? y > 5 ? 1 : 2 : 3", "a").1, 2.0);
+    assert_eq!(
+        eval_pattern("a = 2; a += 3; a *= 2; a -= 1; a /= 3", "a").1,
+        3.0
+    );
+    assert_eq!(
+        eval_pattern("a = max(min(4, 9), clamp(20, 0, 10))", "a").1,
+        10.0
+    );
+    assert_eq!(
+        eval_pattern("a = mix(2, 4, 0.25) + frac(-0.25)", "a").1,
+        3.25
+    );
+    assert_eq!(
+        eval_pattern("a = wave(0.5) + triangle(0.25) + square(0.2, 0.5)", "a").1,
+        2.5
+    );
+    assert_eq!(eval_pattern("a = time(3)", "a").1, 0.5);
+    assert_eq!(
+        eval_pattern("a = floor(pi) + ceil(0.1) + abs(-2)", "a").1,
+        6.0
+    );
+    let (_, n) = eval_pattern("a = noise(x, y, t)", "a");
+    assert!((0.0..=1.0).contains(&n));
+
+    // Control flow across lines (expressions continue after operators)
+    let source = "
+        // Sum 1..10
+        n = 0; total = 0
+        while n < 10 {
+            n += 1
+            total = total +
+                n
+        }
+        if total > 100 {
+            big = 1
+        }
+        else if total == 55 { big = 2 } else { big = 3 }
+    ";
+    assert_eq!(eval_pattern(source, "total").1, 55.0);
+    assert_eq!(eval_pattern(source, "big").1, 2.0);
+
+    // Colour from last colour call or palette of last value
+    assert_eq!(eval_pattern("a = 0; hsv(0, 1, 1)", "a").0, rgb::RED);
+    assert_eq!(eval_pattern("a = 0; hsv(1 / 3, 1, 1)", "a").0, rgb::GREEN);
+    assert_eq!(eval_pattern("a = 0; hsv(0.5, 0, 1)", "a").0, rgb::WHITE);
+    assert_eq!(
+        eval_pattern("a = 0; rgb(2, 0, 1)", "a").0,
+        Rgb::new(255, 0, 255)
+    );
+    assert_eq!(eval_pattern("a = 0; rgb(1, 0, 0); 0.5", "a").0, rgb::RED);
+    assert_eq!(
+        eval_pattern("a = 0; 0.25", "a").0,
+        Palette::Fire.cycle(0.25)
+    );
+    assert_eq!(
+        eval_pattern("a = 0; palette(0.1)", "a").0,
+        Palette::Fire.cycle(0.1)
+    );
+    assert_eq!(eval_pattern("a = 1", "a").0, rgb::OFF);
+
+    // Variables persist between pixels (counted across frame)
+    let mut vm = Vm::new(Program::compile("n = i == 0 ? 0 : n; n += 1").unwrap());
+    let mut frame = Frame::new((16, 8));
+    vm.draw(0.0, &Palette::Rainbow, &mut frame).unwrap();
+    assert_eq!(vm.get("n"), Some(128.0));
+    assert_eq!(vm.get("missing"), None);
+
+    // Compile errors with line numbers
+    for (source, error) in [
+        ("a = 1 +", "line 1: expected value"),
+        ("a = (1 + 2", "line 1: expected ')'"),
+        ("\na = b", "line 2: unknown variable 'b'"),
+        ("a = foo(1)", "line 1: unknown function 'foo'"),
+        ("a = sin(1, 2)", "line 1: sin() takes 1 arguments"),
+        ("a += 1", "line 1: unknown variable 'a'"),
+        ("sin = 1", "line 1: can't assign to 'sin'"),
+        ("if 1 { a = 1", "line 1: expected '}'"),
+        ("a = 1 2", "line 1: expected end of statement"),
+        ("a = 1 $ 2", "line 1: unexpected '$'"),
+        ("a = 1.2.3", "line 1: invalid number '1.2.3'"),
+    ] {
+        let e = Program::compile(source).unwrap_err();
+        assert_eq!(e.to_string(), error, "{}", source);
+    }
+    let deep = format!("a = {}1{}", "(".repeat(40), ")".repeat(40));
+    assert!(Program::compile(&deep).is_err());
+    let long = "a = 1\n".repeat(2000);
+    assert!(Program::compile(&long).is_err());
+
+    // Instruction limit stops runaway patterns
+    let mut vm = Vm::new(Program::compile("while 1 { }").unwrap());
+    let mut steps = 1000;
+    let e = vm
+        .pixel(Pixel::default(), &Palette::Rainbow, &mut steps)
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Instruction limit exceeded");
+    assert_eq!(steps, 0);
+    let mut vm = Vm::new(Program::compile("n = 0; while n < 500 { n += 1 }").unwrap());
+    assert!(vm.draw(0.0, &Palette::Rainbow, &mut frame).is_err());
+
+    // Extreme and NaN arguments don't panic (lattice wraps, casts saturate)
+    let values = [
+        "10000000000",
+        "-10000000000",
+        &format!("{}", f32::MAX),
+        "0 / 0",
+        "1 / 0",
+        "-1 / 0",
+        "0",
+    ];
+    for a in values {
+        for b in values {
+            let source = format!(
+                "a = {a}; b = {b}; n = noise(a, b, a) + time(a) + pow(a, b)
+                 n + noise(b, a, 0); hsv(n, a, b); palette(n); rgb(a, b, n)"
+            );
+            let mut pattern = Pattern::compile(&source).unwrap();
+            pattern.render(0, &EffectParams::default(), &mut frame);
+            assert!(!pattern.is_failed(), "{}", source);
+        }
+    }
+    assert!((0.0..=1.0).contains(&effect::value_noise(1e10, -1e10, 0.5)));
+
+    // As an effect (stops and clears display on error)
+    let mut slow = Pattern::compile("n = 0; while n < 500 { n += 1 }; 1").unwrap();
+    slow.render(0, &EffectParams::default(), &mut frame);
+    assert!(slow.is_failed());
+    assert!((0..16).all(|x| (0..8).all(|y| frame.get((x, y)) == Some(rgb::OFF))));
+    let mut pattern = Pattern::compile(
+        "
+        // Noise with a sweeping highlight
+        n = noise(x * 0.4, y * 0.4, t)
+        d = abs(x - time(2) * w)
+        d < 1.5 ? hsv(t * 0.1, 0.5, 1) : palette(n)
+        ",
+    )
+    .unwrap();
+    let params = EffectParams::default();
+    for t in (0..=1000).step_by(25) {
+        pattern.render(t, &params, &mut frame);
+    }
+    assert!(!pattern.is_failed());
+    assert_snapshot("pattern", &frame);
+}
+
+// Position of first pixel with colour
+fn find_pixel(matrix: &Matrix1D<2>, rgb: Rgb) -> Option<(i32, i32)> {
+    (0..8)
+        .flat_map(|y| (0..16).map(move |x| (x, y)))
+        .find(|&p| matrix.get(p) == Some(rgb))
+}
+
+#[test]
+fn games() {
+    // Snake grows when eating and can't reverse
+    let mut snake = Snake::new((16, 8), 1);
+    assert_eq!(snake.body().collect::<Vec<_>>(), [(8, 4), (7, 4), (6, 4)]);
+    snake.set_food(Some((10, 4)));
+    snake.step();
+    snake.step();
+    assert_eq!(
+        (snake.head(), snake.score(), snake.body().count()),
+        ((10, 4), 1, 4)
+    );
+    assert!(snake
+        .food()
+        .is_some_and(|food| !snake.body().any(|p| p == food)));
+    snake.input(Input::Left);
+    snake.step();
+    assert_eq!(snake.head(), (11, 4));
+    // Wraps round edges and Action turns clockwise
+    snake.set_food(None);
+    snake.input(Input::Up);
+    for _ in 0..5 {
+        snake.step();
+    }
+    assert_eq!(snake.head(), (11, 7));
+    snake.input(Input::Action);
+    snake.step();
+    assert_eq!(snake.head(), (12, 7));
+    assert!(snake.step_ms() < 160);
+
+    // Runs into itself
+    let mut snake = Snake::new((16, 8), 1);
+    for x in 9..12 {
+        snake.set_food(Some((x, 4)));
+        snake.step();
+    }
+    snake.set_food(None);
+    for input in [Input::Down, Input::Left, Input::Up] {
+        snake.input(input);
+        snake.step();
+    }
+    assert!(snake.is_over());
+    assert_eq!(snake.score(), 3);
+
+    // Pong paddle stays on display
+    let mut pong = Pong::new((16, 8), 1);
+    assert_eq!((pong.paddles(), pong.paddle_height()), ((2, 2), 3));
+    for _ in 0..5 {
+        pong.input(Input::Up);
+    }
+    assert_eq!(pong.paddles().0, 0);
+    for _ in 0..10 {
+        pong.input(Input::Down);
+    }
+    assert_eq!(pong.paddles().0, 5);
+    // Action serves straight away
+    assert!(pong.is_serving());
+    pong.input(Input::Action);
+    pong.step();
+    assert!(!pong.is_serving());
+
+    // Tracking ball never misses (but computer sometimes does)
+    let mut pong = Pong::new((16, 8), 2);
+    for _ in 0..2000 {
+        let ((x, y), _) = pong.ball();
+        assert!((1..15).contains(&x) && (0..8).contains(&y));
+        let centre = pong.paddles().0 + 1;
+        if y < centre {
+            pong.input(Input::Up);
+        } else if y > centre {
+            pong.input(Input::Down);
+        }
+        pong.step();
+    }
+    assert_eq!(pong.lives(), 3);
+    assert!(pong.score() > 0);
+
+    // Missing three times ends game
+    let mut pong = Pong::new((16, 8), 2);
+    for _ in 0..5 {
+        pong.input(Input::Up);
+    }
+    let steps = (0..2000).take_while(|_| {
+        pong.step();
+        !pong.is_over()
+    });
+    assert!(steps.count() < 2000);
+    assert_eq!(pong.lives(), 0);
+
+    // Tetris clears full rows (4 at once scores most)
+    let mut tetris = Tetris::new((8, 16), 3);
+    assert_eq!(tetris.board_size(), (8, 16));
+    for r in 12..16 {
+        for c in 0..8 {
+            tetris.set_cell((c, r), Some(rgb::WHITE));
+        }
+    }
+    tetris.input(Input::Action);
+    assert_eq!((tetris.lines(), tetris.score()), (4, 8));
+    let filled = (0..16)
+        .flat_map(|r| (0..8).map(move |c| (c, r)))
+        .filter(|&p| tetris.cell(p).is_some());
+    assert!(filled.clone().count() == 4 && filled.clone().all(|(_, r)| r >= 12));
+
+    // Rotation kicks off walls
+    for _ in 0..10 {
+        tetris.input(Input::Left);
+    }
+    for _ in 0..4 {
+        tetris.input(Input::Up);
+        assert!(tetris
+            .piece()
+            .iter()
+            .all(|&(c, r)| (0..8).contains(&c) && r < 16));
+    }
+
+    // Sideways on wide display - Left drops towards left edge
+    let mut tetris = Tetris::new((16, 8), 3);
+    assert_eq!(tetris.board_size(), (8, 16));
+    let before = tetris.piece();
+    tetris.input(Input::Left);
+    let after = tetris.piece();
+    assert!(before
+        .iter()
+        .zip(after.iter())
+        .all(|(b, a)| (a.0, a.1) == (b.0, b.1 + 1)));
+    // Board fills up
+    let drops = (0..100).take_while(|_| {
+        tetris.input(Input::Action);
+        !tetris.is_over()
+    });
+    assert!(drops.count() < 100);
+
+    assert_eq!("ArrowUp".parse::<Input>().unwrap(), Input::Up);
+    assert_eq!(" ".parse::<Input>().unwrap(), Input::Action);
+    assert_eq!("Tetris".parse::<GameKind>().unwrap(), GameKind::Tetris);
+    assert!("chess".parse::<GameKind>().is_err());
+
+    // Snapshots part way through each game
+    let mut snake = Snake::new((16, 8), 4);
+    for input in [Input::Up, Input::Right, Input::Up, Input::Left] {
+        snake.input(input);
+        for _ in 0..3 {
+            snake.step();
+        }
+    }
+    let mut pong = Pong::new((16, 8), 4);
+    for _ in 0..14 {
+        pong.step();
+    }
+    let mut tetris = Tetris::new((16, 8), 4);
+    for input in [
+        Input::Action,
+        Input::Up,
+        Input::Action,
+        Input::Right,
+        Input::Action,
+    ] {
+        tetris.input(input);
+    }
+    tetris.step();
+    for (name, game) in [
+        ("game_snake", &snake as &dyn Game),
+        ("game_pong", &pong),
+        ("game_tetris", &tetris),
+    ] {
+        let mut matrix = Matrix1D::<2>::new();
+        game.draw(&mut matrix);
+        assert_snapshot(name, &Frame::from_canvas(&matrix));
+    }
+}
+
+#[test]
+fn game_runner() {
+    // Fixed steps from elapsed time (catching up at most 4 steps)
+    let mut runner = GameRunner::new(GameKind::Snake, (16, 8), 1);
+    let mut matrix = Matrix1D::<2>::new();
+    assert!(!runner.tick(100));
+    assert!(runner.tick(100));
+    runner.draw(&mut matrix);
+    assert_eq!(find_pixel(&matrix, rgb::GREEN), Some((9, 4)));
+    assert!(runner.tick(10_000));
+    runner.draw(&mut matrix);
+    assert_eq!(find_pixel(&matrix, rgb::GREEN), Some((13, 4)));
+    assert!(!runner.tick(100));
+
+    // Inputs are queued until next tick
+    runner.input(Input::Down);
+    assert!(runner.tick(0));
+    assert!(runner.tick(160));
+    runner.draw(&mut matrix);
+    assert_eq!(find_pixel(&matrix, rgb::GREEN), Some((13, 5)));
+
+    // Game over flashes then shows score until Action restarts
+    let mut runner = GameRunner::new(GameKind::Tetris, (16, 8), 1);
+    let mut ticks = 0;
+    while runner.phase() == Phase::Playing && ticks < 1000 {
+        runner.input(Input::Action);
+        runner.tick(25);
+        ticks += 1;
+    }
+    assert_eq!(runner.phase(), Phase::GameOver);
+    let score = runner.game().score();
+    assert_eq!(runner.best(), score);
+    runner.draw(&mut matrix);
+    assert!(find_pixel(&matrix, rgb::RED).is_some());
+    assert!(runner.tick(200)); // Flash off
+    runner.input(Input::Action); // Ignored
+    assert!(!runner.tick(25));
+    assert!(runner.tick(1000));
+    assert_eq!(runner.phase(), Phase::Score);
+    assert!(!runner.tick(25));
+    runner.input(Input::Up);
+    assert!(!runner.tick(25));
+    runner.draw(&mut matrix);
+    assert_snapshot("game_score", &Frame::from_canvas(&matrix));
+    runner.input(Input::Action);
+    assert!(runner.tick(25));
+    assert_eq!(runner.phase(), Phase::Playing);
+    assert!(!runner.game().is_over());
+}
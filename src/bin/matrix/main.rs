@@ -4,6 +4,7 @@ use esp_idf_hal::{delay::FreeRtos, gpio::OutputPin, prelude::Peripherals};
 
 use c3zero::matrix::{Matrix, Orientation};
 use c3zero::rgb::{self, Rgb, RgbLayout};
+use c3zero::sprite::{Blit, Sprite};
 use c3zero::ws2812_rmt::{Ws2812Rmt, Ws2812RmtSingle};
 
 fn main() -> Result<()> {
@@ -58,8 +59,26 @@ fn bitmap(matrix: &mut Matrix, ws2812: &mut Ws2812Rmt) -> Result<()> {
         ('.', rgb::OFF),
     ];
 
+    matrix.fill(rgb::OFF);
     matrix.draw_bitmap(&bitmap, &colourmap, 0);
     ws2812.set(matrix.iter())?;
+    FreeRtos::delay_ms(1000);
+
+    // Reuse bitmap as sprite ('.' is transparent)
+    let sprite = Sprite::from_char_art(&bitmap, &colourmap[..4]);
+    for (flip_x, flip_y) in [(false, false), (true, false), (false, true), (true, true)] {
+        for offset in -8..=8 {
+            matrix.fill(Rgb::new(0, 0, 16));
+            let blit = Blit {
+                flip_x,
+                flip_y,
+                tint: (offset > 0).then_some(Rgb::new(255, 128, 0)),
+            };
+            sprite.blit(matrix, (offset, offset / 2), blit);
+            ws2812.set(matrix.iter())?;
+            FreeRtos::delay_ms(50);
+        }
+    }
     Ok(())
 }
 
@@ -2,8 +2,16 @@ use anyhow::Result;
 use esp_idf_hal::rmt::{config::TransmitConfig, TxRmtDriver};
 use esp_idf_hal::{delay::FreeRtos, gpio::OutputPin, prelude::Peripherals};
 
+use c3zero::draw::{Canvas, Geometry};
+use c3zero::easing::Easing;
+use c3zero::effect::EffectRegistry;
+use c3zero::font::FONT_8X8;
+use c3zero::image::{self, Filter, Quantise, ScaleMode};
 use c3zero::matrix_1d::{Matrix1D, Orientation, Panel};
 use c3zero::rgb::{self, Rgb, RgbLayout, RgbTransform};
+use c3zero::scroll::{ScrollMode, ScrollOptions, SmoothScroll};
+use c3zero::sprite::Sprite;
+use c3zero::tween::{Animator, Props, Repeat, SystemClock, Tween};
 use c3zero::ws2812_rmt::{Ws2812Rmt, Ws2812RmtSingle};
 
 fn main() -> Result<()> {
@@ -30,28 +38,177 @@ fn main() -> Result<()> {
             &mut ws2812,
             "This is a long message... 0123456789 ±!@£$%^&*()",
         )?;
+        scroll_modes(&mut ws2812, "Bounce!")?;
+        smooth_scroll(&mut ws2812, "Smooth...")?;
+        tween(&mut ws2812, "Hi!")?;
+        effects(&mut ws2812)?;
         chase(&mut ws2812)?;
+        image(&mut ws2812)?;
+        geometry(&mut ws2812)?;
     }
 }
 
+fn geometry(ws2812: &mut Ws2812Rmt) -> Result<()> {
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    // Mirrored text (for display behind glass)
+    matrix.draw_str("Hello", &FONT_8X8, Rgb::new(0, 64, 0), (0, 0));
+    matrix.geometry((0, 0), (16, 8), &[Geometry::FlipH]);
+    ws2812.set(matrix.iter())?;
+    FreeRtos::delay_ms(2000);
+    // Scroll by rolling pixels rather than redrawing
+    matrix.clear();
+    matrix.draw_str("Hi!", &FONT_8X8, Rgb::new(0, 0, 64), (0, 0));
+    for _ in 0..32 {
+        matrix.geometry((0, 0), (16, 8), &[Geometry::Roll(1, 0)]);
+        ws2812.set(matrix.iter())?;
+        FreeRtos::delay_ms(50);
+    }
+    // Upside down
+    matrix.geometry((0, 0), (16, 8), &[Geometry::Rotate180]);
+    ws2812.set(matrix.iter())?;
+    FreeRtos::delay_ms(2000);
+    Ok(())
+}
+
+fn image(ws2812: &mut Ws2812Rmt) -> Result<()> {
+    let logo = image::decode(include_bytes!("../../../images/logo.png"))?;
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    for mode in [ScaleMode::Fit, ScaleMode::Fill, ScaleMode::Crop] {
+        for brightness in [0.1, 0.3, 0.6] {
+            matrix.clear();
+            let q = Quantise {
+                brightness,
+                levels: 8,
+            };
+            image::draw_image(&mut matrix, &logo, mode, Filter::Box, Some(q));
+            ws2812.set(matrix.iter())?;
+            FreeRtos::delay_ms(1000);
+        }
+    }
+    Ok(())
+}
+
 fn scroll(ws2812: &mut Ws2812Rmt, msg: &str) -> Result<()> {
     let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
     let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
-    for x in matrix.scroll_iter(msg.len()) {
+    for x in matrix.scroll_iter(msg, &FONT_8X8) {
         matrix.clear();
-        matrix.draw_str(msg, Rgb::new(128, 0, 0), (x, 0));
+        matrix.draw_str(msg, &FONT_8X8, Rgb::new(128, 0, 0), (x, 0));
         ws2812.set(matrix.iter())?;
         FreeRtos::delay_ms(50);
     }
-    for x in matrix.scroll_iter(msg.len()) {
+    for x in matrix.scroll_iter(msg, &FONT_8X8) {
         matrix.clear();
-        matrix.draw_str(msg, Rgb::new(128, 0, 0), (x, 0));
+        matrix.draw_str(msg, &FONT_8X8, Rgb::new(128, 0, 0), (x, 0));
         ws2812.set(matrix.iter())?;
         FreeRtos::delay_ms(25);
     }
     Ok(())
 }
 
+fn scroll_modes(ws2812: &mut Ws2812Rmt, msg: &str) -> Result<()> {
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    for mode in [
+        ScrollMode::Right,
+        ScrollMode::Up,
+        ScrollMode::Down,
+        ScrollMode::Bounce,
+    ] {
+        let options = ScrollOptions {
+            mode,
+            lead: 2,
+            trail: 2,
+            pause: 10,
+        };
+        for p in matrix.scroll_positions(msg, &FONT_8X8, options) {
+            matrix.clear();
+            matrix.draw_str(msg, &FONT_8X8, Rgb::new(0, 64, 64), p);
+            ws2812.set(matrix.iter())?;
+            FreeRtos::delay_ms(50);
+        }
+    }
+    Ok(())
+}
+
+fn smooth_scroll(ws2812: &mut Ws2812Rmt, msg: &str) -> Result<()> {
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    // Slow scroll (8 px/s) - text is blended between pixels
+    let path = matrix.scroll_positions(msg, &FONT_8X8, ScrollOptions::default());
+    let mut scroll = SmoothScroll::new(path, 8.0);
+    while let Some(p) = scroll.tick(20) {
+        matrix.clear();
+        matrix.draw_str_smooth(msg, &FONT_8X8, Rgb::new(64, 32, 0), p);
+        ws2812.set(matrix.iter())?;
+        FreeRtos::delay_ms(20);
+    }
+    Ok(())
+}
+
+fn tween(ws2812: &mut Ws2812Rmt, msg: &str) -> Result<()> {
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    // Render text once as a white sprite so it can be scaled and tinted
+    let mut text = Matrix1D::<2>::new();
+    text.draw_str(msg, &FONT_8X8, rgb::WHITE, (0, 0));
+    let sprite = Sprite::from_fn(text.size(), |p| match text.get(p) {
+        Some(rgb) if rgb != rgb::OFF => (rgb, 255),
+        _ => (rgb::OFF, 0),
+    });
+    // Drop in from above growing from half size, fade from red to blue,
+    // then go back up
+    let start = Props {
+        position: (0.0, -8.0),
+        colour: Rgb::new(128, 0, 0),
+        brightness: 0.2,
+        scale: 0.5,
+    };
+    let end = Props {
+        position: (0.0, 0.0),
+        colour: Rgb::new(0, 0, 128),
+        brightness: 1.0,
+        scale: 1.0,
+    };
+    let tween = Tween::new(start)
+        .to(end, 800, Easing::Bounce)
+        .hold(1000)
+        .repeat(Repeat::Yoyo)
+        .cycles(4);
+    let clock = SystemClock::new();
+    let animator = Animator::start(tween, &clock);
+    while !animator.is_finished(&clock) {
+        let props = animator.value(&clock);
+        matrix.clear();
+        props.draw_sprite(&mut matrix, &sprite);
+        ws2812.set(matrix.iter())?;
+        FreeRtos::delay_ms(20);
+    }
+    Ok(())
+}
+
+fn effects(ws2812: &mut Ws2812Rmt) -> Result<()> {
+    let (p1, p2) = (Panel::new(Orientation::East), Panel::new(Orientation::East));
+    let mut matrix = Matrix1D::<2>::from_panels([p1, p2]);
+    let registry = EffectRegistry::default();
+    for name in registry.names() {
+        println!(">> Effect:: {name}");
+        if let Some(mut effect) = registry.create(name) {
+            let mut params = effect.defaults();
+            params.set("speed", "1.5")?;
+            for t in (0..5000).step_by(25) {
+                effect.render(t, &params, &mut matrix);
+                matrix.transform((0, 0), (16, 8), &[RgbTransform::Intensity(0.2)]);
+                ws2812.set(matrix.iter())?;
+                FreeRtos::delay_ms(25);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn chase(ws2812: &mut Ws2812Rmt) -> Result<()> {
     for o in [
         Orientation::North,
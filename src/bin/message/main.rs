@@ -1,19 +1,39 @@
 use anyhow::Result;
 use askama::Template;
-use esp_idf_hal::{delay::FreeRtos, gpio::OutputPin, prelude::Peripherals};
+use esp_idf_hal::{
+    delay::FreeRtos,
+    gpio::{OutputPin, PinDriver, Pull},
+    prelude::Peripherals,
+};
 use esp_idf_svc::eventloop::EspSystemEventLoop;
 use esp_idf_svc::http;
 use esp_idf_svc::http::server::{EspHttpConnection, Request};
 use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use esp_idf_svc::sntp::EspSntp;
 use esp_idf_svc::wifi::EspWifi;
 use serde::Deserialize;
 
+use c3zero::animation::AnimationSource;
+use c3zero::chart::{Chart, Range};
+use c3zero::clock::{self, ClockOptions};
+use c3zero::easing::Easing;
+use c3zero::effect::{EffectParams, EffectRegistry};
+use c3zero::game::{GameKind, Input};
 use c3zero::httpd;
+use c3zero::image::{self, ScaleMode};
+use c3zero::layout::PageMode;
 use c3zero::matrix_1d::{Orientation, Panel};
 use c3zero::message::{Message, Ws2812Message};
-use c3zero::nvs::APStore;
+use c3zero::nvs::{APStore, PlaylistStore};
+use c3zero::pattern::Pattern;
+use c3zero::playlist::Playlist;
 use c3zero::rgb::{self, Rgb, RgbLayout};
+use c3zero::schedule::{Action, Schedule};
+use c3zero::scroll::{ScrollMode, ScrollOptions};
 use c3zero::status::{LedState, Status};
+use c3zero::timer::{self, Alert, Countdown, TimerControl};
+use c3zero::transition::{Transition, TransitionKind};
+use c3zero::widget::Widget;
 use c3zero::wifi::{self, APConfig};
 
 const STARTING: LedState = LedState::On(rgb::BLUE);
@@ -22,6 +42,8 @@ const WIFI_CONNECT: LedState = LedState::Flash(rgb::BLUE, 250);
 const WIFI_CONNECTED: LedState = LedState::On(rgb::GREEN);
 const WIFI_AP_ACTIVE: LedState = LedState::Wheel(1);
 
+const BUTTON_POLL_MS: u32 = 20;
+
 fn main() -> Result<()> {
     esp_idf_hal::sys::link_patches();
 
@@ -49,6 +71,7 @@ fn main() -> Result<()> {
 
     // Initislise NVS APStore
     APStore::init(nvs_default_partition.clone())?;
+    PlaylistStore::init(nvs_default_partition.clone())?;
 
     // Initialise WiFi
     let mut wifi: EspWifi<'_> = EspWifi::new(
@@ -58,13 +81,30 @@ fn main() -> Result<()> {
     )?;
     wifi::wifi_init(&mut wifi)?;
 
+    // Spinner while scanning then progress through known networks
+    Ws2812Message::<2>::update(Message::Layers(vec![
+        Message::Widget("spinner".into(), Widget::Spinner(rgb::BLUE), (0, 0), (8, 8)),
+        Message::Widget(
+            "progress".into(),
+            Widget::Progress(0.0, rgb::BLUE),
+            (9, 3),
+            (7, 2),
+        ),
+    ]))?;
+
     // Initial scan
     Status::update(WIFI_SCAN)?;
     wifi::wifi_scan(&mut wifi)?;
 
     let mut wifi_config: Option<APConfig> = None;
-    for config in wifi::find_known_aps() {
+    let known = wifi::find_known_aps();
+    let attempts = known.len();
+    for (i, config) in known.into_iter().enumerate() {
         Status::update(WIFI_CONNECT)?;
+        Ws2812Message::<2>::update(Message::UpdateWidget(
+            "progress".into(),
+            Widget::Progress(i as f32 / attempts as f32, rgb::BLUE),
+        ))?;
         log::info!("Trying network: {}", config.ssid);
         match wifi::connect_wifi(&mut wifi, &config, 10000) {
             Ok(true) => {
@@ -81,18 +121,39 @@ fn main() -> Result<()> {
         }
     }
 
+    // Synchronise system clock when connected (needed for Message::Clock)
+    let _sntp = wifi_config
+        .as_ref()
+        .map(|_| EspSntp::new_default())
+        .transpose()?;
+
     let mut server = if let Some(config) = wifi_config {
         Status::update(WIFI_CONNECTED)?;
-        Ws2812Message::<2>::update(Message::Scroll(
-            format!("{} : {}", config.ssid, wifi.sta_netif().get_ip_info()?.ip),
-            rgb::BLUE,
-            1,
-        ))?;
+        let rssi = wifi::scan_rssi(config.ssid.as_str());
+        Ws2812Message::<2>::update(Message::Layers(vec![
+            Message::Scroll(
+                format!("{} : {}", config.ssid, wifi.sta_netif().get_ip_info()?.ip),
+                rgb::BLUE,
+                ScrollOptions::default(),
+                40.0,
+            ),
+            Message::Widget(
+                "wifi".into(),
+                Widget::Wifi(rssi, rgb::GREEN),
+                (12, 0),
+                (4, 8),
+            ),
+        ]))?;
         log::info!("Connected to SSID: {}", config.ssid);
         httpd::start_http_server()?
     } else {
         Status::update(WIFI_AP_ACTIVE)?;
-        Ws2812Message::<2>::update(Message::Scroll("Starting AP:".to_string(), rgb::BLUE, 1))?;
+        Ws2812Message::<2>::update(Message::Scroll(
+            "Starting AP:".to_string(),
+            rgb::BLUE,
+            ScrollOptions::default(),
+            40.0,
+        ))?;
         log::info!("No valid config found - starting AP");
         wifi::start_access_point(&mut wifi)?;
         httpd::start_http_server()?
@@ -101,24 +162,216 @@ fn main() -> Result<()> {
     // Add local handlers
     server.fn_handler("/message", http::Method::Get, handle_message_form)?;
     server.fn_handler("/message", http::Method::Post, handle_message_post)?;
+    server.fn_handler("/message/stats", http::Method::Get, handle_message_stats)?;
+    server.fn_handler(
+        "/message/samples",
+        http::Method::Post,
+        handle_message_samples,
+    )?;
+    server.fn_handler(
+        "/message/control",
+        http::Method::Post,
+        handle_message_control,
+    )?;
+
+    server.fn_handler("/game/input", http::Method::Post, handle_game_input)?;
+    server.fn_handler("/pattern", http::Method::Post, handle_pattern_post)?;
+    server.fn_handler("/playlists", http::Method::Get, handle_playlists_get)?;
+    server.fn_handler("/playlists", http::Method::Post, handle_playlists_post)?;
+    server.fn_handler("/schedule", http::Method::Get, handle_schedule_get)?;
+    server.fn_handler("/schedule", http::Method::Post, handle_schedule_post)?;
 
+    // C3-Zero onboard BOOT button = GPIO9 (pulls pin low when pressed)
+    let mut button = PinDriver::input(peripherals.pins.gpio9)?;
+    button.set_pull(Pull::Up)?;
+    let mut pressed = false;
+
+    // Scheduled message last sent (only resent when schedule or playlist
+    // changes so manual messages stay until the next rule fires)
+    let mut scheduled: Option<Message> = None;
+    let mut ticks = 0_u32;
     loop {
-        FreeRtos::delay_ms(1000); // Delay for 100 milliseconds
+        // Poll button often enough for games
+        FreeRtos::delay_ms(BUTTON_POLL_MS);
+        let down = button.is_low();
+        if down && !pressed {
+            Ws2812Message::<2>::update(Message::Input(Input::Action))?;
+        }
+        pressed = down;
+        // Check schedule once a second
+        ticks = ticks.wrapping_add(1);
+        if ticks % (1000 / BUTTON_POLL_MS) != 0 {
+            continue;
+        }
+        let Some(now) = clock::unix_ms() else {
+            continue;
+        };
+        let message = match PlaylistStore::get_schedule().active_at(now) {
+            Some(Action::Playlist(name)) => match PlaylistStore::get_playlist(name) {
+                Some(playlist) => Some(Message::Playlist(playlist)),
+                None => {
+                    log::error!("Scheduled playlist not found: {}", name);
+                    None
+                }
+            },
+            Some(Action::Off) => Some(Message::Off),
+            None => None,
+        };
+        if message != scheduled {
+            if let Some(message) = message.clone() {
+                log::info!("Schedule:: {:?}", message);
+                Ws2812Message::<2>::update(message)?;
+            }
+        }
+        scheduled = message;
+    }
+}
+
+// Read request body (up to buf size)
+fn read_body<'a>(
+    request: &mut Request<&mut EspHttpConnection>,
+    buf: &'a mut [u8],
+) -> anyhow::Result<&'a [u8]> {
+    let mut len = 0;
+    while len < buf.len() {
+        match request.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
     }
+    Ok(&buf[..len])
+}
+
+fn write_json<T: serde::Serialize>(
+    request: Request<&mut EspHttpConnection>,
+    value: &T,
+) -> anyhow::Result<()> {
+    let mut response = request.into_response(200, None, &[("Content-Type", "application/json")])?;
+    response.write(serde_json::to_string(value)?.as_bytes())?;
+    Ok(())
+}
+
+// Compile and show pattern (body is pattern source) - compile errors are
+// returned as 400 Bad Request with the error message
+//
+//   curl --data-binary @plasma.pat http://<ip>/pattern
+fn handle_pattern_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    let mut buf = vec![0_u8; 4096];
+    let source = std::str::from_utf8(read_body(&mut request, &mut buf)?)?.to_string();
+    if let Err(e) = Pattern::compile(&source) {
+        let mut response = request.into_response(400, Some("Pattern error"), &[])?;
+        response.write(format!("{}\n", e).as_bytes())?;
+        return Ok(());
+    }
+    Ws2812Message::<2>::update(Message::Pattern(source, EffectParams::default()))?;
+    request.into_ok_response()?;
+    Ok(())
+}
+
+fn handle_playlists_get(request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    write_json(request, &PlaylistStore::get_playlists())
+}
+
+// Replace all playlists (JSON array - see c3zero::playlist)
+fn handle_playlists_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    let mut buf = vec![0_u8; 8192];
+    let playlists: Vec<Playlist> = serde_json::from_slice(read_body(&mut request, &mut buf)?)?;
+    // Check scenes are valid before saving
+    for entry in playlists.iter().flat_map(|p| p.entries.iter()) {
+        Message::try_from(&entry.scene)?;
+    }
+    PlaylistStore::set_playlists(playlists)?;
+    write_json(request, &PlaylistStore::get_playlists())
+}
+
+fn handle_schedule_get(request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    write_json(request, &PlaylistStore::get_schedule())
+}
+
+// Replace schedule (JSON - see c3zero::schedule)
+fn handle_schedule_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    let mut buf = vec![0_u8; 2048];
+    let schedule: Schedule = serde_json::from_slice(read_body(&mut request, &mut buf)?)?;
+    PlaylistStore::set_schedule(schedule)?;
+    write_json(request, &PlaylistStore::get_schedule())
 }
 
 #[derive(askama::Template)]
 #[template(path = "message_page.html")]
-struct MessagePage {}
+struct MessagePage {
+    effects: Vec<&'static str>,
+}
 
 fn handle_message_form(request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
-    let message_page = MessagePage {};
+    let message_page = MessagePage {
+        effects: EffectRegistry::default().names().collect(),
+    };
     let mut response = request.into_ok_response()?;
     let html = message_page.render()?;
     response.write(html.as_bytes())?;
     Ok::<(), anyhow::Error>(())
 }
 
+fn handle_message_stats(request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    let stats = Ws2812Message::<2>::stats()?;
+    let mut response = request.into_response(200, None, &[("Content-Type", "application/json")])?;
+    response.write(serde_json::to_string(&stats)?.as_bytes())?;
+    Ok(())
+}
+
+// Control running countdown/stopwatch/counter (action=start|pause|reset|increment:n|decrement:n)
+fn handle_message_control(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    #[derive(Deserialize)]
+    struct ControlParams {
+        action: String,
+    }
+
+    let mut buf = [0_u8; 128];
+    let len = request.read(&mut buf)?;
+    let params: ControlParams = serde_urlencoded::from_bytes(&buf[..len])?;
+    let control: TimerControl = params.action.parse()?;
+    Ws2812Message::<2>::update(Message::Control(control))?;
+    request.into_response(302, None, &[("Location", "/message")])?;
+    Ok(())
+}
+
+// Input to running game (key=up|down|left|right|action - browser key names
+// such as ArrowUp also accepted)
+fn handle_game_input(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    #[derive(Deserialize)]
+    struct InputParams {
+        key: String,
+    }
+
+    let mut buf = [0_u8; 128];
+    let len = request.read(&mut buf)?;
+    let params: InputParams = serde_urlencoded::from_bytes(&buf[..len])?;
+    let input: Input = params.key.parse()?;
+    Ws2812Message::<2>::update(Message::Input(input))?;
+    request.into_ok_response()?;
+    Ok(())
+}
+
+// Push samples to running chart (values=1.5,2,3)
+fn handle_message_samples(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
+    #[derive(Deserialize)]
+    struct SampleParams {
+        values: String,
+    }
+
+    let mut buf = [0_u8; 512];
+    let len = request.read(&mut buf)?;
+    let params: SampleParams = serde_urlencoded::from_bytes(&buf[..len])?;
+    let samples = params
+        .values
+        .split(',')
+        .map(|v| v.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ws2812Message::<2>::update(Message::Samples(samples))?;
+    request.into_ok_response()?;
+    Ok(())
+}
+
 fn handle_message_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::Result<()> {
     #[derive(Deserialize, Default)]
     struct MessageParams {
@@ -133,16 +386,112 @@ fn handle_message_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::
         b: u8,
         #[serde(default = "default_delay")]
         delay: u8,
+        // Page scroll step (ticks per row)
+        #[serde(default = "default_page_step")]
+        page_step: u8,
+        #[serde(default = "default_speed")]
+        speed: f32,
+        #[serde(default)]
+        scroll: u8,
+        #[serde(default)]
+        gap: u8,
+        #[serde(default)]
+        pause: u8,
+        #[serde(default)]
+        effect: String,
+        // Effect parameters (empty for effect default)
+        #[serde(default)]
+        palette: String,
+        #[serde(default)]
+        density: String,
+        #[serde(default)]
+        effect_speed: String,
+        // Transition from current message (eg. "wipe-left" - empty for none)
+        #[serde(default)]
+        transition: String,
+        #[serde(default)]
+        duration: u32,
+        #[serde(default)]
+        easing: String,
+        // Clock (strftime-like format - empty for default)
+        #[serde(default)]
+        clock_format: String,
+        #[serde(default)]
+        hour12: bool,
+        #[serde(default)]
+        seconds: bool,
+        #[serde(default)]
+        date: bool,
+        #[serde(default)]
+        utc_offset: i32, // Minutes
+        // Countdown for seconds or until local time (HH:MM)
+        #[serde(default)]
+        countdown: u32,
+        #[serde(default)]
+        until: String,
+        // Alert colour (#rrggbb - empty for message colour)
+        #[serde(default)]
+        alert: String,
+        #[serde(default)]
+        alert_flash: bool,
+        #[serde(default)]
+        counter: i64,
+        // Chart type and range (empty for autoscale)
+        #[serde(default)]
+        chart: String,
+        #[serde(default)]
+        range_min: String,
+        #[serde(default)]
+        range_max: String,
+        #[serde(default = "default_history")]
+        history: usize,
+        // Pattern source (see c3zero::pattern)
+        #[serde(default)]
+        pattern: String,
+        // Game (snake|pong|tetris)
+        #[serde(default)]
+        game: String,
+    }
+
+    fn default_history() -> usize {
+        16
     }
 
     fn default_delay() -> u8 {
         1
     }
 
-    let mut buf = [0_u8; 1024];
-    let len = request.read(&mut buf)?;
-    log::info!("Req:: {}", std::str::from_utf8(&buf[..len])?);
-    let params: MessageParams = serde_urlencoded::from_bytes(&buf[..len])?;
+    fn default_speed() -> f32 {
+        40.0
+    }
+
+    fn default_page_step() -> u8 {
+        1
+    }
+
+    // Large enough for pattern source
+    let mut buf = vec![0_u8; 4096];
+    let body = read_body(&mut request, &mut buf)?;
+    log::info!("Req:: {}", std::str::from_utf8(body)?);
+    let params: MessageParams = serde_urlencoded::from_bytes(body)?;
+
+    let scroll = ScrollOptions {
+        mode: match params.scroll {
+            1 => ScrollMode::Right,
+            2 => ScrollMode::Up,
+            3 => ScrollMode::Down,
+            4 => ScrollMode::Bounce,
+            _ => ScrollMode::Left,
+        },
+        lead: params.gap as i32,
+        trail: params.gap as i32,
+        pause: params.pause as usize,
+    };
+
+    let palette = match params.palette.as_str() {
+        "colour" => format!("#{:02x}{:02x}{:02x}", params.r, params.g, params.b),
+        palette => palette.to_string(),
+    };
 
     let message = match params.mode {
         0 => Message::Off,
@@ -150,11 +499,136 @@ fn handle_message_post(mut request: Request<&mut EspHttpConnection>) -> anyhow::
         2 => Message::Scroll(
             params.message,
             Rgb::new(params.r, params.g, params.b),
+            scroll,
+            params.speed,
+        ),
+        3 => Message::Pages(
+            params.message,
+            Rgb::new(params.r, params.g, params.b),
+            PageMode::Flip,
+            params.delay as usize,
+        ),
+        4 => Message::Pages(
+            params.message,
+            Rgb::new(params.r, params.g, params.b),
+            PageMode::Scroll(params.page_step.max(1) as usize),
             params.delay as usize,
         ),
+        5 => Message::Animation(AnimationSource::Embedded(include_bytes!(
+            "../../../images/spinner.gif"
+        ))),
+        // Message is built-in image name
+        6 => match image::builtin(&params.message) {
+            Some(_) => Message::Image(params.message, ScaleMode::Fit),
+            None => return Err(anyhow::anyhow!("Unknown image: {}", params.message)),
+        },
+        // Scroll message over built-in animation
+        7 => Message::Layers(vec![
+            Message::Animation(AnimationSource::Embedded(include_bytes!(
+                "../../../images/spinner.gif"
+            ))),
+            Message::Scroll(
+                params.message,
+                Rgb::new(params.r, params.g, params.b),
+                scroll,
+                params.speed,
+            ),
+        ]),
+        8 => {
+            let effect = EffectRegistry::default()
+                .create(&params.effect)
+                .ok_or(anyhow::anyhow!("Unknown effect: {}", params.effect))?;
+            let mut effect_params = effect.defaults();
+            for (name, value) in [
+                ("palette", &palette),
+                ("density", &params.density),
+                ("speed", &params.effect_speed),
+            ] {
+                if !value.is_empty() {
+                    effect_params.set(name, value)?;
+                }
+            }
+            Message::Effect(params.effect, effect_params)
+        }
+        9 => Message::Clock(
+            ClockOptions {
+                format: params.clock_format,
+                hour12: params.hour12,
+                seconds: params.seconds,
+                date: params.date,
+                utc_offset_min: params.utc_offset,
+                ..Default::default()
+            },
+            Rgb::new(params.r, params.g, params.b),
+        ),
+        10 => {
+            let countdown = if params.until.trim().is_empty() {
+                Countdown::Duration(params.countdown as u64 * 1000)
+            } else {
+                let time = timer::parse_time_of_day(&params.until)?;
+                let now = clock::unix_ms().ok_or(anyhow::anyhow!("Clock not synchronised"))?;
+                Countdown::Until(timer::next_time_of_day(now, params.utc_offset, time))
+            };
+            let alert = Alert {
+                colour: match params.alert.as_str() {
+                    "" => None,
+                    colour => Some(colour.parse()?),
+                },
+                flash: params.alert_flash,
+            };
+            Message::Countdown(countdown, Rgb::new(params.r, params.g, params.b), alert)
+        }
+        11 => Message::Stopwatch(Rgb::new(params.r, params.g, params.b)),
+        12 => Message::Counter(params.counter, Rgb::new(params.r, params.g, params.b)),
+        13 => {
+            let mut chart = Chart::new(params.chart.parse()?);
+            if !palette.is_empty() {
+                chart.palette = palette.parse()?;
+            }
+            if !params.range_min.is_empty() && !params.range_max.is_empty() {
+                chart.range = Range::Fixed(params.range_min.parse()?, params.range_max.parse()?);
+            }
+            Message::Chart(chart, params.history)
+        }
+        // Message is playlist name
+        14 => Message::Playlist(
+            PlaylistStore::get_playlist(&params.message)
+                .ok_or(anyhow::anyhow!("Unknown playlist: {}", params.message))?,
+        ),
+        15 => {
+            // Compile here so errors are returned to the client
+            Pattern::compile(&params.pattern)?;
+            let mut effect_params = EffectParams::default();
+            for (name, value) in [
+                ("palette", &palette),
+                ("density", &params.density),
+                ("speed", &params.effect_speed),
+            ] {
+                if !value.is_empty() {
+                    effect_params.set(name, value)?;
+                }
+            }
+            Message::Pattern(params.pattern, effect_params)
+        }
+        16 => Message::Game(params.game.parse::<GameKind>()?),
         _ => return Err(anyhow::anyhow!("Invalid mode")),
     };
 
+    // Playlists switch scenes themselves so aren't wrapped in a transition
+    let message = if params.transition.is_empty() || matches!(message, Message::Playlist(_)) {
+        message
+    } else {
+        let kind: TransitionKind = params.transition.parse()?;
+        let easing: Easing = match params.easing.as_str() {
+            "" => Easing::default(),
+            easing => easing.parse()?,
+        };
+        Message::Transition(
+            Box::new(message),
+            Transition::new(kind, params.duration, easing),
+        )
+    };
+
     Ws2812Message::<2>::update(message)?;
     request.into_response(302, None, &[("Location", "/message")])?;
 
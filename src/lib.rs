@@ -1,12 +1,34 @@
 pub mod utils;
 
+pub use utils::animation;
+pub use utils::bdf;
+pub use utils::chart;
+pub use utils::clock;
+pub use utils::draw;
+pub use utils::easing;
+pub use utils::effect;
+pub use utils::font;
+pub use utils::game;
 pub use utils::hash;
 pub use utils::httpd;
+pub use utils::image;
+pub use utils::layer;
+pub use utils::layout;
 pub use utils::matrix;
 pub use utils::matrix_1d;
 pub use utils::message;
 pub use utils::nvs;
+pub use utils::pattern;
+pub use utils::playlist;
+pub use utils::render;
 pub use utils::rgb;
+pub use utils::schedule;
+pub use utils::scroll;
+pub use utils::sprite;
 pub use utils::status;
+pub use utils::timer;
+pub use utils::transition;
+pub use utils::tween;
+pub use utils::widget;
 pub use utils::wifi;
 pub use utils::ws2812_rmt;
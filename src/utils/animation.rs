@@ -0,0 +1,543 @@
+// Animation playback from animated GIFs or C3A frame sequences
+//
+// Frames are decoded one at a time from a Read + Seek source (eg. an
+// embedded byte slice) so only the current screen is held in RAM
+//
+// C3A format (all values little endian):
+//
+//   "C3A1" | width: u16 | height: u16 | loops: u16 (0 = forever)
+//   palette_len: u8 | palette: [r, g, b] * palette_len
+//   frames (until EOF):
+//     delay_ms: u16 | dispose: u8 | x: u16 | y: u16 | w: u16 | h: u16
+//     RLE pixels: [run: u8 (1..=255), index: u8] covering w * h pixels
+//     (index 255 is transparent)
+//
+// Dispose has the same meaning as for GIF (0/1 = keep, 2 = clear frame
+// area, 3 = restore previous) and is applied before the next frame
+use anyhow::{anyhow, bail, Result};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+
+use crate::rgb::Rgb;
+use crate::sprite::Sprite;
+
+const C3A_MAGIC: &[u8; 4] = b"C3A1";
+const C3A_TRANSPARENT: u8 = 255;
+
+// Largest screen we will allocate (pixels)
+const MAX_PIXELS: usize = 128 * 128;
+// GIF frames with no delay are conventionally shown for 100ms
+const DEFAULT_DELAY_MS: u32 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Dispose {
+    #[default]
+    Keep,
+    Background, // Clear frame area to transparent
+    Previous,   // Restore screen from before frame was drawn
+}
+
+impl Dispose {
+    fn from_u8(v: u8) -> Self {
+        match v {
+            2 => Dispose::Background,
+            3 => Dispose::Previous,
+            _ => Dispose::Keep,
+        }
+    }
+    fn to_u8(self) -> u8 {
+        match self {
+            Dispose::Keep => 1,
+            Dispose::Background => 2,
+            Dispose::Previous => 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub image: Sprite,
+    pub delay_ms: u32,
+}
+
+pub trait FrameSource {
+    /// Screen size (width, height)
+    fn size(&self) -> (i32, i32);
+    /// Draw next frame onto screen - returns frame delay (None at end of animation)
+    fn next_frame(&mut self) -> Result<Option<u32>>;
+    /// Screen with current frame composited (updated in place by next_frame)
+    fn screen(&self) -> &Sprite;
+    /// Restart from first frame
+    fn rewind(&mut self) -> Result<()>;
+    /// Number of times to play animation (None = forever)
+    fn loops(&self) -> Option<u32>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnimationSource {
+    Embedded(&'static [u8]),
+}
+
+impl AnimationSource {
+    pub fn open(&self) -> Result<Box<dyn FrameSource + Send>> {
+        match self {
+            AnimationSource::Embedded(data) => open(Cursor::new(*data)),
+        }
+    }
+}
+
+/// Open GIF or C3A frame source (detected from signature)
+pub fn open<R: Read + Seek + Send + 'static>(mut reader: R) -> Result<Box<dyn FrameSource + Send>> {
+    let mut magic = [0_u8; 4];
+    reader.read_exact(&mut magic)?;
+    reader.seek(SeekFrom::Current(-4))?;
+    match &magic {
+        b"GIF8" => Ok(Box::new(GifFrames::new(reader)?)),
+        C3A_MAGIC => Ok(Box::new(C3aFrames::new(reader)?)),
+        _ => bail!("Unknown animation format"),
+    }
+}
+
+fn check_size(width: usize, height: usize) -> Result<()> {
+    match width.checked_mul(height) {
+        Some(1..=MAX_PIXELS) => Ok(()),
+        _ => bail!("Animation size {}x{} not supported", width, height),
+    }
+}
+
+// Screen that frames are drawn onto (handles disposal)
+//
+// Frames are drawn in place so the only other buffer is the copy of the
+// screen kept for Dispose::Previous (reused between frames)
+struct Compositor {
+    width: usize,
+    height: usize,
+    screen: Sprite,
+    previous: Vec<(Rgb, u8)>, // Empty if nothing to restore
+    pending: Option<(Dispose, (usize, usize, usize, usize))>,
+}
+
+impl Compositor {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            screen: Sprite::from_fn((width as i32, height as i32), |_| (Rgb::default(), 0)),
+            previous: Vec::new(),
+            pending: None,
+        }
+    }
+    fn points(&self) -> impl Iterator<Item = (i32, i32)> {
+        let (w, h) = (self.width as i32, self.height as i32);
+        (0..h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+    fn reset(&mut self) {
+        for p in self.points() {
+            self.screen.set_pixel(p, (Rgb::default(), 0));
+        }
+        self.previous.clear();
+        self.pending = None;
+    }
+    // Draw frame at (x, y) - pixel(i) returns colour for i'th pixel in frame
+    // (None is transparent)
+    fn draw(
+        &mut self,
+        (x, y, w, h): (usize, usize, usize, usize),
+        dispose: Dispose,
+        mut pixel: impl FnMut(usize) -> Option<Rgb>,
+    ) {
+        // Apply disposal from previous frame
+        match self.pending.take() {
+            Some((Dispose::Background, (px, py, pw, ph))) => {
+                for row in py..(py + ph).min(self.height) {
+                    for col in px..(px + pw).min(self.width) {
+                        self.screen
+                            .set_pixel((col as i32, row as i32), (Rgb::default(), 0));
+                    }
+                }
+            }
+            Some((Dispose::Previous, _)) if !self.previous.is_empty() => {
+                for (p, px) in self.points().zip(&self.previous) {
+                    self.screen.set_pixel(p, *px);
+                }
+                self.previous.clear();
+            }
+            _ => {}
+        }
+        if dispose == Dispose::Previous {
+            let screen = &self.screen;
+            self.previous.clear();
+            self.previous
+                .extend(self.points().map(|p| screen.pixel(p).unwrap_or_default()));
+        }
+        for i in 0..w * h {
+            let (col, row) = (x + i % w, y + i / w);
+            if let Some(rgb) = pixel(i) {
+                if col < self.width && row < self.height {
+                    self.screen.set_pixel((col as i32, row as i32), (rgb, 255));
+                }
+            }
+        }
+        self.pending = Some((dispose, (x, y, w, h)));
+    }
+}
+
+pub struct GifFrames<R: Read + Seek> {
+    decoder: Option<gif::Decoder<R>>,
+    start: u64,
+    compositor: Compositor,
+    repeat: gif::Repeat,
+}
+
+fn gif_decoder<R: Read>(reader: R) -> Result<gif::Decoder<R>> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    Ok(options.read_info(reader)?)
+}
+
+impl<R: Read + Seek> GifFrames<R> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let start = reader.stream_position()?;
+        let decoder = gif_decoder(reader)?;
+        let (w, h) = (decoder.width() as usize, decoder.height() as usize);
+        check_size(w, h)?;
+        Ok(Self {
+            decoder: Some(decoder),
+            start,
+            compositor: Compositor::new(w, h),
+            repeat: gif::Repeat::Finite(0),
+        })
+    }
+}
+
+impl<R: Read + Seek> FrameSource for GifFrames<R> {
+    fn size(&self) -> (i32, i32) {
+        (self.compositor.width as i32, self.compositor.height as i32)
+    }
+    fn next_frame(&mut self) -> Result<Option<u32>> {
+        let decoder = self
+            .decoder
+            .as_mut()
+            .ok_or_else(|| anyhow!("GIF decoder not available"))?;
+        let frame = match decoder.read_next_frame()? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+        let dispose = match frame.dispose {
+            gif::DisposalMethod::Background => Dispose::Background,
+            gif::DisposalMethod::Previous => Dispose::Previous,
+            _ => Dispose::Keep,
+        };
+        let rect = (
+            frame.left as usize,
+            frame.top as usize,
+            frame.width as usize,
+            frame.height as usize,
+        );
+        let buffer = &frame.buffer;
+        self.compositor
+            .draw(rect, dispose, |i| match buffer.get(i * 4..i * 4 + 4) {
+                Some(&[r, g, b, a]) if a > 0 => Some(Rgb::new(r, g, b)),
+                _ => None,
+            });
+        let delay_ms = frame.delay as u32 * 10;
+        // Loop count (NETSCAPE extension) is read with first frame
+        self.repeat = decoder.repeat();
+        Ok(Some(delay_ms))
+    }
+    fn screen(&self) -> &Sprite {
+        &self.compositor.screen
+    }
+    fn rewind(&mut self) -> Result<()> {
+        let decoder = self
+            .decoder
+            .take()
+            .ok_or_else(|| anyhow!("GIF decoder not available"))?;
+        // Discard decoder buffer and restart from header
+        let mut reader = decoder.into_inner().into_inner();
+        reader.seek(SeekFrom::Start(self.start))?;
+        self.decoder = Some(gif_decoder(reader)?);
+        self.compositor.reset();
+        Ok(())
+    }
+    fn loops(&self) -> Option<u32> {
+        match self.repeat {
+            gif::Repeat::Infinite => None,
+            gif::Repeat::Finite(n) => Some(n as u32 + 1),
+        }
+    }
+}
+
+pub struct C3aFrames<R: Read + Seek> {
+    reader: BufReader<R>,
+    frames: u64, // Offset of first frame
+    compositor: Compositor,
+    palette: Vec<Rgb>,
+    loops: u16,
+    indices: Vec<u8>, // Expanded RLE pixels (reused between frames)
+}
+
+fn read_u8(r: &mut impl Read) -> Result<u8> {
+    let mut b = [0_u8; 1];
+    r.read_exact(&mut b)?;
+    Ok(b[0])
+}
+
+fn read_u16(r: &mut impl Read) -> Result<u16> {
+    let mut b = [0_u8; 2];
+    r.read_exact(&mut b)?;
+    Ok(u16::from_le_bytes(b))
+}
+
+impl<R: Read + Seek> C3aFrames<R> {
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut magic = [0_u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != C3A_MAGIC {
+            bail!("C3A invalid header");
+        }
+        let (w, h) = (
+            read_u16(&mut reader)? as usize,
+            read_u16(&mut reader)? as usize,
+        );
+        check_size(w, h)?;
+        let loops = read_u16(&mut reader)?;
+        let mut palette = vec![0_u8; read_u8(&mut reader)? as usize * 3];
+        reader.read_exact(&mut palette)?;
+        let frames = reader.stream_position()?;
+        Ok(Self {
+            reader,
+            frames,
+            compositor: Compositor::new(w, h),
+            palette: palette
+                .chunks(3)
+                .map(|c| Rgb::new(c[0], c[1], c[2]))
+                .collect(),
+            loops,
+            indices: Vec::new(),
+        })
+    }
+}
+
+impl<R: Read + Seek> FrameSource for C3aFrames<R> {
+    fn size(&self) -> (i32, i32) {
+        (self.compositor.width as i32, self.compositor.height as i32)
+    }
+    fn next_frame(&mut self) -> Result<Option<u32>> {
+        let r = &mut self.reader;
+        // Clean EOF at frame boundary is end of animation
+        let delay_ms = match read_u16(r) {
+            Ok(delay) => delay as u32,
+            Err(e) => match e.downcast_ref::<std::io::Error>() {
+                Some(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+                _ => return Err(e),
+            },
+        };
+        let dispose = Dispose::from_u8(read_u8(r)?);
+        let rect = (
+            read_u16(r)? as usize,
+            read_u16(r)? as usize,
+            read_u16(r)? as usize,
+            read_u16(r)? as usize,
+        );
+        let len = rect.2 * rect.3;
+        if len > MAX_PIXELS {
+            bail!("C3A frame too large");
+        }
+        // Expand RLE runs (frame is at most screen sized)
+        let indices = &mut self.indices;
+        indices.clear();
+        while indices.len() < len {
+            let (run, index) = (read_u8(r)? as usize, read_u8(r)?);
+            if run == 0 || indices.len() + run > len {
+                bail!("C3A invalid pixel run");
+            }
+            indices.resize(indices.len() + run, index);
+        }
+        let palette = &self.palette;
+        self.compositor.draw(rect, dispose, |i| match indices[i] {
+            C3A_TRANSPARENT => None,
+            index => palette.get(index as usize).copied(),
+        });
+        Ok(Some(delay_ms))
+    }
+    fn screen(&self) -> &Sprite {
+        &self.compositor.screen
+    }
+    fn rewind(&mut self) -> Result<()> {
+        self.reader.seek(SeekFrom::Start(self.frames))?;
+        self.compositor.reset();
+        Ok(())
+    }
+    fn loops(&self) -> Option<u32> {
+        match self.loops {
+            0 => None,
+            n => Some(n as u32),
+        }
+    }
+}
+
+/// Encode frames (each full screen) as C3A
+///
+/// Frames are stored as the changed area from the previous frame where
+/// possible. Pixels with alpha < 128 are transparent and at most 255
+/// distinct colours are supported
+pub fn encode_c3a(frames: &[Frame], loops: u16) -> Result<Vec<u8>> {
+    let (w, h) = match frames.first() {
+        Some(frame) => frame.image.size(),
+        None => bail!("No frames to encode"),
+    };
+    check_size(w as usize, h as usize)?;
+    if frames.iter().any(|f| f.image.size() != (w, h)) {
+        bail!("Frames must all be {}x{}", w, h);
+    }
+    let pixel = |f: &Frame, x: i32, y: i32| match f.image.pixel((x, y)) {
+        Some((rgb, a)) if a >= 128 => Some(rgb),
+        _ => None,
+    };
+    let mut palette: Vec<Rgb> = vec![];
+    for f in frames {
+        for (x, y) in (0..h).flat_map(|y| (0..w).map(move |x| (x, y))) {
+            if let Some(rgb) = pixel(f, x, y) {
+                if !palette.contains(&rgb) {
+                    palette.push(rgb);
+                }
+            }
+        }
+    }
+    if palette.len() > C3A_TRANSPARENT as usize {
+        bail!(
+            "Too many colours ({} - max {})",
+            palette.len(),
+            C3A_TRANSPARENT
+        );
+    }
+    // Frame can only be drawn over previous frame if no pixels become transparent
+    let needs_clear = |i: usize| {
+        i > 0
+            && i < frames.len()
+            && (0..h).any(|y| {
+                (0..w).any(|x| {
+                    pixel(&frames[i - 1], x, y).is_some() && pixel(&frames[i], x, y).is_none()
+                })
+            })
+    };
+
+    let mut out = C3A_MAGIC.to_vec();
+    for v in [w as u16, h as u16, loops] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.push(palette.len() as u8);
+    for rgb in &palette {
+        out.extend_from_slice(&[rgb.r(), rgb.g(), rgb.b()]);
+    }
+    for (i, frame) in frames.iter().enumerate() {
+        let full = i == 0 || needs_clear(i) || needs_clear(i + 1);
+        let dispose = if needs_clear(i + 1) {
+            Dispose::Background
+        } else {
+            Dispose::Keep
+        };
+        let changed = |x: i32, y: i32| full || pixel(frame, x, y) != pixel(&frames[i - 1], x, y);
+        // Bounding box of changed pixels (1x1 if nothing changed)
+        let (mut x1, mut y1, mut x2, mut y2) = (w, h, 0, 0);
+        for (x, y) in (0..h).flat_map(|y| (0..w).map(move |x| (x, y))) {
+            if changed(x, y) {
+                (x1, y1, x2, y2) = (x1.min(x), y1.min(y), x2.max(x + 1), y2.max(y + 1));
+            }
+        }
+        if x1 >= x2 {
+            (x1, y1, x2, y2) = (0, 0, 1, 1);
+        }
+        out.extend_from_slice(&(frame.delay_ms.min(u16::MAX as u32) as u16).to_le_bytes());
+        out.push(dispose.to_u8());
+        for v in [x1, y1, x2 - x1, y2 - y1] {
+            out.extend_from_slice(&(v as u16).to_le_bytes());
+        }
+        let mut runs: Vec<(u8, u8)> = vec![];
+        for (x, y) in (y1..y2).flat_map(|y| (x1..x2).map(move |x| (x, y))) {
+            let index = match pixel(frame, x, y) {
+                Some(rgb) if changed(x, y) => {
+                    palette.iter().position(|p| *p == rgb).unwrap_or(0) as u8
+                }
+                _ => C3A_TRANSPARENT,
+            };
+            match runs.last_mut() {
+                Some((run, last)) if *last == index && *run < 255 => *run += 1,
+                _ => runs.push((1, index)),
+            }
+        }
+        for (run, index) in runs {
+            out.extend_from_slice(&[run, index]);
+        }
+    }
+    Ok(out)
+}
+
+// Plays frame source in real time
+pub struct Player {
+    source: Box<dyn FrameSource + Send>,
+    started: bool,
+    remaining_ms: i64, // Carries overshoot so tick rate doesn't stretch delays
+    played: u32,
+    finished: bool,
+}
+
+impl Player {
+    pub fn new(source: Box<dyn FrameSource + Send>) -> Self {
+        Self {
+            source,
+            started: false,
+            remaining_ms: 0,
+            played: 0,
+            finished: false,
+        }
+    }
+    pub fn size(&self) -> (i32, i32) {
+        self.source.size()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// Completed passes through the animation
+    pub fn played(&self) -> u32 {
+        self.played
+    }
+    /// Advance playback by elapsed_ms - returns frame when display needs updating
+    pub fn tick(&mut self, elapsed_ms: u32) -> Result<Option<&Sprite>> {
+        if self.finished {
+            return Ok(None);
+        }
+        self.remaining_ms -= elapsed_ms as i64;
+        if self.started && self.remaining_ms > 0 {
+            return Ok(None);
+        }
+        let delay = match self.source.next_frame()? {
+            Some(delay) => delay,
+            None => {
+                // End of pass - check loop count
+                self.played += 1;
+                if self.source.loops().is_some_and(|n| self.played >= n) {
+                    self.finished = true;
+                    return Ok(None);
+                }
+                self.source.rewind()?;
+                match self.source.next_frame()? {
+                    Some(delay) => delay,
+                    None => {
+                        self.finished = true;
+                        return Ok(None);
+                    }
+                }
+            }
+        };
+        let delay = match delay {
+            0 => DEFAULT_DELAY_MS,
+            delay => delay,
+        } as i64;
+        // Don't try to catch up more than one frame
+        self.remaining_ms = (self.remaining_ms + delay).max(1);
+        self.started = true;
+        Ok(Some(self.source.screen()))
+    }
+}
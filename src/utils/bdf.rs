@@ -0,0 +1,219 @@
+// BDF bitmap font parser
+//
+// This module only depends on std/anyhow so that it can also be included
+// from build.rs (using #[path]) to convert fonts into const glyph tables
+use anyhow::{anyhow, bail, Result};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+// Glyph rows are limited to u32 (matches font::MAX_GLYPH_SIZE)
+const MAX_SIZE: i32 = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BdfGlyph {
+    pub c: char,
+    pub width: i32,     // Advance (DWIDTH)
+    pub rows: Vec<u32>, // One row per line of the font cell - LSB is leftmost
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BdfFont {
+    pub name: String,
+    pub height: i32,
+    pub baseline: i32,
+    pub glyphs: Vec<BdfGlyph>, // Sorted by char
+}
+
+// (char, advance, BBX, bitmap) as read from file
+type RawGlyph = (char, i32, (i32, i32, i32, i32), Vec<u32>);
+
+// Per-glyph state while parsing
+#[derive(Default)]
+struct GlyphState {
+    encoding: Option<u32>,
+    dwidth: Option<i32>,
+    bbx: Option<(i32, i32, i32, i32)>,
+    bitmap: Option<Vec<u32>>,
+}
+
+fn parse_ints<const N: usize>(args: &[&str], keyword: &str, line: usize) -> Result<[i32; N]> {
+    if args.len() < N {
+        bail!("BDF line {}: {} expects {} values", line, keyword, N);
+    }
+    let mut out = [0; N];
+    for (i, v) in args.iter().take(N).enumerate() {
+        out[i] = v
+            .parse()
+            .map_err(|_| anyhow!("BDF line {}: invalid {} value: {}", line, keyword, v))?;
+    }
+    Ok(out)
+}
+
+// Convert hex row (MSB is leftmost pixel) to LSB-first u32 starting at column x
+fn parse_row(hex: &str, width: i32, x: i32, line: usize) -> Result<u32> {
+    let mut row = 0_u32;
+    for (i, byte) in hex.as_bytes().chunks(2).enumerate() {
+        let byte = std::str::from_utf8(byte)
+            .ok()
+            .and_then(|b| u8::from_str_radix(b, 16).ok())
+            .ok_or_else(|| anyhow!("BDF line {}: invalid bitmap row: {}", line, hex))?;
+        for bit in 0..8 {
+            let px = i as i32 * 8 + bit;
+            let col = x.saturating_add(px);
+            if px < width && (0..MAX_SIZE).contains(&col) && byte & (0x80 >> bit) != 0 {
+                row |= 1 << col;
+            }
+        }
+    }
+    Ok(row)
+}
+
+impl BdfFont {
+    /// Parse BDF data keeping only glyphs in subset (empty subset keeps all glyphs)
+    pub fn parse(data: &[u8], subset: &[RangeInclusive<char>]) -> Result<Self> {
+        let text = std::str::from_utf8(data).map_err(|_| anyhow!("BDF data is not UTF8"))?;
+        let mut name = String::new();
+        let mut bbox: Option<[i32; 4]> = None;
+        let (mut ascent, mut descent): (Option<i32>, Option<i32>) = (None, None);
+        let mut raw: Vec<RawGlyph> = Vec::new();
+        let mut glyph: Option<GlyphState> = None;
+        let mut lines = text.lines().enumerate();
+
+        while let Some((n, line)) = lines.next() {
+            let n = n + 1;
+            let mut parts = line.split_whitespace();
+            let keyword = match parts.next() {
+                Some(k) => k,
+                None => continue,
+            };
+            let args: Vec<&str> = parts.collect();
+            match (keyword, glyph.as_mut()) {
+                ("FONT", None) => name = args.join(" "),
+                ("FONTBOUNDINGBOX", None) => bbox = Some(parse_ints::<4>(&args, keyword, n)?),
+                ("FONT_ASCENT", None) => ascent = Some(parse_ints::<1>(&args, keyword, n)?[0]),
+                ("FONT_DESCENT", None) => descent = Some(parse_ints::<1>(&args, keyword, n)?[0]),
+                ("STARTCHAR", None) => glyph = Some(GlyphState::default()),
+                ("STARTCHAR", Some(_)) => bail!("BDF line {}: STARTCHAR without ENDCHAR", n),
+                ("ENCODING", Some(g)) => {
+                    // Unencoded glyphs have ENCODING -1
+                    let [code] = parse_ints::<1>(&args, keyword, n)?;
+                    g.encoding = u32::try_from(code).ok();
+                }
+                ("DWIDTH", Some(g)) => g.dwidth = Some(parse_ints::<1>(&args, keyword, n)?[0]),
+                ("BBX", Some(g)) => {
+                    let [w, h, x, y] = parse_ints::<4>(&args, keyword, n)?;
+                    g.bbx = Some((w, h, x, y));
+                }
+                ("BITMAP", Some(g)) => {
+                    let (w, h, x, _) = g
+                        .bbx
+                        .ok_or_else(|| anyhow!("BDF line {}: BITMAP before BBX", n))?;
+                    let mut rows = Vec::with_capacity(h.clamp(0, MAX_SIZE) as usize);
+                    for _ in 0..h {
+                        let (n, row) = lines
+                            .next()
+                            .ok_or_else(|| anyhow!("BDF line {}: truncated BITMAP", n))?;
+                        rows.push(parse_row(row.trim(), w, x, n + 1)?);
+                    }
+                    g.bitmap = Some(rows);
+                }
+                ("ENDCHAR", Some(g)) => {
+                    let c = g.encoding.and_then(char::from_u32);
+                    let wanted = c
+                        .map(|c| subset.is_empty() || subset.iter().any(|r| r.contains(&c)))
+                        .unwrap_or(false);
+                    if let (Some(c), true) = (c, wanted) {
+                        let bbx = g
+                            .bbx
+                            .ok_or_else(|| anyhow!("BDF line {}: glyph without BBX", n))?;
+                        let width = g.dwidth.unwrap_or(bbx.0.saturating_add(bbx.2));
+                        raw.push((c, width, bbx, g.bitmap.take().unwrap_or_default()));
+                    }
+                    glyph = None;
+                }
+                ("ENDFONT", _) => break,
+                _ => {}
+            }
+        }
+
+        // Cell metrics (properties take precedence over bounding box) - values
+        // come from the file so use saturating arithmetic
+        let bbox = bbox.ok_or_else(|| anyhow!("BDF missing FONTBOUNDINGBOX"))?;
+        let ascent = ascent.unwrap_or(bbox[1].saturating_add(bbox[3]));
+        let descent = descent.unwrap_or(0_i32.saturating_sub(bbox[3]));
+        let height = ascent.saturating_add(descent);
+        if !(1..=MAX_SIZE).contains(&height) {
+            bail!(
+                "BDF font height {} not supported (max {})",
+                height,
+                MAX_SIZE
+            );
+        }
+        if !(0..=height).contains(&ascent) {
+            bail!("BDF font ascent {} outside font cell", ascent);
+        }
+
+        // Place glyph bitmaps in font cell relative to baseline
+        let mut glyphs: Vec<BdfGlyph> = raw
+            .into_iter()
+            .map(|(c, width, (_, h, _, y), bitmap)| {
+                let mut rows = vec![0_u32; height as usize];
+                let top = ascent.saturating_sub(y.saturating_add(h));
+                for (i, row) in bitmap.into_iter().enumerate() {
+                    let line = top.saturating_add(i as i32);
+                    if (0..height).contains(&line) {
+                        rows[line as usize] = row;
+                    }
+                }
+                BdfGlyph {
+                    c,
+                    width: width.clamp(0, MAX_SIZE),
+                    rows,
+                }
+            })
+            .collect();
+        glyphs.sort_by_key(|g| g.c);
+        glyphs.dedup_by_key(|g| g.c);
+
+        Ok(Self {
+            name,
+            height,
+            baseline: ascent,
+            glyphs,
+        })
+    }
+
+    pub fn get(&self, c: char) -> Option<&BdfGlyph> {
+        self.glyphs
+            .binary_search_by_key(&c, |g| g.c)
+            .ok()
+            .map(|i| &self.glyphs[i])
+    }
+
+    /// Generate Rust source for a const font::StaticFont
+    pub fn to_rust(&self, name: &str) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "// Generated from BDF font: {}", self.name);
+        let _ = writeln!(out, "#[rustfmt::skip]");
+        let _ = writeln!(out, "pub const {}: StaticFont = StaticFont {{", name);
+        let _ = writeln!(out, "    height: {},", self.height);
+        let _ = writeln!(out, "    baseline: {},", self.baseline);
+        let _ = writeln!(out, "    glyphs: &[");
+        for g in &self.glyphs {
+            let rows = g
+                .rows
+                .iter()
+                .map(|r| format!("{:#x}", r))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                out,
+                "        StaticGlyph {{ c: {:?}, width: {}, rows: &[{}] }},",
+                g.c, g.width, rows
+            );
+        }
+        let _ = writeln!(out, "    ],");
+        let _ = writeln!(out, "}};");
+        out
+    }
+}
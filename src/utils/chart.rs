@@ -0,0 +1,190 @@
+use anyhow::{anyhow, Result};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use crate::draw::{Canvas, Paint};
+use crate::effect::Palette;
+use crate::rgb::OFF;
+
+// Sample history with fixed capacity (oldest samples dropped)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Samples {
+    values: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl Samples {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+    pub fn push(&mut self, value: f32) {
+        if self.capacity == 0 || !value.is_finite() {
+            return;
+        }
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// Samples oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = f32> + ExactSizeIterator + '_ {
+        self.values.iter().copied()
+    }
+    pub fn latest(&self) -> Option<f32> {
+        self.values.back().copied()
+    }
+    pub fn min_max(&self) -> Option<(f32, f32)> {
+        self.iter().fold(None, |acc, v| match acc {
+            Some((lo, hi)) => Some((v.min(lo), v.max(hi))),
+            None => Some((v, v)),
+        })
+    }
+}
+
+impl Extend<f32> for Samples {
+    fn extend<T: IntoIterator<Item = f32>>(&mut self, iter: T) {
+        for v in iter {
+            self.push(v);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ChartKind {
+    #[default]
+    Bars, // Column per sample (newest on right)
+    Sparkline, // Line through samples
+    Meter,     // Horizontal level of latest sample with peak marker
+}
+
+impl FromStr for ChartKind {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bars" | "bar" => Ok(ChartKind::Bars),
+            "sparkline" | "line" => Ok(ChartKind::Sparkline),
+            "meter" | "vu" => Ok(ChartKind::Meter),
+            _ => Err(anyhow!("Unknown chart: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Range {
+    #[default]
+    Auto, // Scale to samples shown
+    Fixed(f32, f32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub range: Range,
+    pub palette: Palette, // Coloured by level (0.0 = bottom of range)
+}
+
+impl Chart {
+    pub fn new(kind: ChartKind) -> Self {
+        Self {
+            kind,
+            range: Range::Auto,
+            palette: Palette::Meter,
+        }
+    }
+    // Range for samples (never empty)
+    fn scale(&self, samples: &Samples) -> (f32, f32) {
+        let (lo, hi) = match self.range {
+            Range::Fixed(lo, hi) => (lo, hi),
+            Range::Auto => match self.kind {
+                // Level from zero so meter doesn't always look full
+                ChartKind::Meter => samples
+                    .min_max()
+                    .map_or((0.0, 1.0), |(lo, hi)| (lo.min(0.0), hi)),
+                _ => samples.min_max().unwrap_or((0.0, 1.0)),
+            },
+        };
+        if hi > lo {
+            (lo, hi)
+        } else {
+            (lo - 0.5, lo + 0.5)
+        }
+    }
+    /// Draw samples into region at (x, y) of size (w, h) - region is cleared
+    pub fn draw(
+        &self,
+        samples: &Samples,
+        canvas: &mut dyn Canvas,
+        (x, y): (i32, i32),
+        (w, h): (i32, i32),
+    ) {
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        canvas.fill_rect((x, y), (x + w, y + h), Paint::Solid(OFF));
+        let (lo, hi) = self.scale(samples);
+        let level = |v: f32| ((v - lo) / (hi - lo)).clamp(0.0, 1.0);
+        // Newest samples that fit (right aligned)
+        let shown = samples.len().min(w as usize);
+        let x0 = x + w - shown as i32;
+        let visible = samples.iter().skip(samples.len() - shown);
+        match self.kind {
+            ChartKind::Bars => {
+                for (i, v) in visible.enumerate() {
+                    // Top pixel dimmed by fractional part so small changes show
+                    // (every sample gets at least one pixel)
+                    let height = (level(v) * h as f32).max(1.0);
+                    for row in 0..height.ceil() as i32 {
+                        let rgb = self.palette.colour((row as f32 + 0.5) / h as f32);
+                        let coverage = (height - row as f32).min(1.0);
+                        canvas.set((x0 + i as i32, y + h - 1 - row), OFF.lerp(rgb, coverage));
+                    }
+                }
+            }
+            ChartKind::Sparkline => {
+                let point = |i: usize, v: f32| {
+                    let py = ((1.0 - level(v)) * (h - 1) as f32).round() as i32;
+                    (x0 + i as i32, y + py)
+                };
+                let mut previous = None;
+                for (i, v) in visible.enumerate() {
+                    let p = point(i, v);
+                    let paint = Paint::Solid(self.palette.colour(level(v)));
+                    canvas.line(previous.unwrap_or(p), p, paint);
+                    previous = Some(p);
+                }
+            }
+            ChartKind::Meter => {
+                let Some(latest) = samples.latest() else {
+                    return;
+                };
+                let width = (level(latest) * w as f32).round() as i32;
+                for col in 0..width {
+                    let rgb = self.palette.colour((col as f32 + 0.5) / w as f32);
+                    canvas.fill_rect((x + col, y), (x + col + 1, y + h), Paint::Solid(rgb));
+                }
+                // Peak hold over sample history
+                let peak = samples.iter().fold(latest, f32::max);
+                let col = ((level(peak) * w as f32).round() as i32 - 1).clamp(0, w - 1);
+                if col >= width {
+                    let rgb = self.palette.colour((col as f32 + 0.5) / w as f32);
+                    canvas.fill_rect((x + col, y), (x + col + 1, y + h), Paint::Solid(rgb));
+                }
+            }
+        }
+    }
+}
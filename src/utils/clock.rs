@@ -0,0 +1,220 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::draw::Canvas;
+use crate::font::{Font, Glyph, FONT_3X5, FONT_TALL_DIGITS};
+use crate::rgb::Rgb;
+
+// System time before this (2024-01-01) is assumed not to be synchronised
+// (ESP32 starts at the epoch until SNTP completes)
+const SYNC_THRESHOLD_S: i64 = 1_704_067_200;
+
+/// System time as Unix time ms (None until clock is synchronised)
+pub fn unix_ms() -> Option<i64> {
+    let ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    (ms / 1000 >= SYNC_THRESHOLD_S).then_some(ms)
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i32,
+    pub month: u32,   // 1..=12
+    pub day: u32,     // 1..=31
+    pub weekday: u32, // 0 = Sunday
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub millis: u32,
+}
+
+impl DateTime {
+    /// Civil date/time from milliseconds since the Unix epoch
+    pub fn from_unix_ms(ms: i64) -> Self {
+        let (days, ms_of_day) = (ms.div_euclid(86_400_000), ms.rem_euclid(86_400_000));
+        // Days to civil date (proleptic Gregorian calendar)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+        Self {
+            year,
+            month,
+            day,
+            weekday: (days + 4).rem_euclid(7) as u32, // 1970-01-01 was a Thursday
+            hour: (ms_of_day / 3_600_000) as u32,
+            minute: (ms_of_day / 60_000 % 60) as u32,
+            second: (ms_of_day / 1000 % 60) as u32,
+            millis: (ms_of_day % 1000) as u32,
+        }
+    }
+    /// Local time from system clock (None until clock is synchronised)
+    pub fn now(utc_offset_min: i32) -> Option<Self> {
+        unix_ms().map(|ms| Self::from_unix_ms(ms + utc_offset_min as i64 * 60_000))
+    }
+    /// Format using strftime-like specifiers
+    ///
+    ///   %H %I %M %S %d %m %y %Y %e %p %a %b %%
+    ///
+    /// A '-' flag (eg. %-I) drops the leading zero
+    pub fn format(&self, fmt: &str) -> String {
+        let mut out = String::new();
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let (pad, spec) = match chars.next() {
+                Some('-') => (false, chars.next()),
+                spec => (true, spec),
+            };
+            let number = |n: u32| {
+                if pad {
+                    format!("{n:02}")
+                } else {
+                    n.to_string()
+                }
+            };
+            match spec {
+                Some('H') => out.push_str(&number(self.hour)),
+                Some('I') => out.push_str(&number((self.hour + 11) % 12 + 1)),
+                Some('M') => out.push_str(&number(self.minute)),
+                Some('S') => out.push_str(&number(self.second)),
+                Some('d') => out.push_str(&number(self.day)),
+                Some('e') => out.push_str(&self.day.to_string()),
+                Some('m') => out.push_str(&number(self.month)),
+                Some('y') => out.push_str(&number(self.year.rem_euclid(100) as u32)),
+                Some('Y') => out.push_str(&self.year.to_string()),
+                Some('p') => out.push_str(if self.hour < 12 { "AM" } else { "PM" }),
+                Some('a') => out.push_str(WEEKDAYS[self.weekday as usize % 7]),
+                Some('b') => out.push_str(MONTHS[(self.month as usize + 11) % 12]),
+                Some('%') => out.push('%'),
+                Some(c) => {
+                    out.push('%');
+                    out.push(c);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockOptions {
+    pub format: String, // Custom format (empty to build from options below)
+    pub hour12: bool,
+    pub blink: bool, // Flash separators once a second
+    pub seconds: bool,
+    pub date: bool,          // Show date for 3s every 15s
+    pub utc_offset_min: i32, // Local time offset
+}
+
+impl Default for ClockOptions {
+    fn default() -> Self {
+        Self {
+            format: String::new(),
+            hour12: false,
+            blink: true,
+            seconds: false,
+            date: false,
+            utc_offset_min: 0,
+        }
+    }
+}
+
+impl ClockOptions {
+    pub fn time_format(&self) -> String {
+        if !self.format.is_empty() {
+            return self.format.clone();
+        }
+        let hours = if self.hour12 { "%-I" } else { "%H" };
+        let seconds = if self.seconds { ":%S" } else { "" };
+        format!("{hours}:%M{seconds}")
+    }
+    /// Text to display and whether separators are lit
+    pub fn text(&self, now: Option<&DateTime>) -> (String, bool) {
+        match now {
+            Some(now) if self.date && now.second % 15 >= 12 => (now.format("%d.%m"), true),
+            Some(now) => (
+                now.format(&self.time_format()),
+                !self.blink || now.millis < 500,
+            ),
+            None => ("--:--".to_string(), true),
+        }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == ':'
+}
+
+// Tall digits fill an 8 pixel display - fall back to 3x5 for anything else
+fn clock_font(text: &str) -> &'static dyn Font {
+    if text.chars().all(|c| FONT_TALL_DIGITS.glyph(c).is_some()) {
+        &FONT_TALL_DIGITS
+    } else {
+        &FONT_3X5
+    }
+}
+
+/// Draw clock text centred on canvas and return its width
+///
+/// Glyphs are trimmed to their inked columns with a 1 pixel gap. If the text
+/// is too wide the gaps next to separators are dropped (so HH:MM in tall
+/// digits fits two panels). Hidden separators are skipped rather than
+/// removed so the digits don't move.
+pub fn draw_clock(canvas: &mut dyn Canvas, text: &str, rgb: Rgb, separators: bool) -> i32 {
+    let (w, h) = canvas.size();
+    let font = clock_font(text);
+    // (char, glyph, first inked column, width)
+    let glyphs: Vec<(char, Option<Glyph>, i32, i32)> = text
+        .chars()
+        .map(|c| {
+            let glyph = font.glyph(c);
+            let (first, width) = glyph.as_ref().and_then(|g| g.columns()).unwrap_or((0, 2));
+            (c, glyph, first, width)
+        })
+        .collect();
+    let mut gaps = vec![1; glyphs.len().saturating_sub(1)];
+    let width = |gaps: &[i32]| glyphs.iter().map(|g| g.3).sum::<i32>() + gaps.iter().sum::<i32>();
+    // Gaps after separators then before
+    let tight = (0..gaps.len())
+        .filter(|&i| is_separator(glyphs[i].0))
+        .chain((0..gaps.len()).filter(|&i| is_separator(glyphs[i + 1].0)))
+        .collect::<Vec<_>>();
+    for i in tight {
+        if width(&gaps) <= w {
+            break;
+        }
+        gaps[i] = 0;
+    }
+    let total = width(&gaps);
+    let mut x = ((w - total) / 2).max(0);
+    let y = ((h - font.height()) / 2).max(0);
+    for (i, (c, glyph, first, width)) in glyphs.iter().enumerate() {
+        if let Some(glyph) = glyph.as_ref().filter(|_| separators || !is_separator(*c)) {
+            for gy in 0..glyph.height {
+                for gx in *first..*first + *width {
+                    if glyph.pixel(gx, gy) {
+                        canvas.set((x + gx - first, y + gy), rgb);
+                    }
+                }
+            }
+        }
+        x += width + gaps.get(i).unwrap_or(&0);
+    }
+    total
+}
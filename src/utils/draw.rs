@@ -0,0 +1,636 @@
+use crate::matrix::{self, Matrix};
+use crate::matrix_1d::{Matrix1D, Panel, HEIGHT, WIDTH};
+use crate::rgb::{BlendMode, Rgb, OFF};
+
+// Colour source for drawing primitives
+//
+// Palette colours are sampled along the primitive - start to end for lines
+// and top to bottom for shapes
+#[derive(Clone, Copy, Debug)]
+pub enum Paint<'a> {
+    Solid(Rgb),
+    Palette(&'a [Rgb]),
+    Blend(Rgb, BlendMode),
+}
+
+impl Paint<'_> {
+    /// Colour for pixel at position t (0.0..=1.0) drawn over dst
+    pub fn colour(&self, dst: Rgb, t: f32) -> Rgb {
+        match self {
+            Paint::Solid(rgb) => *rgb,
+            Paint::Palette(palette) => match palette.len() {
+                0 => dst,
+                n => palette[((t.clamp(0.0, 1.0) * (n - 1) as f32).round() as usize).min(n - 1)],
+            },
+            Paint::Blend(rgb, mode) => dst.blend(*rgb, *mode),
+        }
+    }
+}
+
+impl From<Rgb> for Paint<'_> {
+    fn from(rgb: Rgb) -> Self {
+        Paint::Solid(rgb)
+    }
+}
+
+// Geometric transform applied to a region (see Canvas::geometry)
+//
+// Transpose and quarter turns swap width and height - on a non-square
+// region the result is centred and clipped to the region (so rotating the
+// whole 16x8 display shows the middle 8 rows of the rotated image)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Geometry {
+    FlipH,
+    FlipV,
+    Transpose,
+    Rotate90, // Clockwise
+    Rotate180,
+    Rotate270,
+    Shift(i32, i32), // Vacated pixels are cleared
+    Roll(i32, i32),  // Pixels wrap around region
+    Scale(f32, f32), // Nearest-neighbour from top-left of region
+}
+
+// Apply geometry to w x h buffer - returns new buffer and size
+fn apply_geometry(buf: &[Rgb], (w, h): (i32, i32), g: Geometry) -> (Vec<Rgb>, (i32, i32)) {
+    let get = |x: i32, y: i32| {
+        if (0..w).contains(&x) && (0..h).contains(&y) {
+            buf[(x + y * w) as usize]
+        } else {
+            OFF
+        }
+    };
+    let size = match g {
+        Geometry::Transpose | Geometry::Rotate90 | Geometry::Rotate270 => (h, w),
+        _ => (w, h),
+    };
+    // Source pixel for output pixel (x, y)
+    let source = |x: i32, y: i32| match g {
+        Geometry::FlipH => get(w - 1 - x, y),
+        Geometry::FlipV => get(x, h - 1 - y),
+        Geometry::Transpose => get(y, x),
+        Geometry::Rotate90 => get(y, h - 1 - x),
+        Geometry::Rotate180 => get(w - 1 - x, h - 1 - y),
+        Geometry::Rotate270 => get(w - 1 - y, x),
+        Geometry::Shift(dx, dy) => get(x.saturating_sub(dx), y.saturating_sub(dy)),
+        Geometry::Roll(dx, dy) => get(
+            (x as i64 - dx as i64).rem_euclid(w as i64) as i32,
+            (y as i64 - dy as i64).rem_euclid(h as i64) as i32,
+        ),
+        Geometry::Scale(sx, sy) if sx > 0.0 && sy > 0.0 => get(
+            (x as f32 / sx).floor() as i32,
+            (y as f32 / sy).floor() as i32,
+        ),
+        Geometry::Scale(_, _) => OFF,
+    };
+    let out = (0..size.1)
+        .flat_map(|y| (0..size.0).map(move |x| (x, y)))
+        .map(|(x, y)| source(x, y))
+        .collect();
+    (out, size)
+}
+
+// Set of pixels to paint - used to clip shapes to the canvas and make
+// sure that each pixel is only painted once (matters for blend modes)
+//
+// Only covers the shape's bounding box (clipped to the canvas) so small
+// shapes don't allocate or scan the whole canvas
+struct Mask {
+    x0: i32,
+    y0: i32,
+    w: i32,
+    h: i32,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    /// Mask for (x1,y1)-(x2,y2) (end exclusive) clipped to canvas size
+    fn new((w, h): (i32, i32), (x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> Self {
+        let (x0, y0) = (x1.max(0), y1.max(0));
+        let (w, h) = (
+            x2.min(w).saturating_sub(x0).max(0),
+            y2.min(h).saturating_sub(y0).max(0),
+        );
+        Self {
+            x0,
+            y0,
+            w,
+            h,
+            bits: vec![false; (w * h) as usize],
+        }
+    }
+    fn xs(&self) -> std::ops::Range<i32> {
+        self.x0..self.x0 + self.w
+    }
+    fn ys(&self) -> std::ops::Range<i32> {
+        self.y0..self.y0 + self.h
+    }
+    fn index(&self, (x, y): (i32, i32)) -> Option<usize> {
+        (self.xs().contains(&x) && self.ys().contains(&y))
+            .then(|| ((x - self.x0) + (y - self.y0) * self.w) as usize)
+    }
+    fn set(&mut self, p: (i32, i32)) {
+        if let Some(i) = self.index(p) {
+            self.bits[i] = true;
+        }
+    }
+    fn get(&self, p: (i32, i32)) -> bool {
+        self.index(p).is_some_and(|i| self.bits[i])
+    }
+    fn span(&mut self, y: i32, x1: i32, x2: i32) {
+        if self.ys().contains(&y) {
+            for x in x1.max(self.x0)..=x2.min(self.x0 + self.w - 1) {
+                self.set((x, y));
+            }
+        }
+    }
+}
+
+// Palette position for row y in shape spanning rows top..=bottom
+fn row_t(y: i32, top: i32, bottom: i32) -> f32 {
+    if bottom > top {
+        (y as i64 - top as i64) as f32 / (bottom as i64 - top as i64) as f32
+    } else {
+        0.0
+    }
+}
+
+// Clip line to rectangle (x1,y1)-(x2,y2) (Liang-Barsky)
+fn clip_line(
+    (x0, y0): (f64, f64),
+    (x1, y1): (f64, f64),
+    (xmin, ymin): (f64, f64),
+    (xmax, ymax): (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-dx, x0 - xmin),
+        (dx, xmax - x0),
+        (-dy, y0 - ymin),
+        (dy, ymax - y0),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < 0.0 {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+        }
+    }
+    if t0 > t1 {
+        None
+    } else {
+        Some(((x0 + t0 * dx, y0 + t0 * dy), (x0 + t1 * dx, y0 + t1 * dy)))
+    }
+}
+
+// Bresenham line from p0 to p1 (inclusive) - calls f for every visible point
+fn bresenham((w, h): (i32, i32), p0: (i32, i32), p1: (i32, i32), mut f: impl FnMut((i32, i32))) {
+    let visible = |(x, y): (i32, i32)| (0..w).contains(&x) && (0..h).contains(&y);
+    // Only clip if we need to - clipping can shift the pixel pattern slightly
+    let ((mut x0, mut y0), (x1, y1)) = if visible(p0) && visible(p1) {
+        (p0, p1)
+    } else {
+        match clip_line(
+            (p0.0 as f64, p0.1 as f64),
+            (p1.0 as f64, p1.1 as f64),
+            (-1.0, -1.0),
+            (w as f64, h as f64),
+        ) {
+            Some(((x0, y0), (x1, y1))) => (
+                (x0.round() as i32, y0.round() as i32),
+                (x1.round() as i32, y1.round() as i32),
+            ),
+            None => return,
+        }
+    };
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+    let mut err = dx + dy;
+    loop {
+        if visible((x0, y0)) {
+            f((x0, y0));
+        }
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+}
+
+// Midpoint ellipse - calls f for each point in the outline (may repeat points)
+fn ellipse_points((cx, cy): (i32, i32), (rx, ry): (i32, i32), mut f: impl FnMut((i32, i32))) {
+    if rx < 0 || ry < 0 {
+        return;
+    }
+    if ry == 0 {
+        (cx.saturating_sub(rx)..=cx.saturating_add(rx)).for_each(|x| f((x, cy)));
+        return;
+    }
+    let (rx2, ry2) = ((rx as f64).powi(2), (ry as f64).powi(2));
+    let mut plot4 = |x: i32, y: i32| {
+        let (x1, x2) = (cx.saturating_sub(x), cx.saturating_add(x));
+        let (y1, y2) = (cy.saturating_sub(y), cy.saturating_add(y));
+        f((x2, y2));
+        f((x1, y2));
+        f((x2, y1));
+        f((x1, y1));
+    };
+    let (mut x, mut y) = (0_i32, ry);
+    let (mut px, mut py) = (0.0, 2.0 * rx2 * y as f64);
+    // Region 1 (slope > -1)
+    let mut p = ry2 - rx2 * ry as f64 + 0.25 * rx2;
+    while px < py {
+        plot4(x, y);
+        x += 1;
+        px += 2.0 * ry2;
+        if p < 0.0 {
+            p += ry2 + px;
+        } else {
+            y -= 1;
+            py -= 2.0 * rx2;
+            p += ry2 + px - py;
+        }
+    }
+    // Region 2 (slope < -1)
+    p = ry2 * (x as f64 + 0.5).powi(2) + rx2 * (y as f64 - 1.0).powi(2) - rx2 * ry2;
+    while y >= 0 {
+        plot4(x, y);
+        y -= 1;
+        py -= 2.0 * rx2;
+        if p > 0.0 {
+            p += rx2 - py;
+        } else {
+            x += 1;
+            px += 2.0 * ry2;
+            p += rx2 - py + px;
+        }
+    }
+}
+
+// Drawing primitives for any framebuffer - all co-ordinates are signed and
+// clipped to the canvas
+pub trait Canvas {
+    fn size(&self) -> (i32, i32);
+    fn get(&self, p: (i32, i32)) -> Option<Rgb>;
+    fn set(&mut self, p: (i32, i32), rgb: Rgb);
+
+    /// Paint single pixel (t is palette position)
+    fn plot(&mut self, p: (i32, i32), paint: Paint, t: f32) {
+        if let Some(dst) = self.get(p) {
+            self.set(p, paint.colour(dst, t));
+        }
+    }
+    /// Paint single pixel with partial coverage (0.0..=1.0)
+    fn plot_aa(&mut self, p: (i32, i32), paint: Paint, t: f32, coverage: f32) {
+        if let Some(dst) = self.get(p) {
+            self.set(p, dst.lerp(paint.colour(dst, t), coverage));
+        }
+    }
+    fn line(&mut self, p0: (i32, i32), p1: (i32, i32), paint: Paint) {
+        // Deltas in i64 so lines between extreme co-ordinates can't overflow
+        let delta = |a: i32, b: i32| (b as i64 - a as i64) as f32;
+        let (dx, dy) = (delta(p0.0, p1.0), delta(p0.1, p1.1));
+        let len2 = dx * dx + dy * dy;
+        bresenham(self.size(), p0, p1, |(x, y)| {
+            // Project onto line to get palette position (handles clipping)
+            let t = if len2 > 0.0 {
+                (delta(p0.0, x) * dx + delta(p0.1, y) * dy) / len2
+            } else {
+                0.0
+            };
+            self.plot((x, y), paint, t);
+        });
+    }
+    /// Anti-aliased line (Xiaolin Wu) - end points can be fractional
+    fn line_aa(&mut self, p0: (f32, f32), p1: (f32, f32), paint: Paint) {
+        let (w, h) = self.size();
+        let steep = (p1.1 - p0.1).abs() > (p1.0 - p0.0).abs();
+        // Work in (major, minor) axis space
+        let ((mut a0, mut b0), (mut a1, mut b1)) = if steep {
+            ((p0.1, p0.0), (p1.1, p1.0))
+        } else {
+            (p0, p1)
+        };
+        let reversed = a0 > a1;
+        if reversed {
+            (a0, a1) = (a1, a0);
+            (b0, b1) = (b1, b0);
+        }
+        let gradient = if a1 - a0 == 0.0 {
+            1.0
+        } else {
+            (b1 - b0) / (a1 - a0)
+        };
+        let span = a1 - a0;
+        let mut plot = |a: i32, b: i32, c: f32| {
+            let t = if span > 0.0 {
+                (a as f32 - a0) / span
+            } else {
+                0.0
+            };
+            let t = if reversed { 1.0 - t } else { t };
+            let p = if steep { (b, a) } else { (a, b) };
+            self.plot_aa(p, paint, t.clamp(0.0, 1.0), c);
+        };
+        let fpart = |v: f32| v - v.floor();
+        // First end point
+        let aend = a0.round();
+        let bend = b0 + gradient * (aend - a0);
+        let gap = 1.0 - fpart(a0 + 0.5);
+        let a_start = aend as i32;
+        plot(a_start, bend.floor() as i32, (1.0 - fpart(bend)) * gap);
+        plot(a_start, bend.floor() as i32 + 1, fpart(bend) * gap);
+        let mut inter = bend + gradient;
+        // Second end point
+        let aend = a1.round();
+        let bend = b1 + gradient * (aend - a1);
+        let gap = fpart(a1 + 0.5);
+        let a_end = aend as i32;
+        plot(a_end, bend.floor() as i32, (1.0 - fpart(bend)) * gap);
+        plot(a_end, bend.floor() as i32 + 1, fpart(bend) * gap);
+        // Main loop - clipped to visible range on major axis
+        let limit = if steep { h } else { w };
+        let first = (a_start + 1).max(-1);
+        let last = (a_end - 1).min(limit);
+        if first > a_start + 1 {
+            inter += gradient * (first - a_start - 1) as f32;
+        }
+        for a in first..=last {
+            plot(a, inter.floor() as i32, 1.0 - fpart(inter));
+            plot(a, inter.floor() as i32 + 1, fpart(inter));
+            inter += gradient;
+        }
+    }
+    /// Rectangle outline - (x1,y1) inclusive, (x2,y2) exclusive
+    fn rect(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32), paint: Paint) {
+        let mut mask = Mask::new(self.size(), (x1, y1), (x2, y2));
+        if x2 > x1 && y2 > y1 {
+            mask.span(y1, x1, x2 - 1);
+            mask.span(y2 - 1, x1, x2 - 1);
+            for y in mask.ys() {
+                mask.set((x1, y));
+                mask.set((x2 - 1, y));
+            }
+        }
+        self.paint_mask(&mask, paint, (y1, y2.saturating_sub(1)));
+    }
+    /// Filled rectangle - (x1,y1) inclusive, (x2,y2) exclusive
+    fn fill_rect(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32), paint: Paint) {
+        let mut mask = Mask::new(self.size(), (x1, y1), (x2, y2));
+        for y in mask.ys() {
+            mask.span(y, x1, x2.saturating_sub(1));
+        }
+        self.paint_mask(&mask, paint, (y1, y2.saturating_sub(1)));
+    }
+    fn circle(&mut self, c: (i32, i32), r: i32, paint: Paint) {
+        self.ellipse(c, (r, r), paint);
+    }
+    fn fill_circle(&mut self, c: (i32, i32), r: i32, paint: Paint) {
+        self.fill_ellipse(c, (r, r), paint);
+    }
+    fn ellipse(&mut self, c: (i32, i32), (rx, ry): (i32, i32), paint: Paint) {
+        let rows = (c.1.saturating_sub(ry), c.1.saturating_add(ry));
+        let mut mask = ellipse_mask(self.size(), c, (rx, ry));
+        ellipse_points(c, (rx, ry), |p| mask.set(p));
+        self.paint_mask(&mask, paint, rows);
+    }
+    fn fill_ellipse(&mut self, c: (i32, i32), (rx, ry): (i32, i32), paint: Paint) {
+        let rows = (c.1.saturating_sub(ry), c.1.saturating_add(ry));
+        let mut mask = ellipse_mask(self.size(), c, (rx, ry));
+        // Fill between outline points on each (visible) row so fill
+        // matches outline
+        let mut spans: Vec<Option<(i32, i32)>> = vec![None; mask.h as usize];
+        ellipse_points(c, (rx, ry), |(x, y)| {
+            let row = usize::try_from(y as i64 - mask.y0 as i64).ok();
+            if let Some(span) = row.and_then(|row| spans.get_mut(row)) {
+                *span = Some(span.map_or((x, x), |(x1, x2)| (x1.min(x), x2.max(x))));
+            }
+        });
+        for (y, span) in mask.ys().zip(spans) {
+            if let Some((x1, x2)) = span {
+                mask.span(y, x1, x2);
+            }
+        }
+        self.paint_mask(&mask, paint, rows);
+    }
+    fn triangle(&mut self, a: (i32, i32), b: (i32, i32), c: (i32, i32), paint: Paint) {
+        self.polygon(&[a, b, c], paint);
+    }
+    fn fill_triangle(&mut self, a: (i32, i32), b: (i32, i32), c: (i32, i32), paint: Paint) {
+        self.fill_polygon(&[a, b, c], paint);
+    }
+    /// Closed polygon outline
+    fn polygon(&mut self, points: &[(i32, i32)], paint: Paint) {
+        let mut mask = polygon_mask(self.size(), points);
+        for (i, &p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            bresenham(self.size(), p0, p1, |p| mask.set(p));
+        }
+        let (top, bottom) = y_range(points);
+        self.paint_mask(&mask, paint, (top, bottom));
+    }
+    /// Filled polygon (even-odd rule) - includes outline pixels
+    fn fill_polygon(&mut self, points: &[(i32, i32)], paint: Paint) {
+        let mut mask = polygon_mask(self.size(), points);
+        let (top, bottom) = y_range(points);
+        let mut nodes: Vec<f32> = Vec::new();
+        for y in mask.ys() {
+            // Sample at pixel centre
+            let yc = y as f32 + 0.5;
+            nodes.clear();
+            for (i, &(xi, yi)) in points.iter().enumerate() {
+                let (xj, yj) = points[(i + 1) % points.len()];
+                let (fyi, fyj) = (yi as f32 + 0.5, yj as f32 + 0.5);
+                if (fyi <= yc && yc < fyj) || (fyj <= yc && yc < fyi) {
+                    let (fxi, fxj) = (xi as f32 + 0.5, xj as f32 + 0.5);
+                    nodes.push(fxi + (yc - fyi) / (fyj - fyi) * (fxj - fxi));
+                }
+            }
+            nodes.sort_by(|a, b| a.total_cmp(b));
+            for pair in nodes.chunks_exact(2) {
+                let (x1, x2) = (
+                    (pair[0] - 0.5).ceil() as i32,
+                    (pair[1] - 0.5).floor() as i32,
+                );
+                mask.span(y, x1, x2);
+            }
+        }
+        for (i, &p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            bresenham(self.size(), p0, p1, |p| mask.set(p));
+        }
+        self.paint_mask(&mask, paint, (top, bottom));
+    }
+    /// Fill 4-connected region with the same colour as the seed pixel
+    fn flood_fill(&mut self, seed: (i32, i32), paint: Paint) {
+        let target = match self.get(seed) {
+            Some(rgb) => rgb,
+            None => return,
+        };
+        // Extent isn't known up front so mask covers the whole canvas
+        let mut mask = Mask::new(self.size(), (0, 0), self.size());
+        let (mut top, mut bottom) = (seed.1, seed.1);
+        let mut stack = vec![seed];
+        while let Some((x, y)) = stack.pop() {
+            if mask.get((x, y)) || self.get((x, y)) != Some(target) {
+                continue;
+            }
+            // Scan left and right from point
+            let mut x1 = x;
+            while x1 > 0 && self.get((x1 - 1, y)) == Some(target) && !mask.get((x1 - 1, y)) {
+                x1 -= 1;
+            }
+            let mut x2 = x;
+            while self.get((x2 + 1, y)) == Some(target) && !mask.get((x2 + 1, y)) {
+                x2 += 1;
+            }
+            mask.span(y, x1, x2);
+            (top, bottom) = (top.min(y), bottom.max(y));
+            // Queue rows above and below
+            for ny in [y - 1, y + 1] {
+                for nx in x1..=x2 {
+                    if self.get((nx, ny)) == Some(target) && !mask.get((nx, ny)) {
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+        }
+        self.paint_mask(&mask, paint, (top, bottom));
+    }
+    /// Apply geometric transforms to region (x1,y1)-(x2,y2) (end exclusive)
+    ///
+    /// Transforms that change the region shape (rotate/transpose of non
+    /// square regions) are centred in the region, clipped and uncovered
+    /// pixels cleared
+    fn geometry(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32), t: &[Geometry]) {
+        let (w, h) = self.size();
+        let (x1, y1, x2, y2) = (x1.max(0), y1.max(0), x2.min(w), y2.min(h));
+        if x1 >= x2 || y1 >= y2 {
+            return;
+        }
+        let mut size = (x2 - x1, y2 - y1);
+        let mut buf: Vec<Rgb> = (y1..y2)
+            .flat_map(|y| (x1..x2).map(move |x| (x, y)))
+            .map(|p| self.get(p).unwrap_or(OFF))
+            .collect();
+        for g in t {
+            (buf, size) = apply_geometry(&buf, size, *g);
+        }
+        let (ox, oy) = ((x2 - x1 - size.0) / 2, (y2 - y1 - size.1) / 2);
+        for y in 0..y2 - y1 {
+            for x in 0..x2 - x1 {
+                let (bx, by) = (x - ox, y - oy);
+                let rgb = if (0..size.0).contains(&bx) && (0..size.1).contains(&by) {
+                    buf[(bx + by * size.0) as usize]
+                } else {
+                    OFF
+                };
+                self.set((x1 + x, y1 + y), rgb);
+            }
+        }
+    }
+}
+
+// Helper for shapes using Mask
+trait PaintMask {
+    fn paint_mask(&mut self, mask: &Mask, paint: Paint, rows: (i32, i32));
+}
+
+impl<T: Canvas + ?Sized> PaintMask for T {
+    fn paint_mask(&mut self, mask: &Mask, paint: Paint, (top, bottom): (i32, i32)) {
+        for y in mask.ys() {
+            let t = row_t(y, top, bottom);
+            for x in mask.xs() {
+                if mask.get((x, y)) {
+                    self.plot((x, y), paint, t);
+                }
+            }
+        }
+    }
+}
+
+fn y_range(points: &[(i32, i32)]) -> (i32, i32) {
+    points
+        .iter()
+        .fold((i32::MAX, i32::MIN), |(t, b), &(_, y)| (t.min(y), b.max(y)))
+}
+
+fn ellipse_mask(size: (i32, i32), (cx, cy): (i32, i32), (rx, ry): (i32, i32)) -> Mask {
+    Mask::new(
+        size,
+        (cx.saturating_sub(rx), cy.saturating_sub(ry)),
+        (
+            cx.saturating_add(rx).saturating_add(1),
+            cy.saturating_add(ry).saturating_add(1),
+        ),
+    )
+}
+
+fn polygon_mask(size: (i32, i32), points: &[(i32, i32)]) -> Mask {
+    let (left, right) = points
+        .iter()
+        .fold((i32::MAX, i32::MIN), |(l, r), &(x, _)| (l.min(x), r.max(x)));
+    let (top, bottom) = y_range(points);
+    Mask::new(
+        size,
+        (left, top),
+        (right.saturating_add(1), bottom.saturating_add(1)),
+    )
+}
+
+impl<const N: usize> Canvas for Matrix1D<N> {
+    fn size(&self) -> (i32, i32) {
+        ((N * WIDTH) as i32, HEIGHT as i32)
+    }
+    fn get(&self, p: (i32, i32)) -> Option<Rgb> {
+        Matrix1D::get(self, p)
+    }
+    fn set(&mut self, p: (i32, i32), rgb: Rgb) {
+        Matrix1D::set(self, p, rgb)
+    }
+}
+
+impl Canvas for Panel {
+    fn size(&self) -> (i32, i32) {
+        (WIDTH as i32, HEIGHT as i32)
+    }
+    fn get(&self, p: (i32, i32)) -> Option<Rgb> {
+        Panel::get(self, p)
+    }
+    fn set(&mut self, p: (i32, i32), rgb: Rgb) {
+        Panel::set(self, p, rgb)
+    }
+}
+
+impl Canvas for Matrix {
+    fn size(&self) -> (i32, i32) {
+        (matrix::WIDTH as i32, matrix::HEIGHT as i32)
+    }
+    fn get(&self, (x, y): (i32, i32)) -> Option<Rgb> {
+        if (0..matrix::WIDTH as i32).contains(&x) && (0..matrix::HEIGHT as i32).contains(&y) {
+            Some(Matrix::get(self, (x as usize, y as usize)))
+        } else {
+            None
+        }
+    }
+    fn set(&mut self, (x, y): (i32, i32), rgb: Rgb) {
+        if (0..matrix::WIDTH as i32).contains(&x) && (0..matrix::HEIGHT as i32).contains(&y) {
+            Matrix::set(self, (x as usize, y as usize), rgb)
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Result};
+use std::f32::consts::PI;
+use std::str::FromStr;
+
+// Easing curves mapping progress t (0.0..=1.0) to eased progress
+//
+// Back and Elastic overshoot so can return values outside 0.0..=1.0
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    EaseIn,    // Cubic - starts slowly
+    EaseOut,   // Cubic - ends slowly
+    EaseInOut, // Cubic - starts and ends slowly
+    SineIn,
+    SineOut,
+    SineInOut,
+    Back,    // Overshoots then settles
+    Elastic, // Springs past target
+    Bounce,  // Bounces at end
+    Step,    // Jumps at end
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((t * PI).cos() - 1.0) / 2.0,
+            Easing::Back => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+            Easing::Bounce => {
+                const N1: f32 = 7.5625;
+                const D1: f32 = 2.75;
+                if t < 1.0 / D1 {
+                    N1 * t * t
+                } else if t < 2.0 / D1 {
+                    let t = t - 1.5 / D1;
+                    N1 * t * t + 0.75
+                } else if t < 2.5 / D1 {
+                    let t = t - 2.25 / D1;
+                    N1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D1;
+                    N1 * t * t + 0.984375
+                }
+            }
+            Easing::Step => {
+                if t < 1.0 {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Easing {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "linear" => Ok(Easing::Linear),
+            "quadin" => Ok(Easing::QuadIn),
+            "quadout" => Ok(Easing::QuadOut),
+            "quadinout" => Ok(Easing::QuadInOut),
+            "easein" | "in" | "cubicin" => Ok(Easing::EaseIn),
+            "easeout" | "out" | "cubicout" => Ok(Easing::EaseOut),
+            "easeinout" | "inout" | "cubicinout" => Ok(Easing::EaseInOut),
+            "sinein" => Ok(Easing::SineIn),
+            "sineout" => Ok(Easing::SineOut),
+            "sineinout" => Ok(Easing::SineInOut),
+            "back" => Ok(Easing::Back),
+            "elastic" => Ok(Easing::Elastic),
+            "bounce" => Ok(Easing::Bounce),
+            "step" => Ok(Easing::Step),
+            _ => Err(anyhow!("Unknown easing: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,658 @@
+use anyhow::{anyhow, bail, Result};
+use std::hash::Hasher;
+use std::str::FromStr;
+
+use crate::draw::Canvas;
+use crate::hash::Fnv1a;
+use crate::rgb::{Rgb, OFF};
+
+// Gradient stops for palettes
+const FIRE: &[(u8, u8, u8)] = &[
+    (0, 0, 0),
+    (96, 0, 0),
+    (255, 32, 0),
+    (255, 128, 0),
+    (255, 224, 64),
+    (255, 255, 192),
+];
+const OCEAN: &[(u8, u8, u8)] = &[
+    (0, 0, 16),
+    (0, 16, 96),
+    (0, 64, 192),
+    (0, 160, 224),
+    (128, 255, 255),
+];
+const FOREST: &[(u8, u8, u8)] = &[
+    (0, 16, 0),
+    (0, 80, 16),
+    (32, 160, 32),
+    (128, 192, 0),
+    (224, 255, 96),
+];
+const METER: &[(u8, u8, u8)] = &[(0, 192, 0), (0, 255, 0), (255, 255, 0), (255, 0, 0)];
+
+// Colour gradient used by effects and charts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Rainbow,
+    Fire,
+    Ocean,
+    Forest,
+    Meter,     // Green to red (levels)
+    Mono(Rgb), // Black to colour
+}
+
+impl Palette {
+    /// Colour at position t (0.0..=1.0)
+    pub fn colour(&self, t: f32) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let gradient = |stops: &[(u8, u8, u8)]| {
+            let p = t * (stops.len() - 1) as f32;
+            let i = (p as usize).min(stops.len() - 2);
+            let (a, b) = (stops[i], stops[i + 1]);
+            Rgb::new(a.0, a.1, a.2).lerp(Rgb::new(b.0, b.1, b.2), p - i as f32)
+        };
+        match self {
+            Palette::Rainbow => Rgb::from_hsv((t * 359.0) as u32, 100, 100).unwrap_or(OFF),
+            Palette::Fire => gradient(FIRE),
+            Palette::Ocean => gradient(OCEAN),
+            Palette::Forest => gradient(FOREST),
+            Palette::Meter => gradient(METER),
+            Palette::Mono(rgb) => OFF.lerp(*rgb, t),
+        }
+    }
+    /// Colour at position t repeating every 1.0 (gradients run there and back)
+    pub fn cycle(&self, t: f32) -> Rgb {
+        let t = t.rem_euclid(1.0);
+        match self {
+            Palette::Rainbow => self.colour(t),
+            _ => self.colour(1.0 - (2.0 * t - 1.0).abs()),
+        }
+    }
+}
+
+// Palette name or #rrggbb for Mono
+impl FromStr for Palette {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "rainbow" => Ok(Palette::Rainbow),
+            "fire" => Ok(Palette::Fire),
+            "ocean" => Ok(Palette::Ocean),
+            "forest" => Ok(Palette::Forest),
+            "meter" => Ok(Palette::Meter),
+            hex if hex.starts_with('#') => Ok(Palette::Mono(hex.parse()?)),
+            _ => Err(anyhow!("Unknown palette: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EffectParams {
+    pub speed: f32, // Time multiplier (1.0 = normal)
+    pub palette: Palette,
+    pub density: f32, // 0.0..=1.0 - how busy the effect is
+}
+
+impl Default for EffectParams {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            palette: Palette::Rainbow,
+            density: 0.5,
+        }
+    }
+}
+
+impl EffectParams {
+    // Set parameter by name (eg. from HTTP form)
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "speed" => self.speed = value.trim().parse::<f32>()?.max(0.0),
+            "density" => self.density = value.trim().parse::<f32>()?.clamp(0.0, 1.0),
+            "palette" => self.palette = value.trim().parse()?,
+            _ => bail!("Unknown parameter: {}", name),
+        }
+        Ok(())
+    }
+}
+
+// Animated effect drawn into any framebuffer
+//
+// render is called with time since the effect started - effects keep any
+// state they need and are responsible for drawing every pixel
+pub trait Effect: Send {
+    fn name(&self) -> &'static str;
+    /// Parameters effect is designed for
+    fn defaults(&self) -> EffectParams {
+        EffectParams::default()
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas);
+}
+
+pub type EffectFactory = fn() -> Box<dyn Effect>;
+
+// Named effects
+pub struct EffectRegistry {
+    effects: Vec<(&'static str, EffectFactory)>,
+}
+
+impl EffectRegistry {
+    // Empty registry (use default() for built-in effects)
+    pub fn new() -> Self {
+        Self { effects: vec![] }
+    }
+    // Add effect (replaces existing effect with same name)
+    pub fn register(&mut self, name: &'static str, factory: EffectFactory) {
+        self.effects.retain(|(n, _)| *n != name);
+        self.effects.push((name, factory));
+    }
+    pub fn create(&self, name: &str) -> Option<Box<dyn Effect>> {
+        self.effects
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, factory)| factory())
+    }
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.effects.iter().map(|(n, _)| *n)
+    }
+}
+
+impl Default for EffectRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register("rainbow", || Box::<Rainbow>::default());
+        registry.register("fire", || Box::<Fire>::default());
+        registry.register("plasma", || Box::<Plasma>::default());
+        registry.register("twinkle", || Box::<Twinkle>::default());
+        registry.register("noise", || Box::<Noise>::default());
+        registry.register("matrix-rain", || Box::<MatrixRain>::default());
+        registry.register("meteor", || Box::<Meteor>::default());
+        registry.register("life", || Box::<Life>::default());
+        registry
+    }
+}
+
+// Small xorshift PRNG - effects only need something that looks random
+#[derive(Clone, Debug)]
+pub struct Rng(u32);
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self(seed.max(1))
+    }
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+    /// Random number in 0.0..1.0
+    pub fn f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(0x2545f491)
+    }
+}
+
+// Effect time scaled by speed (so speed can change without jumps)
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Clock {
+    last_ms: Option<u32>,
+    time_ms: f64,    // f64 so effects stay smooth after running for days
+    pending_ms: f32, // Time not yet used by steps()
+}
+
+impl Clock {
+    pub(crate) fn advance(&mut self, elapsed_ms: u32, speed: f32) {
+        let dt = match self.last_ms {
+            Some(last) => elapsed_ms.saturating_sub(last) as f32 * speed.max(0.0),
+            None => 0.0,
+        };
+        self.last_ms = Some(elapsed_ms);
+        self.time_ms += dt as f64;
+        self.pending_ms += dt;
+    }
+    // Scaled time in seconds
+    pub(crate) fn seconds(&self) -> f32 {
+        (self.time_ms / 1000.0) as f32
+    }
+    // Advance and return number of fixed interval steps due (first call gives
+    // one step so there is something to draw)
+    fn steps(&mut self, elapsed_ms: u32, speed: f32, interval_ms: f32) -> usize {
+        let first = self.last_ms.is_none();
+        self.advance(elapsed_ms, speed);
+        let n = (self.pending_ms / interval_ms) as usize;
+        self.pending_ms -= n as f32 * interval_ms;
+        // Don't try to catch up after long gaps
+        (n + first as usize).min(8)
+    }
+}
+
+// Per pixel state sized to canvas
+#[derive(Clone, Debug, Default)]
+struct Grid<T> {
+    w: i32,
+    h: i32,
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Default> Grid<T> {
+    // Resize to canvas - returns true if grid was reset
+    fn fit(&mut self, (w, h): (i32, i32)) -> bool {
+        if (w, h) == (self.w, self.h) {
+            return false;
+        }
+        (self.w, self.h) = (w.max(0), h.max(0));
+        self.cells = vec![T::default(); (self.w * self.h) as usize];
+        true
+    }
+    fn index(&self, (x, y): (i32, i32)) -> usize {
+        (x.rem_euclid(self.w) + y.rem_euclid(self.h) * self.w) as usize
+    }
+    // Co-ordinates wrap around edges
+    fn get(&self, p: (i32, i32)) -> T {
+        self.cells[self.index(p)].clone()
+    }
+    fn set(&mut self, p: (i32, i32), v: T) {
+        let i = self.index(p);
+        self.cells[i] = v;
+    }
+}
+
+fn for_each_pixel(canvas: &mut dyn Canvas, mut f: impl FnMut((i32, i32)) -> Rgb) {
+    let (w, h) = canvas.size();
+    for y in 0..h {
+        for x in 0..w {
+            canvas.set((x, y), f((x, y)));
+        }
+    }
+}
+
+// Diagonal rainbow bands
+#[derive(Clone, Debug, Default)]
+pub struct Rainbow {
+    clock: Clock,
+}
+
+impl Effect for Rainbow {
+    fn name(&self) -> &'static str {
+        "rainbow"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        self.clock.advance(elapsed_ms, params.speed);
+        let (w, h) = canvas.size();
+        // Density sets number of bands across display
+        let bands = 0.5 + params.density * 2.0;
+        let t = self.clock.seconds() / 2.0;
+        for_each_pixel(canvas, |(x, y)| {
+            let p = (x as f32 / w as f32 + y as f32 / (h * 4) as f32) * bands;
+            params.palette.cycle(p - t)
+        });
+    }
+}
+
+// Heat rising from sparks along the bottom row
+#[derive(Clone, Debug, Default)]
+pub struct Fire {
+    clock: Clock,
+    rng: Rng,
+    heat: Grid<f32>,
+}
+
+impl Effect for Fire {
+    fn name(&self) -> &'static str {
+        "fire"
+    }
+    fn defaults(&self) -> EffectParams {
+        EffectParams {
+            palette: Palette::Fire,
+            ..Default::default()
+        }
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        let (w, h) = canvas.size();
+        if w == 0 || h == 0 {
+            return;
+        }
+        self.heat.fit((w, h));
+        for _ in 0..self.clock.steps(elapsed_ms, params.speed, 40.0) {
+            // Cool (taller fires cool more slowly)
+            let cooling = (0.6 - params.density * 0.4) * 8.0 / h as f32;
+            for i in 0..self.heat.cells.len() {
+                let c = self.rng.f32() * cooling;
+                self.heat.cells[i] = (self.heat.cells[i] - c).max(0.0);
+            }
+            // Drift up and diffuse
+            for y in 0..h - 1 {
+                for x in 0..w {
+                    let below = self.heat.get((x, y + 1));
+                    let below2 = if y + 2 < h {
+                        self.heat.get((x, y + 2))
+                    } else {
+                        below
+                    };
+                    let side = self.heat.get((x + 1, y + 1)) + self.heat.get((x - 1, y + 1));
+                    self.heat
+                        .set((x, y), (below * 2.0 + below2 * 1.5 + side * 0.25) / 4.0);
+                }
+            }
+            // Sparks
+            for x in 0..w {
+                if self.rng.f32() < 0.2 + params.density * 0.6 {
+                    let v = self.heat.get((x, h - 1)) + 0.4 + self.rng.f32() * 0.6;
+                    self.heat.set((x, h - 1), v.min(1.0));
+                }
+            }
+        }
+        let heat = &self.heat;
+        for_each_pixel(canvas, |p| params.palette.colour(heat.get(p)));
+    }
+}
+
+// Sum of sine waves
+#[derive(Clone, Debug, Default)]
+pub struct Plasma {
+    clock: Clock,
+}
+
+impl Effect for Plasma {
+    fn name(&self) -> &'static str {
+        "plasma"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        self.clock.advance(elapsed_ms, params.speed);
+        let (w, h) = canvas.size();
+        let t = self.clock.seconds();
+        // Density sets spatial frequency
+        let k = 0.2 + params.density * 0.6;
+        let (cx, cy) = (
+            w as f32 / 2.0 + (t * 0.7).sin() * w as f32 / 3.0,
+            h as f32 / 2.0,
+        );
+        for_each_pixel(canvas, |(x, y)| {
+            let (x, y) = (x as f32, y as f32);
+            let v = (x * k + t).sin()
+                + (y * k * 1.3 - t * 0.8).sin()
+                + ((x + y) * k * 0.7 + t * 1.2).sin()
+                + (((x - cx).powi(2) + (y - cy).powi(2)).sqrt() * k - t).sin();
+            params.palette.cycle(v / 8.0 + t * 0.05)
+        });
+    }
+}
+
+// Random pixels light up and fade
+#[derive(Clone, Debug, Default)]
+pub struct Twinkle {
+    clock: Clock,
+    rng: Rng,
+    stars: Grid<(f32, f32)>, // (brightness, palette position)
+}
+
+impl Effect for Twinkle {
+    fn name(&self) -> &'static str {
+        "twinkle"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        self.stars.fit(canvas.size());
+        for _ in 0..self.clock.steps(elapsed_ms, params.speed, 25.0) {
+            for star in self.stars.cells.iter_mut() {
+                if star.0 > 0.02 {
+                    star.0 *= 0.93;
+                } else if self.rng.f32() < params.density * 0.02 {
+                    *star = (1.0, self.rng.f32());
+                } else {
+                    star.0 = 0.0;
+                }
+            }
+        }
+        let stars = &self.stars;
+        for_each_pixel(canvas, |p| {
+            let (v, t) = stars.get(p);
+            OFF.lerp(params.palette.colour(t), v)
+        });
+    }
+}
+
+// Hash lattice point to 0.0..1.0
+fn lattice(x: i32, y: i32, z: i32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6b343)
+        ^ (y as u32).wrapping_mul(0xd8163841)
+        ^ (z as u32).wrapping_mul(0xcb1ab31f);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0x5bd1e995);
+    h ^= h >> 15;
+    (h & 0xffff) as f32 / 65535.0
+}
+
+// Smooth value noise (0.0..1.0)
+//
+// Lattice wraps for huge inputs (casts saturate) so any f32 is safe
+pub fn value_noise(x: f32, y: f32, z: f32) -> f32 {
+    let (xi, yi, zi) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+    let s = |t: f32| {
+        let t = t.clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+    let (fx, fy, fz) = (s(x - xi as f32), s(y - yi as f32), s(z - zi as f32));
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let (xn, yn) = (xi.wrapping_add(1), yi.wrapping_add(1));
+    let plane = |z: i32| {
+        let top = lerp(lattice(xi, yi, z), lattice(xn, yi, z), fx);
+        let bottom = lerp(lattice(xi, yn, z), lattice(xn, yn, z), fx);
+        lerp(top, bottom, fy)
+    };
+    lerp(plane(zi), plane(zi.wrapping_add(1)), fz)
+}
+
+// Drifting value noise
+#[derive(Clone, Debug, Default)]
+pub struct Noise {
+    clock: Clock,
+}
+
+impl Effect for Noise {
+    fn name(&self) -> &'static str {
+        "noise"
+    }
+    fn defaults(&self) -> EffectParams {
+        EffectParams {
+            palette: Palette::Ocean,
+            ..Default::default()
+        }
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        self.clock.advance(elapsed_ms, params.speed);
+        let t = self.clock.seconds();
+        // Density sets feature size
+        let scale = 0.1 + params.density * 0.4;
+        for_each_pixel(canvas, |(x, y)| {
+            let (x, y) = (x as f32 * scale + t * 0.3, y as f32 * scale);
+            let v = value_noise(x, y, t * 0.5) * 0.7 + value_noise(x * 2.0, y * 2.0, t) * 0.3;
+            params.palette.colour(v)
+        });
+    }
+}
+
+// Falling columns of light with fading trails
+#[derive(Clone, Debug, Default)]
+pub struct MatrixRain {
+    clock: Clock,
+    rng: Rng,
+    trails: Grid<f32>,
+    drops: Vec<Option<i32>>, // Head position for each column
+}
+
+impl Effect for MatrixRain {
+    fn name(&self) -> &'static str {
+        "matrix-rain"
+    }
+    fn defaults(&self) -> EffectParams {
+        EffectParams {
+            palette: Palette::Mono(Rgb::new(64, 255, 64)),
+            ..Default::default()
+        }
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        let (w, h) = canvas.size();
+        if self.trails.fit((w, h)) {
+            self.drops = vec![None; w.max(0) as usize];
+        }
+        for _ in 0..self.clock.steps(elapsed_ms, params.speed, 80.0) {
+            self.trails.cells.iter_mut().for_each(|v| *v *= 0.7);
+            for (x, drop) in self.drops.iter_mut().enumerate() {
+                *drop = match *drop {
+                    Some(y) if y + 1 < h => Some(y + 1),
+                    Some(_) => None,
+                    None if self.rng.f32() < params.density * 0.15 => Some(0),
+                    None => None,
+                };
+                if let Some(y) = *drop {
+                    self.trails.set((x as i32, y), 1.0);
+                }
+            }
+        }
+        let trails = &self.trails;
+        for_each_pixel(canvas, |p| params.palette.colour(trails.get(p)));
+    }
+}
+
+// Meteors streaking across each row leaving sparkling trails
+#[derive(Clone, Debug, Default)]
+pub struct Meteor {
+    clock: Clock,
+    rng: Rng,
+    trails: Grid<(f32, f32)>,         // (brightness, palette position)
+    meteors: Vec<Option<(i32, f32)>>, // (x, palette position) for each row
+}
+
+impl Effect for Meteor {
+    fn name(&self) -> &'static str {
+        "meteor"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        let (w, h) = canvas.size();
+        if self.trails.fit((w, h)) {
+            self.meteors = vec![None; h.max(0) as usize];
+        }
+        for _ in 0..self.clock.steps(elapsed_ms, params.speed, 30.0) {
+            // Trails decay unevenly so they sparkle
+            for cell in self.trails.cells.iter_mut() {
+                if self.rng.f32() < 0.6 {
+                    cell.0 *= 0.55 + self.rng.f32() * 0.3;
+                }
+            }
+            for (y, meteor) in self.meteors.iter_mut().enumerate() {
+                *meteor = match *meteor {
+                    Some((x, t)) if x + 1 < w => Some((x + 1, t)),
+                    Some(_) => None,
+                    None if self.rng.f32() < params.density * 0.05 => Some((0, self.rng.f32())),
+                    None => None,
+                };
+                if let Some((x, t)) = *meteor {
+                    self.trails.set((x, y as i32), (1.0, t));
+                }
+            }
+        }
+        let trails = &self.trails;
+        for_each_pixel(canvas, |p| {
+            let (v, t) = trails.get(p);
+            OFF.lerp(params.palette.colour(t), v)
+        });
+    }
+}
+
+// Conway's Game of Life on a torus - reseeds when the colony dies out or
+// gets stuck in a short cycle
+#[derive(Clone, Debug, Default)]
+pub struct Life {
+    clock: Clock,
+    rng: Rng,
+    cells: Grid<u16>, // Age of live cells (0 = dead)
+    history: [u64; 2],
+    generation: usize,
+}
+
+impl Life {
+    const MAX_GENERATIONS: usize = 500;
+    fn seed(&mut self, density: f32) {
+        let density = 0.15 + density * 0.35;
+        for cell in self.cells.cells.iter_mut() {
+            *cell = (self.rng.f32() < density) as u16;
+        }
+        self.history = [0, 0];
+        self.generation = 0;
+    }
+    fn step(&mut self) {
+        let (w, h) = (self.cells.w, self.cells.h);
+        let mut next = self.cells.clone();
+        for y in 0..h {
+            for x in 0..w {
+                let mut n = 0;
+                for (dx, dy) in [
+                    (-1, -1),
+                    (0, -1),
+                    (1, -1),
+                    (-1, 0),
+                    (1, 0),
+                    (-1, 1),
+                    (0, 1),
+                    (1, 1),
+                ] {
+                    n += (self.cells.get((x + dx, y + dy)) > 0) as u8;
+                }
+                let age = self.cells.get((x, y));
+                next.set(
+                    (x, y),
+                    match (age > 0, n) {
+                        (true, 2) | (true, 3) => age.saturating_add(1),
+                        (false, 3) => 1,
+                        _ => 0,
+                    },
+                );
+            }
+        }
+        self.cells = next;
+        self.generation += 1;
+    }
+    // Hash of live cells (ignoring age)
+    fn state(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        self.cells
+            .cells
+            .iter()
+            .for_each(|c| hasher.write_u8((*c > 0) as u8));
+        hasher.finish()
+    }
+}
+
+impl Effect for Life {
+    fn name(&self) -> &'static str {
+        "life"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        if self.cells.fit(canvas.size()) {
+            self.seed(params.density);
+        }
+        for _ in 0..self.clock.steps(elapsed_ms, params.speed, 200.0) {
+            self.step();
+            let state = self.state();
+            let alive = self.cells.cells.iter().any(|c| *c > 0);
+            if !alive || self.history.contains(&state) || self.generation > Self::MAX_GENERATIONS {
+                self.seed(params.density);
+            } else {
+                self.history = [self.history[1], state];
+            }
+        }
+        let cells = &self.cells;
+        for_each_pixel(canvas, |p| match cells.get(p) {
+            0 => OFF,
+            age => params.palette.cycle(age as f32 / 32.0),
+        });
+    }
+}
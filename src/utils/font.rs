@@ -0,0 +1,589 @@
+use font8x8::{
+    UnicodeFonts, BASIC_FONTS, BLOCK_FONTS, BOX_FONTS, GREEK_FONTS, HIRAGANA_FONTS, LATIN_FONTS,
+};
+
+use crate::bdf::BdfFont;
+
+pub const MAX_GLYPH_SIZE: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    pub width: i32,                  // Advance in pixels (including any built in spacing)
+    pub height: i32,                 // Rows used
+    pub rows: [u32; MAX_GLYPH_SIZE], // LSB is the leftmost column
+}
+
+impl Glyph {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width: width.clamp(0, MAX_GLYPH_SIZE as i32),
+            height: height.clamp(0, MAX_GLYPH_SIZE as i32),
+            rows: [0; MAX_GLYPH_SIZE],
+        }
+    }
+    pub fn from_rows<T>(width: i32, rows: T) -> Self
+    where
+        T: IntoIterator<Item = u32>,
+    {
+        let mut glyph = Self::new(width, 0);
+        for (i, row) in rows.into_iter().take(MAX_GLYPH_SIZE).enumerate() {
+            glyph.rows[i] = row;
+            glyph.height = i as i32 + 1;
+        }
+        glyph
+    }
+    pub fn pixel(&self, x: i32, y: i32) -> bool {
+        (0..self.height).contains(&y)
+            && (0..MAX_GLYPH_SIZE as i32).contains(&x)
+            && self.rows[y as usize] & (1 << x) != 0
+    }
+    // Returns (first column, width) of inked columns
+    pub fn columns(&self) -> Option<(i32, i32)> {
+        let cols = self.rows[..self.height as usize]
+            .iter()
+            .fold(0_u32, |acc, row| acc | row);
+        if cols == 0 {
+            None
+        } else {
+            let first = cols.trailing_zeros() as i32;
+            let last = 31 - cols.leading_zeros() as i32;
+            Some((first, last - first + 1))
+        }
+    }
+}
+
+pub trait Font {
+    /// Glyph for char (None if the font has no glyph)
+    fn glyph(&self, c: char) -> Option<Glyph>;
+    /// Line height in pixels
+    fn height(&self) -> i32;
+    /// Rows from top of line to baseline
+    fn baseline(&self) -> i32;
+}
+
+impl<F: Font + ?Sized> Font for &F {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        (**self).glyph(c)
+    }
+    fn height(&self) -> i32 {
+        (**self).height()
+    }
+    fn baseline(&self) -> i32 {
+        (**self).baseline()
+    }
+}
+
+// Hollow box
+pub const REPLACEMENT_GLYPH: [u8; 8] = [0x7e, 0x42, 0x42, 0x42, 0x42, 0x42, 0x7e, 0x00];
+
+// Look up char in font8x8 sets (in fallback order)
+pub fn font8x8_glyph(c: char) -> Option<[u8; 8]> {
+    BASIC_FONTS
+        .get(c)
+        .or_else(|| LATIN_FONTS.get(c))
+        .or_else(|| GREEK_FONTS.get(c))
+        .or_else(|| BOX_FONTS.get(c))
+        .or_else(|| BLOCK_FONTS.get(c))
+        .or_else(|| HIRAGANA_FONTS.get(c))
+}
+
+// font8x8 with fallback across the unicode sets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Font8x8 {
+    pub replacement: Option<[u8; 8]>, // Glyph for characters missing from all sets
+}
+
+pub const FONT_8X8: Font8x8 = Font8x8 {
+    replacement: Some(REPLACEMENT_GLYPH),
+};
+
+impl Font for Font8x8 {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        font8x8_glyph(c)
+            .or(self.replacement)
+            .map(|g| Glyph::from_rows(8, g.map(|row| row as u32)))
+    }
+    fn height(&self) -> i32 {
+        8
+    }
+    fn baseline(&self) -> i32 {
+        7
+    }
+}
+
+// Fixed size font (up to 8 pixels wide) covering one or more char ranges
+//
+// Rows are stored MSB first (leftmost pixel in bit width-1) so that the
+// tables below can be read as binary literals
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitmapFont {
+    width: i32,
+    height: i32,
+    baseline: i32,
+    chars: &'static [(char, char)], // Inclusive char ranges in data order
+    data: &'static [u8],
+    fold_case: bool, // Uppercase only font
+}
+
+impl BitmapFont {
+    pub const fn new(
+        (width, height): (i32, i32),
+        baseline: i32,
+        chars: &'static [(char, char)],
+        data: &'static [u8],
+        fold_case: bool,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            baseline,
+            chars,
+            data,
+            fold_case,
+        }
+    }
+    // Position of char in data
+    fn index(&self, c: char) -> Option<usize> {
+        let mut start = 0;
+        for &(first, last) in self.chars {
+            if (first..=last).contains(&c) {
+                return Some(start + (c as u32 - first as u32) as usize);
+            }
+            start += (last as u32 - first as u32 + 1) as usize;
+        }
+        None
+    }
+}
+
+impl Font for BitmapFont {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        let c = if self.fold_case {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        };
+        let index = self.index(c)?;
+        let h = self.height as usize;
+        let rows = self.data.get(index * h..(index + 1) * h)?;
+        // Leave a blank column after each glyph
+        Some(Glyph::from_rows(
+            self.width + 1,
+            rows.iter()
+                .map(|&row| (row.reverse_bits() >> (8 - self.width)) as u32),
+        ))
+    }
+    fn height(&self) -> i32 {
+        self.height
+    }
+    fn baseline(&self) -> i32 {
+        self.baseline
+    }
+}
+
+// Uppercase only - no rows for 'a'..='z'
+pub const FONT_3X5: BitmapFont =
+    BitmapFont::new((3, 5), 5, &[(' ', '`'), ('{', '~')], &FONT_3X5_DATA, true);
+pub const FONT_4X6: BitmapFont = BitmapFont::new((4, 6), 5, &[(' ', '~')], &FONT_4X6_DATA, false);
+pub const FONT_5X7: BitmapFont = BitmapFont::new((5, 7), 7, &[(' ', '~')], &FONT_5X7_DATA, false);
+// Digits, ':', '.', '-' and ' ' only - fills an 8 pixel high display
+pub const FONT_TALL_DIGITS: BitmapFont = BitmapFont::new(
+    (3, 8),
+    8,
+    &[(' ', ' '), ('-', '.'), ('0', ':')],
+    &FONT_TALL_DIGITS_DATA,
+    false,
+);
+
+// Integer scaled font
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scaled<F> {
+    font: F,
+    scale: i32,
+}
+
+impl<F: Font> Scaled<F> {
+    /// Scale is clamped so that scaled glyphs fit in MAX_GLYPH_SIZE rows
+    pub fn new(font: F, scale: i32) -> Self {
+        let max = (MAX_GLYPH_SIZE as i32 / font.height().max(1)).max(1);
+        Self {
+            font,
+            scale: scale.clamp(1, max),
+        }
+    }
+}
+
+impl<F: Font> Font for Scaled<F> {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        let glyph = self.font.glyph(c)?;
+        let s = self.scale;
+        let mut out = Glyph::new(glyph.width * s, glyph.height * s);
+        for y in 0..out.height {
+            for x in 0..MAX_GLYPH_SIZE as i32 {
+                if glyph.pixel(x / s, y / s) {
+                    out.rows[y as usize] |= 1 << x;
+                }
+            }
+        }
+        Some(out)
+    }
+    fn height(&self) -> i32 {
+        self.font.height() * self.scale
+    }
+    fn baseline(&self) -> i32 {
+        self.font.baseline() * self.scale
+    }
+}
+
+// Synthetic bold - each pixel is doubled to the right
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bold<F>(pub F);
+
+impl<F: Font> Font for Bold<F> {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        let mut glyph = self.0.glyph(c)?;
+        glyph.rows.iter_mut().for_each(|row| *row |= *row << 1);
+        glyph.width = (glyph.width + 1).min(MAX_GLYPH_SIZE as i32);
+        Some(glyph)
+    }
+    fn height(&self) -> i32 {
+        self.0.height()
+    }
+    fn baseline(&self) -> i32 {
+        self.0.baseline()
+    }
+}
+
+// Sparse font with variable width glyphs (generated from BDF files by build.rs)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaticFont {
+    pub height: i32,
+    pub baseline: i32,
+    pub glyphs: &'static [StaticGlyph], // Sorted by char
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaticGlyph {
+    pub c: char,
+    pub width: i32,
+    pub rows: &'static [u32],
+}
+
+impl Font for StaticFont {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        let i = self.glyphs.binary_search_by_key(&c, |g| g.c).ok()?;
+        let glyph = &self.glyphs[i];
+        Some(Glyph::from_rows(glyph.width, glyph.rows.iter().copied()))
+    }
+    fn height(&self) -> i32 {
+        self.height
+    }
+    fn baseline(&self) -> i32 {
+        self.baseline
+    }
+}
+
+// BDF font parsed at runtime (eg. from include_bytes!)
+impl Font for BdfFont {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        self.get(c)
+            .map(|g| Glyph::from_rows(g.width, g.rows.iter().copied()))
+    }
+    fn height(&self) -> i32 {
+        self.height
+    }
+    fn baseline(&self) -> i32 {
+        self.baseline
+    }
+}
+
+// Fonts converted from BDF files at build time (see build.rs)
+include!(concat!(env!("OUT_DIR"), "/bdf_fonts.rs"));
+
+#[rustfmt::skip]
+const FONT_3X5_DATA: [u8; 69 * 5] = [
+    0b000, 0b000, 0b000, 0b000, 0b000, // ' '
+    0b010, 0b010, 0b010, 0b000, 0b010, // '!'
+    0b101, 0b101, 0b000, 0b000, 0b000, // '"'
+    0b101, 0b111, 0b101, 0b111, 0b101, // '#'
+    0b011, 0b110, 0b010, 0b011, 0b110, // '$'
+    0b101, 0b001, 0b010, 0b100, 0b101, // '%'
+    0b010, 0b101, 0b010, 0b101, 0b011, // '&'
+    0b010, 0b010, 0b000, 0b000, 0b000, // '\''
+    0b001, 0b010, 0b010, 0b010, 0b001, // '('
+    0b100, 0b010, 0b010, 0b010, 0b100, // ')'
+    0b101, 0b010, 0b101, 0b000, 0b000, // '*'
+    0b000, 0b010, 0b111, 0b010, 0b000, // '+'
+    0b000, 0b000, 0b000, 0b010, 0b100, // ','
+    0b000, 0b000, 0b111, 0b000, 0b000, // '-'
+    0b000, 0b000, 0b000, 0b000, 0b010, // '.'
+    0b001, 0b001, 0b010, 0b100, 0b100, // '/'
+    0b111, 0b101, 0b101, 0b101, 0b111, // '0'
+    0b010, 0b110, 0b010, 0b010, 0b111, // '1'
+    0b111, 0b001, 0b111, 0b100, 0b111, // '2'
+    0b111, 0b001, 0b011, 0b001, 0b111, // '3'
+    0b101, 0b101, 0b111, 0b001, 0b001, // '4'
+    0b111, 0b100, 0b111, 0b001, 0b111, // '5'
+    0b111, 0b100, 0b111, 0b101, 0b111, // '6'
+    0b111, 0b001, 0b001, 0b010, 0b010, // '7'
+    0b111, 0b101, 0b111, 0b101, 0b111, // '8'
+    0b111, 0b101, 0b111, 0b001, 0b111, // '9'
+    0b000, 0b010, 0b000, 0b010, 0b000, // ':'
+    0b000, 0b010, 0b000, 0b010, 0b100, // ';'
+    0b001, 0b010, 0b100, 0b010, 0b001, // '<'
+    0b000, 0b111, 0b000, 0b111, 0b000, // '='
+    0b100, 0b010, 0b001, 0b010, 0b100, // '>'
+    0b111, 0b001, 0b010, 0b000, 0b010, // '?'
+    0b010, 0b101, 0b111, 0b100, 0b011, // '@'
+    0b010, 0b101, 0b111, 0b101, 0b101, // 'A'
+    0b110, 0b101, 0b110, 0b101, 0b110, // 'B'
+    0b011, 0b100, 0b100, 0b100, 0b011, // 'C'
+    0b110, 0b101, 0b101, 0b101, 0b110, // 'D'
+    0b111, 0b100, 0b110, 0b100, 0b111, // 'E'
+    0b111, 0b100, 0b110, 0b100, 0b100, // 'F'
+    0b011, 0b100, 0b101, 0b101, 0b011, // 'G'
+    0b101, 0b101, 0b111, 0b101, 0b101, // 'H'
+    0b111, 0b010, 0b010, 0b010, 0b111, // 'I'
+    0b001, 0b001, 0b001, 0b101, 0b010, // 'J'
+    0b101, 0b101, 0b110, 0b101, 0b101, // 'K'
+    0b100, 0b100, 0b100, 0b100, 0b111, // 'L'
+    0b101, 0b111, 0b111, 0b101, 0b101, // 'M'
+    0b110, 0b101, 0b101, 0b101, 0b101, // 'N'
+    0b010, 0b101, 0b101, 0b101, 0b010, // 'O'
+    0b110, 0b101, 0b110, 0b100, 0b100, // 'P'
+    0b010, 0b101, 0b101, 0b110, 0b011, // 'Q'
+    0b110, 0b101, 0b110, 0b101, 0b101, // 'R'
+    0b011, 0b100, 0b010, 0b001, 0b110, // 'S'
+    0b111, 0b010, 0b010, 0b010, 0b010, // 'T'
+    0b101, 0b101, 0b101, 0b101, 0b111, // 'U'
+    0b101, 0b101, 0b101, 0b010, 0b010, // 'V'
+    0b101, 0b101, 0b111, 0b111, 0b101, // 'W'
+    0b101, 0b101, 0b010, 0b101, 0b101, // 'X'
+    0b101, 0b101, 0b010, 0b010, 0b010, // 'Y'
+    0b111, 0b001, 0b010, 0b100, 0b111, // 'Z'
+    0b110, 0b100, 0b100, 0b100, 0b110, // '['
+    0b100, 0b100, 0b010, 0b001, 0b001, // '\\'
+    0b011, 0b001, 0b001, 0b001, 0b011, // ']'
+    0b010, 0b101, 0b000, 0b000, 0b000, // '^'
+    0b000, 0b000, 0b000, 0b000, 0b111, // '_'
+    0b100, 0b010, 0b000, 0b000, 0b000, // '`'
+    0b011, 0b010, 0b100, 0b010, 0b011, // '{'
+    0b010, 0b010, 0b010, 0b010, 0b010, // '|'
+    0b110, 0b010, 0b001, 0b010, 0b110, // '}'
+    0b000, 0b110, 0b011, 0b000, 0b000, // '~'
+];
+
+#[rustfmt::skip]
+const FONT_4X6_DATA: [u8; 95 * 6] = [
+    0b0000, 0b0000, 0b0000, 0b0000, 0b0000, 0b0000, // ' '
+    0b0100, 0b0100, 0b0100, 0b0000, 0b0100, 0b0000, // '!'
+    0b1010, 0b1010, 0b0000, 0b0000, 0b0000, 0b0000, // '"'
+    0b1010, 0b1110, 0b1010, 0b1110, 0b1010, 0b0000, // '#'
+    0b0110, 0b1100, 0b0100, 0b0110, 0b1100, 0b0000, // '$'
+    0b1010, 0b0010, 0b0100, 0b1000, 0b1010, 0b0000, // '%'
+    0b0100, 0b1010, 0b0100, 0b1010, 0b0110, 0b0000, // '&'
+    0b0100, 0b0100, 0b0000, 0b0000, 0b0000, 0b0000, // '\''
+    0b0010, 0b0100, 0b0100, 0b0100, 0b0010, 0b0000, // '('
+    0b1000, 0b0100, 0b0100, 0b0100, 0b1000, 0b0000, // ')'
+    0b1010, 0b0100, 0b1010, 0b0000, 0b0000, 0b0000, // '*'
+    0b0000, 0b0100, 0b1110, 0b0100, 0b0000, 0b0000, // '+'
+    0b0000, 0b0000, 0b0000, 0b0000, 0b0100, 0b1000, // ','
+    0b0000, 0b0000, 0b1110, 0b0000, 0b0000, 0b0000, // '-'
+    0b0000, 0b0000, 0b0000, 0b0000, 0b0100, 0b0000, // '.'
+    0b0010, 0b0010, 0b0100, 0b1000, 0b1000, 0b0000, // '/'
+    0b1110, 0b1010, 0b1010, 0b1010, 0b1110, 0b0000, // '0'
+    0b0100, 0b1100, 0b0100, 0b0100, 0b1110, 0b0000, // '1'
+    0b1110, 0b0010, 0b1110, 0b1000, 0b1110, 0b0000, // '2'
+    0b1110, 0b0010, 0b0110, 0b0010, 0b1110, 0b0000, // '3'
+    0b1010, 0b1010, 0b1110, 0b0010, 0b0010, 0b0000, // '4'
+    0b1110, 0b1000, 0b1110, 0b0010, 0b1110, 0b0000, // '5'
+    0b1110, 0b1000, 0b1110, 0b1010, 0b1110, 0b0000, // '6'
+    0b1110, 0b0010, 0b0010, 0b0100, 0b0100, 0b0000, // '7'
+    0b1110, 0b1010, 0b1110, 0b1010, 0b1110, 0b0000, // '8'
+    0b1110, 0b1010, 0b1110, 0b0010, 0b1110, 0b0000, // '9'
+    0b0000, 0b0100, 0b0000, 0b0100, 0b0000, 0b0000, // ':'
+    0b0000, 0b0100, 0b0000, 0b0000, 0b0100, 0b1000, // ';'
+    0b0010, 0b0100, 0b1000, 0b0100, 0b0010, 0b0000, // '<'
+    0b0000, 0b1110, 0b0000, 0b1110, 0b0000, 0b0000, // '='
+    0b1000, 0b0100, 0b0010, 0b0100, 0b1000, 0b0000, // '>'
+    0b1110, 0b0010, 0b0100, 0b0000, 0b0100, 0b0000, // '?'
+    0b0100, 0b1010, 0b1110, 0b1000, 0b0110, 0b0000, // '@'
+    0b0100, 0b1010, 0b1110, 0b1010, 0b1010, 0b0000, // 'A'
+    0b1100, 0b1010, 0b1100, 0b1010, 0b1100, 0b0000, // 'B'
+    0b0110, 0b1000, 0b1000, 0b1000, 0b0110, 0b0000, // 'C'
+    0b1100, 0b1010, 0b1010, 0b1010, 0b1100, 0b0000, // 'D'
+    0b1110, 0b1000, 0b1100, 0b1000, 0b1110, 0b0000, // 'E'
+    0b1110, 0b1000, 0b1100, 0b1000, 0b1000, 0b0000, // 'F'
+    0b0110, 0b1000, 0b1010, 0b1010, 0b0110, 0b0000, // 'G'
+    0b1010, 0b1010, 0b1110, 0b1010, 0b1010, 0b0000, // 'H'
+    0b1110, 0b0100, 0b0100, 0b0100, 0b1110, 0b0000, // 'I'
+    0b0010, 0b0010, 0b0010, 0b1010, 0b0100, 0b0000, // 'J'
+    0b1010, 0b1010, 0b1100, 0b1010, 0b1010, 0b0000, // 'K'
+    0b1000, 0b1000, 0b1000, 0b1000, 0b1110, 0b0000, // 'L'
+    0b1001, 0b1111, 0b1111, 0b1001, 0b1001, 0b0000, // 'M'
+    0b1001, 0b1101, 0b1011, 0b1001, 0b1001, 0b0000, // 'N'
+    0b0100, 0b1010, 0b1010, 0b1010, 0b0100, 0b0000, // 'O'
+    0b1100, 0b1010, 0b1100, 0b1000, 0b1000, 0b0000, // 'P'
+    0b0100, 0b1010, 0b1010, 0b1100, 0b0110, 0b0000, // 'Q'
+    0b1100, 0b1010, 0b1100, 0b1010, 0b1010, 0b0000, // 'R'
+    0b0110, 0b1000, 0b0100, 0b0010, 0b1100, 0b0000, // 'S'
+    0b1110, 0b0100, 0b0100, 0b0100, 0b0100, 0b0000, // 'T'
+    0b1010, 0b1010, 0b1010, 0b1010, 0b1110, 0b0000, // 'U'
+    0b1010, 0b1010, 0b1010, 0b0100, 0b0100, 0b0000, // 'V'
+    0b1001, 0b1001, 0b1111, 0b1111, 0b1001, 0b0000, // 'W'
+    0b1010, 0b1010, 0b0100, 0b1010, 0b1010, 0b0000, // 'X'
+    0b1010, 0b1010, 0b0100, 0b0100, 0b0100, 0b0000, // 'Y'
+    0b1110, 0b0010, 0b0100, 0b1000, 0b1110, 0b0000, // 'Z'
+    0b1100, 0b1000, 0b1000, 0b1000, 0b1100, 0b0000, // '['
+    0b1000, 0b1000, 0b0100, 0b0010, 0b0010, 0b0000, // '\\'
+    0b0110, 0b0010, 0b0010, 0b0010, 0b0110, 0b0000, // ']'
+    0b0100, 0b1010, 0b0000, 0b0000, 0b0000, 0b0000, // '^'
+    0b0000, 0b0000, 0b0000, 0b0000, 0b1110, 0b0000, // '_'
+    0b1000, 0b0100, 0b0000, 0b0000, 0b0000, 0b0000, // '`'
+    0b0000, 0b0000, 0b0110, 0b1010, 0b0110, 0b0000, // 'a'
+    0b1000, 0b1000, 0b1100, 0b1010, 0b1100, 0b0000, // 'b'
+    0b0000, 0b0000, 0b0110, 0b1000, 0b0110, 0b0000, // 'c'
+    0b0010, 0b0010, 0b0110, 0b1010, 0b0110, 0b0000, // 'd'
+    0b0000, 0b0000, 0b0100, 0b1110, 0b0110, 0b0000, // 'e'
+    0b0010, 0b0100, 0b1110, 0b0100, 0b0100, 0b0000, // 'f'
+    0b0000, 0b0000, 0b0110, 0b1010, 0b0110, 0b1100, // 'g'
+    0b1000, 0b1000, 0b1100, 0b1010, 0b1010, 0b0000, // 'h'
+    0b0100, 0b0000, 0b0100, 0b0100, 0b0100, 0b0000, // 'i'
+    0b0010, 0b0000, 0b0010, 0b0010, 0b0010, 0b1100, // 'j'
+    0b1000, 0b1000, 0b1010, 0b1100, 0b1010, 0b0000, // 'k'
+    0b1100, 0b0100, 0b0100, 0b0100, 0b1110, 0b0000, // 'l'
+    0b0000, 0b0000, 0b1110, 0b1110, 0b1010, 0b0000, // 'm'
+    0b0000, 0b0000, 0b1100, 0b1010, 0b1010, 0b0000, // 'n'
+    0b0000, 0b0000, 0b0100, 0b1010, 0b0100, 0b0000, // 'o'
+    0b0000, 0b0000, 0b1100, 0b1010, 0b1100, 0b1000, // 'p'
+    0b0000, 0b0000, 0b0110, 0b1010, 0b0110, 0b0010, // 'q'
+    0b0000, 0b0000, 0b1010, 0b1100, 0b1000, 0b0000, // 'r'
+    0b0000, 0b0000, 0b0110, 0b0100, 0b1100, 0b0000, // 's'
+    0b0000, 0b0100, 0b1110, 0b0100, 0b0010, 0b0000, // 't'
+    0b0000, 0b0000, 0b1010, 0b1010, 0b0110, 0b0000, // 'u'
+    0b0000, 0b0000, 0b1010, 0b1010, 0b0100, 0b0000, // 'v'
+    0b0000, 0b0000, 0b1010, 0b1110, 0b1110, 0b0000, // 'w'
+    0b0000, 0b0000, 0b1010, 0b0100, 0b1010, 0b0000, // 'x'
+    0b0000, 0b0000, 0b1010, 0b1010, 0b0110, 0b1100, // 'y'
+    0b0000, 0b0000, 0b1100, 0b0100, 0b0110, 0b0000, // 'z'
+    0b0110, 0b0100, 0b1000, 0b0100, 0b0110, 0b0000, // '{'
+    0b0100, 0b0100, 0b0100, 0b0100, 0b0100, 0b0000, // '|'
+    0b1100, 0b0100, 0b0010, 0b0100, 0b1100, 0b0000, // '}'
+    0b0000, 0b1100, 0b0110, 0b0000, 0b0000, 0b0000, // '~'
+];
+
+#[rustfmt::skip]
+const FONT_5X7_DATA: [u8; 95 * 7] = [
+    0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, // ' '
+    0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00000, 0b00100, // '!'
+    0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000, // '"'
+    0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010, // '#'
+    0b00100, 0b01111, 0b10100, 0b01110, 0b00101, 0b11110, 0b00100, // '$'
+    0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011, // '%'
+    0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101, // '&'
+    0b00100, 0b00100, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, // '\''
+    0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010, // '('
+    0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000, // ')'
+    0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000, // '*'
+    0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000, // '+'
+    0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000, // ','
+    0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000, // '-'
+    0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100, // '.'
+    0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000, // '/'
+    0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110, // '0'
+    0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, // '1'
+    0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111, // '2'
+    0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110, // '3'
+    0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010, // '4'
+    0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110, // '5'
+    0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110, // '6'
+    0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000, // '7'
+    0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110, // '8'
+    0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100, // '9'
+    0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000, // ':'
+    0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b00100, 0b01000, // ';'
+    0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010, // '<'
+    0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000, // '='
+    0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000, // '>'
+    0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100, // '?'
+    0b01110, 0b10001, 0b00001, 0b01101, 0b10101, 0b10101, 0b01110, // '@'
+    0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, // 'A'
+    0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110, // 'B'
+    0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110, // 'C'
+    0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100, // 'D'
+    0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111, // 'E'
+    0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000, // 'F'
+    0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111, // 'G'
+    0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, // 'H'
+    0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, // 'I'
+    0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100, // 'J'
+    0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001, // 'K'
+    0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111, // 'L'
+    0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001, // 'M'
+    0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001, // 'N'
+    0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, // 'O'
+    0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000, // 'P'
+    0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101, // 'Q'
+    0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001, // 'R'
+    0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110, // 'S'
+    0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, // 'T'
+    0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, // 'U'
+    0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, // 'V'
+    0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010, // 'W'
+    0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001, // 'X'
+    0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, // 'Y'
+    0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111, // 'Z'
+    0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110, // '['
+    0b00000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00001, 0b00000, // '\\'
+    0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110, // ']'
+    0b00100, 0b01010, 0b10001, 0b00000, 0b00000, 0b00000, 0b00000, // '^'
+    0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111, // '_'
+    0b01000, 0b00100, 0b00010, 0b00000, 0b00000, 0b00000, 0b00000, // '`'
+    0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111, // 'a'
+    0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110, // 'b'
+    0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110, // 'c'
+    0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111, // 'd'
+    0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110, // 'e'
+    0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000, // 'f'
+    0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110, // 'g'
+    0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001, // 'h'
+    0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110, // 'i'
+    0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100, // 'j'
+    0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, // 'k'
+    0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, // 'l'
+    0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001, // 'm'
+    0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001, // 'n'
+    0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110, // 'o'
+    0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000, // 'p'
+    0b00000, 0b00000, 0b01101, 0b10011, 0b01111, 0b00001, 0b00001, // 'q'
+    0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000, // 'r'
+    0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110, // 's'
+    0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110, // 't'
+    0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101, // 'u'
+    0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, // 'v'
+    0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010, // 'w'
+    0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, // 'x'
+    0b00000, 0b00000, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110, // 'y'
+    0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111, // 'z'
+    0b00010, 0b00100, 0b00100, 0b01000, 0b00100, 0b00100, 0b00010, // '{'
+    0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, // '|'
+    0b01000, 0b00100, 0b00100, 0b00010, 0b00100, 0b00100, 0b01000, // '}'
+    0b00000, 0b00000, 0b01000, 0b10101, 0b00010, 0b00000, 0b00000, // '~'
+];
+
+#[rustfmt::skip]
+const FONT_TALL_DIGITS_DATA: [u8; 14 * 8] = [
+    0b000, 0b000, 0b000, 0b000, 0b000, 0b000, 0b000, 0b000, // ' '
+    0b000, 0b000, 0b000, 0b111, 0b000, 0b000, 0b000, 0b000, // '-'
+    0b000, 0b000, 0b000, 0b000, 0b000, 0b000, 0b000, 0b010, // '.'
+    0b111, 0b101, 0b101, 0b101, 0b101, 0b101, 0b101, 0b111, // '0'
+    0b010, 0b110, 0b010, 0b010, 0b010, 0b010, 0b010, 0b111, // '1'
+    0b111, 0b001, 0b001, 0b111, 0b100, 0b100, 0b100, 0b111, // '2'
+    0b111, 0b001, 0b001, 0b111, 0b001, 0b001, 0b001, 0b111, // '3'
+    0b101, 0b101, 0b101, 0b111, 0b001, 0b001, 0b001, 0b001, // '4'
+    0b111, 0b100, 0b100, 0b111, 0b001, 0b001, 0b001, 0b111, // '5'
+    0b111, 0b100, 0b100, 0b111, 0b101, 0b101, 0b101, 0b111, // '6'
+    0b111, 0b001, 0b001, 0b001, 0b001, 0b001, 0b001, 0b001, // '7'
+    0b111, 0b101, 0b101, 0b111, 0b101, 0b101, 0b101, 0b111, // '8'
+    0b111, 0b101, 0b101, 0b111, 0b001, 0b001, 0b001, 0b111, // '9'
+    0b000, 0b000, 0b010, 0b000, 0b000, 0b010, 0b000, 0b000, // ':'
+];
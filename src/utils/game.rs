@@ -0,0 +1,750 @@
+use anyhow::{anyhow, Result};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use crate::clock;
+use crate::draw::{Canvas, Paint};
+use crate::effect::Rng;
+use crate::rgb::{Rgb, RgbTransform, BLUE, GREEN, OFF, RED, WHITE};
+
+// Steps run per tick before falling behind (so a stall doesn't fast forward)
+const MAX_STEPS_PER_TICK: u32 = 4;
+// Inputs held until next tick
+const MAX_QUEUED_INPUTS: usize = 8;
+const GAME_OVER_MS: u32 = 1200;
+const FLASH_MS: u32 = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    Up,
+    Down,
+    Left,
+    Right,
+    Action, // Button - also restarts after game over
+}
+
+// Input name (accepts browser key names eg. "ArrowUp", " ")
+impl FromStr for Input {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "up" | "arrowup" | "w" => Ok(Input::Up),
+            "down" | "arrowdown" | "s" => Ok(Input::Down),
+            "left" | "arrowleft" | "a" => Ok(Input::Left),
+            "right" | "arrowright" | "d" => Ok(Input::Right),
+            "action" | "button" | "enter" | "space" | " " => Ok(Input::Action),
+            _ => Err(anyhow!("Unknown input: {}", s)),
+        }
+    }
+}
+
+// Game logic advanced in fixed steps
+//
+// Drawing is kept separate from the logic so games can be driven and
+// checked without a display
+pub trait Game: Send {
+    fn name(&self) -> &'static str;
+    /// Fixed step interval (may shorten as the game speeds up)
+    fn step_ms(&self) -> u32;
+    fn input(&mut self, input: Input);
+    fn step(&mut self);
+    fn score(&self) -> u32;
+    fn is_over(&self) -> bool;
+    fn draw(&self, canvas: &mut dyn Canvas);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameKind {
+    Snake,
+    Pong,
+    Tetris,
+}
+
+impl FromStr for GameKind {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "snake" => Ok(GameKind::Snake),
+            "pong" => Ok(GameKind::Pong),
+            "tetris" => Ok(GameKind::Tetris),
+            _ => Err(anyhow!("Unknown game: {}", s)),
+        }
+    }
+}
+
+impl GameKind {
+    /// New game filling display of size (w, h)
+    pub fn create(&self, size: (i32, i32), seed: u32) -> Box<dyn Game> {
+        match self {
+            GameKind::Snake => Box::new(Snake::new(size, seed)),
+            GameKind::Pong => Box::new(Pong::new(size, seed)),
+            GameKind::Tetris => Box::new(Tetris::new(size, seed)),
+        }
+    }
+}
+
+// Snake which wraps round the display edges
+//
+// Action turns clockwise (so the onboard button alone can steer)
+#[derive(Clone, Debug)]
+pub struct Snake {
+    size: (i32, i32),
+    body: VecDeque<(i32, i32)>, // Head first
+    direction: (i32, i32),
+    turns: VecDeque<(i32, i32)>, // Queued so quick double turns aren't lost
+    food: Option<(i32, i32)>,
+    score: u32,
+    over: bool,
+    rng: Rng,
+}
+
+impl Snake {
+    pub fn new((w, h): (i32, i32), seed: u32) -> Self {
+        let (w, h) = (w.max(4), h.max(1));
+        let (x, y) = (w / 2, h / 2);
+        let mut snake = Self {
+            size: (w, h),
+            body: (0..3).map(|i| (x - i, y)).collect(),
+            direction: (1, 0),
+            turns: VecDeque::new(),
+            food: None,
+            score: 0,
+            over: false,
+            rng: Rng::new(seed),
+        };
+        snake.place_food();
+        snake
+    }
+    pub fn body(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.body.iter().copied()
+    }
+    pub fn head(&self) -> (i32, i32) {
+        self.body[0]
+    }
+    pub fn food(&self) -> Option<(i32, i32)> {
+        self.food
+    }
+    /// Move food (eg. to set up a game)
+    pub fn set_food(&mut self, food: Option<(i32, i32)>) {
+        self.food = food;
+    }
+    // Random free cell (board full ends game)
+    fn place_food(&mut self) {
+        let (w, h) = self.size;
+        let free = (0..w * h)
+            .map(|i| (i % w, i / w))
+            .filter(|p| !self.body.contains(p))
+            .collect::<Vec<_>>();
+        self.food = match free.len() {
+            0 => {
+                self.over = true;
+                None
+            }
+            n => Some(free[self.rng.next_u32() as usize % n]),
+        };
+    }
+}
+
+impl Game for Snake {
+    fn name(&self) -> &'static str {
+        "snake"
+    }
+    fn step_ms(&self) -> u32 {
+        160_u32.saturating_sub(self.score * 6).max(70)
+    }
+    fn input(&mut self, input: Input) {
+        let last = self.turns.back().copied().unwrap_or(self.direction);
+        let turn = match input {
+            Input::Up => (0, -1),
+            Input::Down => (0, 1),
+            Input::Left => (-1, 0),
+            Input::Right => (1, 0),
+            Input::Action => (-last.1, last.0),
+        };
+        // Can't reverse into body
+        if turn != last && turn != (-last.0, -last.1) && self.turns.len() < 2 {
+            self.turns.push_back(turn);
+        }
+    }
+    fn step(&mut self) {
+        if self.over {
+            return;
+        }
+        if let Some(turn) = self.turns.pop_front() {
+            self.direction = turn;
+        }
+        let (w, h) = self.size;
+        let (x, y) = self.head();
+        let head = (
+            (x + self.direction.0).rem_euclid(w),
+            (y + self.direction.1).rem_euclid(h),
+        );
+        let eating = self.food == Some(head);
+        // Tail moves out of the way first
+        if !eating {
+            self.body.pop_back();
+        }
+        if self.body.contains(&head) {
+            self.over = true;
+            return;
+        }
+        self.body.push_front(head);
+        if eating {
+            self.score += 1;
+            self.place_food();
+        }
+    }
+    fn score(&self) -> u32 {
+        self.score
+    }
+    fn is_over(&self) -> bool {
+        self.over
+    }
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let body = GREEN.transform(&[RgbTransform::Intensity(0.3)]);
+        for (i, p) in self.body().enumerate() {
+            canvas.set(p, if i == 0 { GREEN } else { body });
+        }
+        if let Some(food) = self.food {
+            canvas.set(food, RED);
+        }
+    }
+}
+
+// Pong against the computer (player paddle on the left)
+//
+// A point is scored each time the computer misses - the game ends when the
+// player has missed three times. Action serves straight away.
+#[derive(Clone, Debug)]
+pub struct Pong {
+    size: (i32, i32),
+    ball: (i32, i32),
+    velocity: (i32, i32),
+    paddle_h: i32,
+    player: i32, // Paddle top
+    cpu: i32,
+    serving: u32, // Steps until ball moves
+    steps: u32,
+    score: u32,
+    lives: u32,
+    rng: Rng,
+}
+
+const PONG_LIVES: u32 = 3;
+const PONG_SERVE_STEPS: u32 = 8;
+
+impl Pong {
+    pub fn new((w, h): (i32, i32), seed: u32) -> Self {
+        let (w, h) = (w.max(4), h.max(3));
+        let paddle_h = ((h + 1) / 3).max(1);
+        let mut pong = Self {
+            size: (w, h),
+            ball: (0, 0),
+            velocity: (1, 1),
+            paddle_h,
+            player: (h - paddle_h) / 2,
+            cpu: (h - paddle_h) / 2,
+            serving: 0,
+            steps: 0,
+            score: 0,
+            lives: PONG_LIVES,
+            rng: Rng::new(seed),
+        };
+        pong.serve(-1);
+        pong
+    }
+    /// Ball position and velocity
+    pub fn ball(&self) -> ((i32, i32), (i32, i32)) {
+        (self.ball, self.velocity)
+    }
+    /// Paddle tops (player, computer)
+    pub fn paddles(&self) -> (i32, i32) {
+        (self.player, self.cpu)
+    }
+    pub fn paddle_height(&self) -> i32 {
+        self.paddle_h
+    }
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+    pub fn is_serving(&self) -> bool {
+        self.serving > 0
+    }
+    // Ball from centre towards dx
+    fn serve(&mut self, dx: i32) {
+        let (w, h) = self.size;
+        self.ball = (w / 2, h / 2);
+        let dy = if self.rng.next_u32() % 2 == 0 { -1 } else { 1 };
+        self.velocity = (dx, dy);
+        self.serving = PONG_SERVE_STEPS;
+    }
+    // Deflect off paddle - edges send ball away at an angle
+    fn deflect(&mut self, top: i32, y: i32) {
+        self.velocity.0 = -self.velocity.0;
+        if y == top {
+            self.velocity.1 = -1;
+        } else if y == top + self.paddle_h - 1 {
+            self.velocity.1 = 1;
+        }
+    }
+}
+
+impl Game for Pong {
+    fn name(&self) -> &'static str {
+        "pong"
+    }
+    fn step_ms(&self) -> u32 {
+        100_u32.saturating_sub(self.score * 4).max(50)
+    }
+    fn input(&mut self, input: Input) {
+        let max = self.size.1 - self.paddle_h;
+        match input {
+            Input::Up => self.player = (self.player - 1).max(0),
+            Input::Down => self.player = (self.player + 1).min(max),
+            Input::Action => self.serving = self.serving.min(1),
+            Input::Left | Input::Right => {}
+        }
+    }
+    fn step(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        self.steps += 1;
+        let (w, h) = self.size;
+        // Computer only reacts once the ball is over halfway and moves every
+        // other step so it can be beaten
+        if self.velocity.0 > 0 && self.ball.0 >= w / 2 && self.steps % 2 == 0 {
+            let target = (self.ball.1 - self.paddle_h / 2).clamp(0, h - self.paddle_h);
+            self.cpu += (target - self.cpu).signum();
+        }
+        if self.serving > 0 {
+            self.serving -= 1;
+            return;
+        }
+        // Bounce off top and bottom
+        let mut y = self.ball.1 + self.velocity.1;
+        if y < 0 || y >= h {
+            self.velocity.1 = -self.velocity.1;
+            y = self.ball.1 + self.velocity.1;
+        }
+        let x = self.ball.0 + self.velocity.0;
+        let covers = |top: i32| (top..top + self.paddle_h).contains(&y);
+        if x <= 0 {
+            if covers(self.player) {
+                self.deflect(self.player, y);
+            } else {
+                self.lives -= 1;
+                if self.lives > 0 {
+                    self.serve(-1);
+                }
+                return;
+            }
+        } else if x >= w - 1 {
+            if covers(self.cpu) {
+                self.deflect(self.cpu, y);
+            } else {
+                self.score += 1;
+                self.serve(1);
+                return;
+            }
+        }
+        // Ball stays beside paddle on the step it is hit
+        self.ball = (x.clamp(1, w - 2), y.clamp(0, h - 1));
+    }
+    fn score(&self) -> u32 {
+        self.score
+    }
+    fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let (w, _) = self.size;
+        let h = self.paddle_h;
+        canvas.fill_rect((0, self.player), (1, self.player + h), Paint::Solid(BLUE));
+        canvas.fill_rect((w - 1, self.cpu), (w, self.cpu + h), Paint::Solid(RED));
+        // Ball blinks while waiting to serve
+        if self.serving == 0 || self.serving % 2 == 0 {
+            canvas.set(self.ball, WHITE);
+        }
+    }
+}
+
+struct Tetromino {
+    size: i32, // Rotation box
+    cells: [(i32, i32); 4],
+    rgb: Rgb,
+}
+
+const TETROMINOES: [Tetromino; 7] = [
+    tetromino(4, [(0, 1), (1, 1), (2, 1), (3, 1)], Rgb::new(0, 255, 255)), // I
+    tetromino(2, [(0, 0), (1, 0), (0, 1), (1, 1)], Rgb::new(255, 255, 0)), // O
+    tetromino(3, [(1, 0), (0, 1), (1, 1), (2, 1)], Rgb::new(160, 0, 255)), // T
+    tetromino(3, [(1, 0), (2, 0), (0, 1), (1, 1)], Rgb::new(0, 255, 0)),   // S
+    tetromino(3, [(0, 0), (1, 0), (1, 1), (2, 1)], Rgb::new(255, 0, 0)),   // Z
+    tetromino(3, [(0, 0), (0, 1), (1, 1), (2, 1)], Rgb::new(0, 0, 255)),   // J
+    tetromino(3, [(2, 0), (0, 1), (1, 1), (2, 1)], Rgb::new(255, 128, 0)), // L
+];
+
+const fn tetromino(size: i32, cells: [(i32, i32); 4], rgb: Rgb) -> Tetromino {
+    Tetromino { size, cells, rgb }
+}
+// Points for lines cleared at once
+const LINE_SCORES: [u32; 5] = [0, 1, 3, 5, 8];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Piece {
+    kind: usize,
+    cells: [(i32, i32); 4], // Within box
+    position: (i32, i32),   // Box top left (column, row)
+}
+
+impl Piece {
+    fn cells(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let (c, r) = self.position;
+        self.cells.iter().map(move |(x, y)| (c + x, r + y))
+    }
+    fn moved(&self, (dc, dr): (i32, i32)) -> Self {
+        let (c, r) = self.position;
+        Self {
+            position: (c + dc, r + dr),
+            ..*self
+        }
+    }
+    // Clockwise within box
+    fn rotated(&self) -> Self {
+        let n = TETROMINOES[self.kind].size;
+        Self {
+            cells: self.cells.map(|(x, y)| (n - 1 - y, x)),
+            ..*self
+        }
+    }
+}
+
+// Falling blocks
+//
+// On a display wider than it is tall the board is turned on its side with
+// blocks falling to the left (so a 16x8 strip gives an 8 wide board) and
+// inputs turned to match - Up/Down move, Left drops and Right rotates.
+// Action hard drops.
+#[derive(Clone, Debug)]
+pub struct Tetris {
+    size: (i32, i32), // Board (columns, rows)
+    sideways: bool,
+    board: Vec<Option<Rgb>>,
+    piece: Piece,
+    bag: Vec<usize>,
+    lines: u32,
+    score: u32,
+    over: bool,
+    rng: Rng,
+}
+
+impl Tetris {
+    pub fn new((w, h): (i32, i32), seed: u32) -> Self {
+        let sideways = w > h;
+        let (cols, rows) = if sideways { (h, w) } else { (w, h) };
+        let (cols, rows) = (cols.max(4), rows.max(4));
+        let mut tetris = Self {
+            size: (cols, rows),
+            sideways,
+            board: vec![None; (cols * rows) as usize],
+            piece: Piece {
+                kind: 0,
+                cells: TETROMINOES[0].cells,
+                position: (0, 0),
+            },
+            bag: vec![],
+            lines: 0,
+            score: 0,
+            over: false,
+            rng: Rng::new(seed),
+        };
+        tetris.spawn();
+        tetris
+    }
+    /// Board (columns, rows)
+    pub fn board_size(&self) -> (i32, i32) {
+        self.size
+    }
+    pub fn cell(&self, (c, r): (i32, i32)) -> Option<Rgb> {
+        let (cols, rows) = self.size;
+        if (0..cols).contains(&c) && (0..rows).contains(&r) {
+            self.board[(r * cols + c) as usize]
+        } else {
+            None
+        }
+    }
+    /// Set board cell (eg. to set up a game)
+    pub fn set_cell(&mut self, (c, r): (i32, i32), rgb: Option<Rgb>) {
+        let (cols, rows) = self.size;
+        if (0..cols).contains(&c) && (0..rows).contains(&r) {
+            self.board[(r * cols + c) as usize] = rgb;
+        }
+    }
+    /// Falling piece cells (column, row)
+    pub fn piece(&self) -> Vec<(i32, i32)> {
+        self.piece.cells().collect()
+    }
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+    fn fits(&self, piece: &Piece) -> bool {
+        let (cols, rows) = self.size;
+        piece.cells().all(|(c, r)| {
+            (0..cols).contains(&c) && r < rows && (r < 0 || self.cell((c, r)).is_none())
+        })
+    }
+    // Next piece from shuffled bag of all seven
+    fn spawn(&mut self) {
+        if self.bag.is_empty() {
+            self.bag = (0..TETROMINOES.len()).collect();
+            for i in (1..self.bag.len()).rev() {
+                let j = self.rng.next_u32() as usize % (i + 1);
+                self.bag.swap(i, j);
+            }
+        }
+        let kind = self.bag.pop().unwrap_or(0);
+        let tetromino = &TETROMINOES[kind];
+        self.piece = Piece {
+            kind,
+            cells: tetromino.cells,
+            position: ((self.size.0 - tetromino.size) / 2, 0),
+        };
+        if !self.fits(&self.piece) {
+            self.over = true;
+        }
+    }
+    fn shift(&mut self, offset: (i32, i32)) -> bool {
+        let moved = self.piece.moved(offset);
+        let fits = self.fits(&moved);
+        if fits {
+            self.piece = moved;
+        }
+        fits
+    }
+    // Rotate with wall kicks
+    fn rotate(&mut self) {
+        let rotated = self.piece.rotated();
+        for kick in [0, -1, 1, -2, 2] {
+            let kicked = rotated.moved((kick, 0));
+            if self.fits(&kicked) {
+                self.piece = kicked;
+                return;
+            }
+        }
+    }
+    // Fix piece to board, clear full rows and spawn next
+    fn lock(&mut self) {
+        let rgb = TETROMINOES[self.piece.kind].rgb;
+        let cells = self.piece();
+        if cells.iter().any(|(_, r)| *r < 0) {
+            self.over = true;
+            return;
+        }
+        for p in cells {
+            self.set_cell(p, Some(rgb));
+        }
+        let cols = self.size.0 as usize;
+        let rows = self
+            .board
+            .chunks(cols)
+            .filter(|row| row.iter().any(|c| c.is_none()));
+        let mut board = rows.flatten().copied().collect::<Vec<_>>();
+        let cleared = (self.board.len() - board.len()) / cols;
+        board.splice(0..0, vec![None; self.board.len() - board.len()]);
+        self.board = board;
+        self.lines += cleared as u32;
+        self.score += LINE_SCORES[cleared.min(4)];
+        self.spawn();
+    }
+}
+
+impl Game for Tetris {
+    fn name(&self) -> &'static str {
+        "tetris"
+    }
+    fn step_ms(&self) -> u32 {
+        500_u32.saturating_sub(self.lines * 20).max(120)
+    }
+    fn input(&mut self, input: Input) {
+        if self.over {
+            return;
+        }
+        let input = match (self.sideways, input) {
+            (true, Input::Up) => Input::Left,
+            (true, Input::Down) => Input::Right,
+            (true, Input::Left) => Input::Down,
+            (true, Input::Right) => Input::Up,
+            (_, input) => input,
+        };
+        match input {
+            Input::Left => {
+                self.shift((-1, 0));
+            }
+            Input::Right => {
+                self.shift((1, 0));
+            }
+            Input::Up => self.rotate(),
+            Input::Down => {
+                self.shift((0, 1));
+            }
+            Input::Action => {
+                while self.shift((0, 1)) {}
+                self.lock();
+            }
+        }
+    }
+    fn step(&mut self) {
+        if !self.over && !self.shift((0, 1)) {
+            self.lock();
+        }
+    }
+    fn score(&self) -> u32 {
+        self.score
+    }
+    fn is_over(&self) -> bool {
+        self.over
+    }
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let (cols, rows) = self.size;
+        let piece = TETROMINOES[self.piece.kind].rgb;
+        let cells = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (c, r)))
+            .filter_map(|p| self.cell(p).map(|rgb| (p, rgb)))
+            .chain(self.piece.cells().map(|p| (p, piece)));
+        for ((c, r), rgb) in cells {
+            // Sideways boards fall from right to left
+            let p = if self.sideways {
+                (rows - 1 - r, c)
+            } else {
+                (c, r)
+            };
+            canvas.set(p, rgb);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Playing,
+    GameOver, // Final frame flashes
+    Score,    // Score shown until Action restarts
+}
+
+// Runs game at its fixed step rate from display ticks
+pub struct GameRunner {
+    kind: GameKind,
+    size: (i32, i32),
+    seed: u32,
+    game: Box<dyn Game>,
+    inputs: VecDeque<Input>,
+    pending_ms: u32,
+    phase: Phase,
+    phase_ms: u32,
+    best: u32,
+}
+
+impl GameRunner {
+    pub fn new(kind: GameKind, size: (i32, i32), seed: u32) -> Self {
+        Self {
+            kind,
+            size,
+            seed,
+            game: kind.create(size, seed),
+            inputs: VecDeque::new(),
+            pending_ms: 0,
+            phase: Phase::Playing,
+            phase_ms: 0,
+            best: 0,
+        }
+    }
+    pub fn game(&self) -> &dyn Game {
+        self.game.as_ref()
+    }
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+    /// Best score since started
+    pub fn best(&self) -> u32 {
+        self.best
+    }
+    /// Queue input for next tick
+    pub fn input(&mut self, input: Input) {
+        if self.inputs.len() < MAX_QUEUED_INPUTS {
+            self.inputs.push_back(input);
+        }
+    }
+    /// Advance by elapsed_ms - returns true if display needs redrawing
+    pub fn tick(&mut self, elapsed_ms: u32) -> bool {
+        match self.phase {
+            Phase::Playing => {
+                let mut changed = !self.inputs.is_empty();
+                while let Some(input) = self.inputs.pop_front() {
+                    self.game.input(input);
+                }
+                self.pending_ms += elapsed_ms;
+                let mut steps = 0;
+                while self.pending_ms >= self.game.step_ms() && !self.game.is_over() {
+                    if steps == MAX_STEPS_PER_TICK {
+                        self.pending_ms = 0;
+                        break;
+                    }
+                    self.pending_ms -= self.game.step_ms();
+                    self.game.step();
+                    steps += 1;
+                    changed = true;
+                }
+                if self.game.is_over() {
+                    self.best = self.best.max(self.game.score());
+                    self.phase = Phase::GameOver;
+                    self.phase_ms = 0;
+                }
+                changed
+            }
+            Phase::GameOver => {
+                // Ignore presses during animation so a restart isn't accidental
+                self.inputs.clear();
+                let flash = self.phase_ms / FLASH_MS;
+                self.phase_ms += elapsed_ms;
+                if self.phase_ms >= GAME_OVER_MS {
+                    self.phase = Phase::Score;
+                    return true;
+                }
+                self.phase_ms / FLASH_MS != flash
+            }
+            Phase::Score => {
+                if !self.inputs.drain(..).any(|input| input == Input::Action) {
+                    return false;
+                }
+                self.seed = self.seed.wrapping_add(1);
+                self.game = self.kind.create(self.size, self.seed);
+                self.phase = Phase::Playing;
+                self.pending_ms = 0;
+                true
+            }
+        }
+    }
+    pub fn draw(&self, canvas: &mut dyn Canvas) {
+        let (w, h) = canvas.size();
+        canvas.fill_rect((0, 0), (w, h), Paint::Solid(OFF));
+        match self.phase {
+            Phase::Playing => self.game.draw(canvas),
+            Phase::GameOver => {
+                self.game.draw(canvas);
+                // Everything lit flashes red
+                if (self.phase_ms / FLASH_MS) % 2 == 0 {
+                    for y in 0..h {
+                        for x in 0..w {
+                            if canvas.get((x, y)).is_some_and(|rgb| rgb != OFF) {
+                                canvas.set((x, y), RED);
+                            }
+                        }
+                    }
+                }
+            }
+            Phase::Score => {
+                clock::draw_clock(canvas, &self.game.score().to_string(), WHITE, true);
+            }
+        }
+    }
+}
@@ -1,15 +1,42 @@
+use std::hash::Hasher;
+
+// FNV1a 64bit hash
+#[derive(Clone, Copy, Debug)]
+pub struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET: u64 = 14695981039346656037;
+    const PRIME: u64 = 1099511628211;
+    pub fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|b| {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        });
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 // We can only use 15 byte string as NVS key
 pub fn hash_ssid(ssid: &str) -> heapless::String<15> {
-    const FNV_OFFSET: u64 = 14695981039346656037;
-    const FNV_PRIME: u64 = 1099511628211;
     const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
 
-    // FNV1 64bit hash
-    let mut hash = FNV_OFFSET;
-    ssid.as_bytes().iter().for_each(|b| {
-        hash ^= *b as u64;
-        hash = hash.wrapping_mul(FNV_PRIME);
-    });
+    let mut hasher = Fnv1a::new();
+    hasher.write(ssid.as_bytes());
+    let mut hash = hasher.finish();
 
     // Convert to hex - only use bottom 60bits
     let mut buffer: heapless::Vec<u8, 15> = heapless::Vec::new();
@@ -0,0 +1,496 @@
+// Image decoding (PPM/BMP/PNG/GIF) and scaling into sprites
+//
+// Decoders only depend on std/anyhow and the png/gif crates so can be
+// run on the host against sample images (see images/)
+use anyhow::{anyhow, bail, Result};
+
+use crate::draw::Canvas;
+use crate::rgb::Rgb;
+use crate::sprite::{Blit, Sprite};
+
+// Largest image we will try to decode (pixels)
+const MAX_PIXELS: usize = 256 * 256;
+
+// Images built into the firmware (name, encoded image)
+pub const BUILTIN_IMAGES: [(&str, &[u8]); 1] = [("logo", include_bytes!("../../images/logo.png"))];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Bmp,
+    Png,
+    Gif,
+}
+
+impl ImageFormat {
+    /// Detect format from file signature
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [b'P', b'1'..=b'6', ..] => Some(ImageFormat::Ppm),
+            [b'B', b'M', ..] => Some(ImageFormat::Bmp),
+            [0x89, b'P', b'N', b'G', ..] => Some(ImageFormat::Png),
+            [b'G', b'I', b'F', b'8', ..] => Some(ImageFormat::Gif),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Filter {
+    #[default]
+    Nearest,
+    Box, // Average of source pixels covered by each output pixel
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ScaleMode {
+    #[default]
+    Fit, // Scale to fit inside display (keep aspect - transparent borders)
+    Fill,    // Scale to cover display (keep aspect - centre cropped)
+    Crop,    // No scaling (centre cropped/padded)
+    Stretch, // Scale to display size (ignore aspect)
+}
+
+// Reduce colours to the levels the LEDs can show at a given brightness
+//
+// At low brightness many input values map to the same LED output so we
+// scale first and then snap to (at most) levels steps per channel
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quantise {
+    pub brightness: f32, // 0.0..=1.0
+    pub levels: u8,      // Levels per channel (including off)
+}
+
+impl Quantise {
+    pub fn apply(&self, rgb: Rgb) -> Rgb {
+        let max = (self.brightness.clamp(0.0, 1.0) * 255.0).round();
+        // Can't have more steps than distinct output values
+        let steps = (self.levels.max(2) as f32 - 1.0).min(max);
+        let channel = |c: u8| {
+            if steps < 1.0 {
+                0
+            } else {
+                ((c as f32 / 255.0 * steps).round() / steps * max).round() as u8
+            }
+        };
+        Rgb::new(channel(rgb.r()), channel(rgb.g()), channel(rgb.b()))
+    }
+}
+
+/// Encoded data for built-in image
+pub fn builtin(name: &str) -> Option<&'static [u8]> {
+    BUILTIN_IMAGES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, data)| *data)
+}
+
+/// Decode image (format detected from data)
+pub fn decode(data: &[u8]) -> Result<Sprite> {
+    match ImageFormat::detect(data) {
+        Some(ImageFormat::Ppm) => decode_ppm(data),
+        Some(ImageFormat::Bmp) => decode_bmp(data),
+        Some(ImageFormat::Png) => decode_png(data),
+        Some(ImageFormat::Gif) => decode_gif(data),
+        None => bail!("Unknown image format"),
+    }
+}
+
+fn check_size(width: usize, height: usize) -> Result<()> {
+    match width.checked_mul(height) {
+        Some(1..=MAX_PIXELS) => Ok(()),
+        _ => bail!("Image size {}x{} not supported", width, height),
+    }
+}
+
+// Build sprite from packed pixel data (channels per pixel: 1-4 - gray/gray+alpha/rgb/rgba)
+fn from_packed(width: usize, height: usize, channels: usize, data: &[u8]) -> Result<Sprite> {
+    if data.len() < width * height * channels {
+        bail!("Image data truncated");
+    }
+    Ok(Sprite::from_fn((width as i32, height as i32), |(x, y)| {
+        let i = (x as usize + y as usize * width) * channels;
+        let px = &data[i..i + channels];
+        match px {
+            [v] => (Rgb::new(*v, *v, *v), 255),
+            [v, a] => (Rgb::new(*v, *v, *v), *a),
+            [r, g, b] => (Rgb::new(*r, *g, *b), 255),
+            [r, g, b, a, ..] => (Rgb::new(*r, *g, *b), *a),
+            [] => (Rgb::default(), 0),
+        }
+    }))
+}
+
+// Netpbm (P1-P6)
+pub fn decode_ppm(data: &[u8]) -> Result<Sprite> {
+    let mut pos = 0;
+    // Read next whitespace separated header token (skipping comments)
+    let mut token = || -> Result<&[u8]> {
+        loop {
+            match data.get(pos) {
+                Some(b'#') => {
+                    while !matches!(data.get(pos), Some(b'\n') | None) {
+                        pos += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => pos += 1,
+                Some(_) => break,
+                None => bail!("PPM header truncated"),
+            }
+        }
+        let start = pos;
+        while data.get(pos).is_some_and(|c| !c.is_ascii_whitespace()) {
+            pos += 1;
+        }
+        Ok(&data[start..pos])
+    };
+    let number = |t: &[u8]| -> Result<usize> {
+        std::str::from_utf8(t)
+            .ok()
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| anyhow!("PPM invalid number: {:?}", String::from_utf8_lossy(t)))
+    };
+    let magic = token()?.to_vec();
+    let (width, height) = (number(token()?)?, number(token()?)?);
+    let maxval = match magic.as_slice() {
+        b"P1" | b"P4" => 1,
+        _ => number(token()?)?,
+    };
+    check_size(width, height)?;
+    if !(1..=65535).contains(&maxval) {
+        bail!("PPM invalid maxval: {}", maxval);
+    }
+    // Single whitespace char separates header from binary data
+    let body = data.get(pos + 1..).unwrap_or_default();
+    let scale = |v: usize| (v.min(maxval) * 255 / maxval) as u8;
+    let samples: Vec<u8> = match magic.as_slice() {
+        // Plain bitmap digits need not be separated (1 = black)
+        b"P1" => body
+            .iter()
+            .filter(|c| matches!(c, b'0' | b'1'))
+            .map(|&c| if c == b'1' { 0 } else { 255 })
+            .collect(),
+        b"P2" | b"P3" => std::str::from_utf8(body)
+            .map_err(|_| anyhow!("PPM data is not ASCII"))?
+            .split_ascii_whitespace()
+            .map(|t| number(t.as_bytes()).map(scale))
+            .collect::<Result<_>>()?,
+        b"P5" | b"P6" if maxval > 255 => body
+            .chunks_exact(2)
+            .map(|b| scale(u16::from_be_bytes([b[0], b[1]]) as usize))
+            .collect(),
+        b"P5" | b"P6" => body.iter().map(|&b| scale(b as usize)).collect(),
+        b"P4" => {
+            let stride = width.div_ceil(8);
+            (0..width * height)
+                .map(|i| {
+                    let (x, y) = (i % width, i / width);
+                    let byte = body.get(y * stride + x / 8).copied().unwrap_or(0);
+                    if byte & (0x80 >> (x % 8)) != 0 {
+                        0
+                    } else {
+                        255
+                    }
+                })
+                .collect()
+        }
+        _ => bail!("PPM unsupported format"),
+    };
+    let channels = if matches!(magic.as_slice(), b"P3" | b"P6") {
+        3
+    } else {
+        1
+    };
+    from_packed(width, height, channels, &samples)
+}
+
+// Windows BMP (1/4/8 bit palette, 16/24/32 bit RGB, BI_RGB/BI_BITFIELDS)
+pub fn decode_bmp(data: &[u8]) -> Result<Sprite> {
+    let u16_at = |i: usize| -> Result<u16> {
+        data.get(i..i + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or_else(|| anyhow!("BMP header truncated"))
+    };
+    let u32_at = |i: usize| -> Result<u32> {
+        data.get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| anyhow!("BMP header truncated"))
+    };
+    let offset = u32_at(10)? as usize;
+    let header = u32_at(14)? as usize;
+    let (width, height, bpp, compression, colours) = if header == 12 {
+        // OS/2 BITMAPCOREHEADER
+        let (w, h, bpp) = (u16_at(18)?, u16_at(20)?, u16_at(24)?);
+        (w as i32, h as i32, bpp, 0, 0)
+    } else {
+        (
+            u32_at(18)? as i32,
+            u32_at(22)? as i32,
+            u16_at(28)?,
+            u32_at(30)?,
+            u32_at(46)? as usize,
+        )
+    };
+    // Negative height is top-down
+    let (top_down, w, h) = (
+        height < 0,
+        width.unsigned_abs() as usize,
+        height.unsigned_abs() as usize,
+    );
+    check_size(w, h)?;
+    let masks = match (compression, bpp) {
+        (0, 16) => [0x7c00, 0x03e0, 0x001f, 0],
+        (0, 32) => [0xff0000, 0x00ff00, 0x0000ff, 0],
+        (3, 16 | 32) => [
+            u32_at(54)?,
+            u32_at(58)?,
+            u32_at(62)?,
+            if header >= 56 { u32_at(66)? } else { 0 },
+        ],
+        (0, 1 | 4 | 8 | 24) => [0; 4],
+        _ => bail!(
+            "BMP unsupported format ({} bpp, compression {})",
+            bpp,
+            compression
+        ),
+    };
+    // Palette follows header (BGR for OS/2, BGRx otherwise)
+    let entry = if header == 12 { 3 } else { 4 };
+    let palette: Vec<Rgb> = if bpp <= 8 {
+        let n = if colours == 0 {
+            1 << bpp
+        } else {
+            colours.min(256)
+        };
+        14_usize
+            .checked_add(header)
+            .and_then(|start| data.get(start..)?.get(..n * entry))
+            .ok_or_else(|| anyhow!("BMP palette truncated"))?
+            .chunks(entry)
+            .map(|c| Rgb::new(c[2], c[1], c[0]))
+            .collect()
+    } else {
+        vec![]
+    };
+    let channel = |px: u32, mask: u32| -> u8 {
+        let bits = mask.count_ones();
+        if bits == 0 {
+            0
+        } else {
+            let max = ((1_u64 << bits) - 1) as u32;
+            (((px & mask) >> mask.trailing_zeros()) * 255 / max) as u8
+        }
+    };
+    let stride = (bpp as usize * w).div_ceil(32) * 4;
+    // Header values are untrusted (and usize is 32 bits on the device)
+    let end = stride.checked_mul(h).and_then(|n| n.checked_add(offset));
+    if end.filter(|&end| end <= data.len()).is_none() {
+        bail!("BMP pixel data truncated");
+    }
+    Ok(Sprite::from_fn((w as i32, h as i32), |(x, y)| {
+        let (x, y) = (x as usize, y as usize);
+        let row = if top_down { y } else { h - 1 - y };
+        let line = &data[offset + row * stride..offset + (row + 1) * stride];
+        let index = |bits: usize| {
+            let bit = x * bits;
+            (line[bit / 8] >> (8 - bits - bit % 8)) as usize & ((1 << bits) - 1)
+        };
+        match bpp {
+            1 | 4 | 8 => (
+                palette
+                    .get(index(bpp as usize))
+                    .copied()
+                    .unwrap_or_default(),
+                255,
+            ),
+            24 => (Rgb::new(line[x * 3 + 2], line[x * 3 + 1], line[x * 3]), 255),
+            _ => {
+                let px = if bpp == 16 {
+                    u16::from_le_bytes([line[x * 2], line[x * 2 + 1]]) as u32
+                } else {
+                    u32::from_le_bytes([
+                        line[x * 4],
+                        line[x * 4 + 1],
+                        line[x * 4 + 2],
+                        line[x * 4 + 3],
+                    ])
+                };
+                let alpha = if masks[3] == 0 {
+                    255
+                } else {
+                    channel(px, masks[3])
+                };
+                (
+                    Rgb::new(
+                        channel(px, masks[0]),
+                        channel(px, masks[1]),
+                        channel(px, masks[2]),
+                    ),
+                    alpha,
+                )
+            }
+        }
+    }))
+}
+
+pub fn decode_png(data: &[u8]) -> Result<Sprite> {
+    let mut decoder = png::Decoder::new(data);
+    // Expand palette/low bit depths and strip 16 bit to 8 bit samples
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+    let info = reader.info();
+    check_size(info.width as usize, info.height as usize)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buf)?;
+    let channels = match frame.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => bail!("PNG palette not expanded"),
+    };
+    let (w, h) = (frame.width as usize, frame.height as usize);
+    // Remove any row padding
+    let packed: Vec<u8> = buf
+        .chunks(frame.line_size)
+        .take(h)
+        .flat_map(|row| &row[..w * channels])
+        .copied()
+        .collect();
+    from_packed(w, h, channels, &packed)
+}
+
+/// Decode first frame of GIF (frame is placed on the logical screen)
+pub fn decode_gif(data: &[u8]) -> Result<Sprite> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(data)?;
+    let (w, h) = (decoder.width() as usize, decoder.height() as usize);
+    check_size(w, h)?;
+    let frame = decoder
+        .read_next_frame()?
+        .ok_or_else(|| anyhow!("GIF has no frames"))?;
+    let (left, top) = (frame.left as usize, frame.top as usize);
+    let (fw, fh) = (frame.width as usize, frame.height as usize);
+    Ok(Sprite::from_fn((w as i32, h as i32), |(x, y)| {
+        let (x, y) = (x as usize, y as usize);
+        if (left..left + fw).contains(&x) && (top..top + fh).contains(&y) {
+            let i = ((x - left) + (y - top) * fw) * 4;
+            match frame.buffer.get(i..i + 4) {
+                Some(&[r, g, b, a]) => (Rgb::new(r, g, b), a),
+                _ => (Rgb::default(), 0),
+            }
+        } else {
+            (Rgb::default(), 0)
+        }
+    }))
+}
+
+/// Resize image to (width, height)
+pub fn resize(image: &Sprite, (width, height): (i32, i32), filter: Filter) -> Sprite {
+    let (sw, sh) = image.size();
+    if sw == 0 || sh == 0 {
+        return Sprite::from_fn((width, height), |_| (Rgb::default(), 0));
+    }
+    let (fx, fy) = (sw as f32 / width as f32, sh as f32 / height as f32);
+    Sprite::from_fn((width, height), |(x, y)| match filter {
+        Filter::Nearest => {
+            let sx = ((x as f32 + 0.5) * fx) as i32;
+            let sy = ((y as f32 + 0.5) * fy) as i32;
+            image
+                .pixel((sx.min(sw - 1), sy.min(sh - 1)))
+                .unwrap_or_default()
+        }
+        Filter::Box => {
+            // Source pixels covered by output pixel (at least one)
+            let x1 = (x as f32 * fx) as i32;
+            let x2 = (((x + 1) as f32 * fx).ceil() as i32).clamp(x1 + 1, sw);
+            let y1 = (y as f32 * fy) as i32;
+            let y2 = (((y + 1) as f32 * fy).ceil() as i32).clamp(y1 + 1, sh);
+            // Alpha weighted average so transparent pixels don't darken edges
+            let (mut r, mut g, mut b, mut a, mut n) = (0_u32, 0_u32, 0_u32, 0_u32, 0_u32);
+            for sy in y1..y2 {
+                for sx in x1..x2 {
+                    if let Some((rgb, alpha)) = image.pixel((sx, sy)) {
+                        let alpha = alpha as u32;
+                        r += rgb.r() as u32 * alpha;
+                        g += rgb.g() as u32 * alpha;
+                        b += rgb.b() as u32 * alpha;
+                        a += alpha;
+                        n += 1;
+                    }
+                }
+            }
+            match a {
+                0 => (Rgb::default(), 0),
+                a => (
+                    Rgb::new((r / a) as u8, (g / a) as u8, (b / a) as u8),
+                    (a / n.max(1)) as u8,
+                ),
+            }
+        }
+    })
+}
+
+/// Scale image to exactly (width, height) using scale mode
+pub fn scale_to(
+    image: &Sprite,
+    (width, height): (i32, i32),
+    mode: ScaleMode,
+    filter: Filter,
+) -> Sprite {
+    let (sw, sh) = image.size();
+    let (w, h) = match mode {
+        ScaleMode::Stretch => (width, height),
+        ScaleMode::Crop => (sw, sh),
+        ScaleMode::Fit | ScaleMode::Fill => {
+            let (ax, ay) = (
+                width as f32 / sw.max(1) as f32,
+                height as f32 / sh.max(1) as f32,
+            );
+            let scale = if mode == ScaleMode::Fit {
+                ax.min(ay)
+            } else {
+                ax.max(ay)
+            };
+            (
+                ((sw as f32 * scale).round() as i32).max(1),
+                ((sh as f32 * scale).round() as i32).max(1),
+            )
+        }
+    };
+    let scaled = if (w, h) == (sw, sh) {
+        image.clone()
+    } else {
+        resize(image, (w, h), filter)
+    };
+    // Centre on output (crops or pads with transparent pixels)
+    let (dx, dy) = ((w - width) / 2, (h - height) / 2);
+    Sprite::from_fn((width, height), |(x, y)| {
+        scaled.pixel((x + dx, y + dy)).unwrap_or_default()
+    })
+}
+
+/// Apply quantisation to every pixel
+pub fn quantise(image: &Sprite, q: Quantise) -> Sprite {
+    Sprite::from_fn(image.size(), |p| {
+        let (rgb, alpha) = image.pixel(p).unwrap_or_default();
+        (q.apply(rgb), alpha)
+    })
+}
+
+/// Scale image to canvas and draw (transparent pixels are blended with canvas)
+pub fn draw_image(
+    canvas: &mut (impl Canvas + ?Sized),
+    image: &Sprite,
+    mode: ScaleMode,
+    filter: Filter,
+    q: Option<Quantise>,
+) {
+    let scaled = scale_to(image, canvas.size(), mode, filter);
+    let scaled = match q {
+        Some(q) => quantise(&scaled, q),
+        None => scaled,
+    };
+    scaled.blit(canvas, (0, 0), Blit::default());
+}
@@ -0,0 +1,133 @@
+use crate::matrix_1d::{Matrix1D, HEIGHT, WIDTH};
+use crate::rgb::{BlendMode, Rgb, OFF};
+
+pub type LayerId = usize;
+
+// Framebuffer with compositing options
+//
+// Layers are opaque unless a key colour is set - pixels matching the key
+// (eg. Some(OFF)) are transparent so text and shapes can be drawn over
+// lower layers with the usual Matrix1D methods
+#[derive(Clone, Debug)]
+pub struct Layer<const N: usize> {
+    pub matrix: Matrix1D<N>,
+    z: i32,               // Layers are drawn lowest z first (see LayerStack::set_z)
+    pub opacity: f32,     // 0.0..=1.0
+    pub blend: BlendMode, // Applied to pixels below
+    pub visible: bool,
+    pub offset: (i32, i32), // Position of layer on display
+    pub key: Option<Rgb>,   // Transparent colour (None for opaque layer)
+}
+
+impl<const N: usize> Layer<N> {
+    pub fn new(z: i32) -> Self {
+        Self {
+            matrix: Matrix1D::new(),
+            z,
+            opacity: 1.0,
+            blend: BlendMode::Normal,
+            visible: true,
+            offset: (0, 0),
+            key: None,
+        }
+    }
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+    // Layer pixel at display position (None if transparent)
+    fn pixel(&self, (x, y): (i32, i32)) -> Option<Rgb> {
+        self.matrix
+            .get((x - self.offset.0, y - self.offset.1))
+            .filter(|rgb| Some(*rgb) != self.key)
+    }
+}
+
+impl<const N: usize> Default for Layer<N> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LayerStack<const N: usize> {
+    layers: Vec<(LayerId, Layer<N>)>, // Kept in z order
+    next_id: LayerId,
+}
+
+impl<const N: usize> Default for LayerStack<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> LayerStack<N> {
+    pub fn new() -> Self {
+        Self {
+            layers: vec![],
+            next_id: 0,
+        }
+    }
+    pub fn push(&mut self, layer: Layer<N>) -> LayerId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.insert(id, layer);
+        id
+    }
+    // Insert after layers with same z (so they keep insertion order)
+    fn insert(&mut self, id: LayerId, layer: Layer<N>) {
+        let index = self.layers.partition_point(|(_, l)| l.z <= layer.z);
+        self.layers.insert(index, (id, layer));
+    }
+    /// Move layer to new z (drawn after existing layers with the same z)
+    pub fn set_z(&mut self, id: LayerId, z: i32) {
+        if let Some(mut layer) = self.remove(id) {
+            layer.z = z;
+            self.insert(id, layer);
+        }
+    }
+    pub fn get(&self, id: LayerId) -> Option<&Layer<N>> {
+        self.layers.iter().find(|(i, _)| *i == id).map(|(_, l)| l)
+    }
+    pub fn get_mut(&mut self, id: LayerId) -> Option<&mut Layer<N>> {
+        self.layers
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+            .map(|(_, l)| l)
+    }
+    pub fn remove(&mut self, id: LayerId) -> Option<Layer<N>> {
+        let index = self.layers.iter().position(|(i, _)| *i == id)?;
+        Some(self.layers.remove(index).1)
+    }
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+    /// Layers in z order (layers with same z are kept in insertion order)
+    pub fn iter(&self) -> impl Iterator<Item = (LayerId, &Layer<N>)> {
+        self.layers.iter().map(|(i, l)| (*i, l))
+    }
+    /// Composite visible layers into out (over black)
+    ///
+    /// Each pixel is composited before being set so unchanged pixels aren't
+    /// marked dirty in out
+    pub fn flatten(&self, out: &mut Matrix1D<N>) {
+        for y in 0..HEIGHT as i32 {
+            for x in 0..(N * WIDTH) as i32 {
+                let rgb = self
+                    .layers
+                    .iter()
+                    .filter(|(_, layer)| layer.visible && layer.opacity > 0.0)
+                    .fold(OFF, |dst, (_, layer)| match layer.pixel((x, y)) {
+                        Some(src) => dst.lerp(dst.blend(src, layer.blend), layer.opacity),
+                        None => dst,
+                    });
+                out.set((x, y), rgb);
+            }
+        }
+    }
+}
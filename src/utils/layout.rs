@@ -0,0 +1,112 @@
+use std::iter;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageMode {
+    Flip,          // Jump to next page
+    Scroll(usize), // Scroll up to next page (ticks per row)
+}
+
+// Text wrapped into lines and split into pages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextLayout {
+    pub lines: Vec<String>,
+    pub line_height: i32,
+    pub lines_per_page: usize,
+}
+
+// Split word that is too wide for line into chunks that fit (at grapheme boundaries)
+fn break_word(word: &str, width: i32, measure: &impl Fn(&str) -> i32) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    for g in word.graphemes(true) {
+        let candidate = format!("{}{}", chunk, g);
+        if measure(&candidate) > width && !chunk.is_empty() {
+            chunks.push(chunk);
+            chunk = g.to_string();
+        } else {
+            chunk = candidate;
+        }
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+impl TextLayout {
+    // Wrap text at word boundaries to fit (width, height) display
+    //
+    // measure returns width of string in pixels and line_height is
+    // usually font.height()
+    pub fn new(
+        s: &str,
+        (width, height): (i32, i32),
+        line_height: i32,
+        measure: impl Fn(&str) -> i32,
+    ) -> Self {
+        let mut lines = vec![];
+        for para in s.split('\n') {
+            let mut line = String::new();
+            for word in para.split_whitespace() {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+                if measure(&candidate) <= width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let mut chunks = break_word(word, width, &measure);
+                // Last chunk can be continued by the next word
+                line = chunks.pop().unwrap_or_default();
+                lines.extend(chunks);
+            }
+            // Keep blank lines from the text
+            if !line.is_empty() || para.trim().is_empty() {
+                lines.push(line);
+            }
+        }
+        let line_height = line_height.max(1);
+        Self {
+            lines,
+            line_height,
+            lines_per_page: (height / line_height).max(1) as usize,
+        }
+    }
+    pub fn pages(&self) -> usize {
+        self.lines.len().div_ceil(self.lines_per_page).max(1)
+    }
+    pub fn page(&self, n: usize) -> &[String] {
+        let start = (n * self.lines_per_page).min(self.lines.len());
+        let end = (start + self.lines_per_page).min(self.lines.len());
+        &self.lines[start..end]
+    }
+    pub fn page_height(&self) -> i32 {
+        self.lines_per_page as i32 * self.line_height
+    }
+    // Returns iterator with vertical offset (in pixels from first line) for
+    // each tick - each page is shown for hold ticks
+    //
+    // The last page doesn't scroll (there is nothing below it) so the
+    // iterator ends there and the caller restarts from the first page
+    pub fn page_offsets(&self, mode: PageMode, hold: usize) -> impl Iterator<Item = i32> {
+        let ph = self.page_height();
+        let last = self.pages() as i32 - 1;
+        (0..=last).flat_map(move |p| {
+            let rows = match mode {
+                PageMode::Scroll(_) if p < last => 1..ph,
+                _ => 0..0,
+            };
+            let rate = match mode {
+                PageMode::Flip => 0,
+                PageMode::Scroll(rate) => rate.max(1),
+            };
+            iter::repeat(p * ph)
+                .take(hold.max(1))
+                .chain(rows.flat_map(move |r| iter::repeat(p * ph + r).take(rate)))
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use font8x8::{UnicodeFonts, BASIC_FONTS};
 
 use crate::rgb::Rgb;
+use crate::sprite::{Blit, Sprite};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Orientation {
@@ -10,8 +11,8 @@ pub enum Orientation {
     West,
 }
 
-const WIDTH: usize = 8;
-const HEIGHT: usize = 8;
+pub const WIDTH: usize = 8;
+pub const HEIGHT: usize = 8;
 
 #[derive(Clone, Debug)]
 pub struct Matrix {
@@ -27,9 +28,7 @@ impl Matrix {
         }
     }
     pub fn fill(&mut self, c: Rgb) {
-        for i in 0..(WIDTH * HEIGHT) {
-            self.leds[i] = c;
-        }
+        self.leds.fill(c);
     }
     pub fn draw_glyph(&mut self, glyph: [u8; 8], colour: Rgb, offset: i8) {
         for (y, row) in glyph.into_iter().enumerate() {
@@ -52,20 +51,10 @@ impl Matrix {
             }
         }
     }
-    pub fn draw_bitmap(&mut self, bitmap: &[&str; 8], colourmap: &[(char, Rgb)], _offset: i8) {
-        // Glyph is in MSB-LSB format (opposite to char)
-        for (y, &row) in bitmap.iter().enumerate() {
-            for (x, c) in row.chars().take(8).enumerate() {
-                let mut colour = Rgb::new(0, 0, 0);
-                for (key, rgb) in colourmap {
-                    if c == *key {
-                        colour = *rgb;
-                        break;
-                    }
-                }
-                self.leds[x + y * HEIGHT] = colour;
-            }
-        }
+    // Draw char-art bitmap shifted offset columns right - chars not in
+    // colourmap are left unchanged
+    pub fn draw_bitmap(&mut self, bitmap: &[&str], colourmap: &[(char, Rgb)], offset: i8) {
+        Sprite::from_char_art(bitmap, colourmap).blit(self, (offset as i32, 0), Blit::default());
     }
     pub fn set_orientation(&mut self, orientation: Orientation) {
         self.orientation = orientation;
@@ -73,10 +62,10 @@ impl Matrix {
     pub fn set(&mut self, (x, y): (usize, usize), c: Rgb) {
         self.leds[x + y * WIDTH] = c;
     }
-    pub fn get(&mut self, (x, y): (usize, usize)) -> Rgb {
+    pub fn get(&self, (x, y): (usize, usize)) -> Rgb {
         self.leds[x + y * WIDTH]
     }
-    pub fn iter(&self) -> MatrixIterator {
+    pub fn iter(&self) -> MatrixIterator<'_> {
         MatrixIterator {
             leds: &self.leds,
             index: 0,
@@ -1,8 +1,13 @@
-use font8x8::{UnicodeFonts, BASIC_FONTS};
+use std::hash::{Hash, Hasher};
 use std::iter::Rev;
 use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::font::{Font, Glyph};
+use crate::hash::Fnv1a;
+use crate::layout::TextLayout;
 use crate::rgb::{Rgb, RgbTransform, OFF};
+use crate::scroll::{ScrollIter, ScrollOptions};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum Orientation {
@@ -16,7 +21,34 @@ pub enum Orientation {
 pub const WIDTH: usize = 8;
 pub const HEIGHT: usize = 8;
 pub const PANEL_PIXELS: usize = WIDTH * HEIGHT;
-pub const CHAR_WIDTH: usize = 8;
+
+// Rectangle ((x1, y1), (x2, y2)) - end exclusive
+pub type Region = ((i32, i32), (i32, i32));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextStyle {
+    pub proportional: bool, // Trim glyphs to their inked columns
+    pub spacing: i32,       // Extra pixels between characters
+    pub space_width: i32,   // Advance for blank glyphs when proportional
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            proportional: false,
+            spacing: 0,
+            space_width: 3,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Panel {
@@ -33,8 +65,18 @@ impl Panel {
         self.orientation = orientation;
     }
     pub fn clear(&mut self) {
-        for j in 0..PANEL_PIXELS {
-            self.leds[j] = OFF;
+        self.leds.fill(OFF);
+    }
+    pub fn set(&mut self, (x, y): (i32, i32), rgb: Rgb) {
+        if (0..WIDTH as i32).contains(&x) && (0..HEIGHT as i32).contains(&y) {
+            self.leds[x as usize + y as usize * WIDTH] = rgb;
+        }
+    }
+    pub fn get(&self, (x, y): (i32, i32)) -> Option<Rgb> {
+        if (0..WIDTH as i32).contains(&x) && (0..HEIGHT as i32).contains(&y) {
+            Some(self.leds[x as usize + y as usize * WIDTH])
+        } else {
+            None
         }
     }
     pub fn iter(&self) -> PanelIterator<'_> {
@@ -90,6 +132,9 @@ impl Default for Panel {
 #[derive(Clone, Debug)]
 pub struct Matrix1D<const N: usize> {
     panels: [Panel; N],
+    style: TextStyle,
+    dirty: Option<Region>,
+    smooth_mask: Vec<bool>, // Reused by draw_str_smooth so scrolling doesn't allocate
 }
 
 impl<const N: usize> Default for Matrix1D<N> {
@@ -101,63 +146,235 @@ impl<const N: usize> Default for Matrix1D<N> {
 impl<const N: usize> Matrix1D<N> {
     pub fn new() -> Self {
         let panels = std::array::from_fn(|_| Panel::default());
-        Self { panels }
+        Self {
+            panels,
+            style: TextStyle::default(),
+            dirty: None,
+            smooth_mask: Vec::new(),
+        }
     }
     pub fn from_panels(panels: [Panel; N]) -> Self {
-        Self { panels }
+        Self {
+            panels,
+            style: TextStyle::default(),
+            dirty: None,
+            smooth_mask: Vec::new(),
+        }
+    }
+    pub fn set_text_style(&mut self, style: TextStyle) {
+        self.style = style;
+    }
+    pub fn text_style(&self) -> TextStyle {
+        self.style
     }
     pub fn clear(&mut self) {
-        (0..N).for_each(|i| self.panels[i].clear())
+        for (i, panel) in self.panels.iter_mut().enumerate() {
+            if panel.leds.iter().any(|rgb| *rgb != OFF) {
+                let x = (i * WIDTH) as i32;
+                Self::mark_dirty(&mut self.dirty, (x, 0), (x + WIDTH as i32, HEIGHT as i32));
+                panel.clear();
+            }
+        }
     }
     // Pass (x,y) as i32 to handle transformations more easily
     pub fn set(&mut self, (x, y): (i32, i32), rgb: Rgb) {
+        if (0..HEIGHT as i32).contains(&y) && (0..(N * WIDTH) as i32).contains(&x) {
+            let (px, py) = (x as usize, y as usize);
+            let (i, px) = (px / WIDTH, px % WIDTH);
+            if self.panels[i].leds[px + py * WIDTH] != rgb {
+                self.panels[i].leds[px + py * WIDTH] = rgb;
+                Self::mark_dirty(&mut self.dirty, (x, y), (x + 1, y + 1));
+            }
+        }
+    }
+    // Extend dirty region to include (x1, y1)..(x2, y2)
+    fn mark_dirty(dirty: &mut Option<Region>, (x1, y1): (i32, i32), (x2, y2): (i32, i32)) {
+        *dirty = Some(match *dirty {
+            Some(((dx1, dy1), (dx2, dy2))) => {
+                ((dx1.min(x1), dy1.min(y1)), (dx2.max(x2), dy2.max(y2)))
+            }
+            None => ((x1, y1), (x2, y2)),
+        });
+    }
+    /// Bounding box of pixels changed since last take_dirty (end exclusive)
+    pub fn dirty(&self) -> Option<Region> {
+        self.dirty
+    }
+    /// Return and reset dirty region
+    pub fn take_dirty(&mut self) -> Option<Region> {
+        self.dirty.take()
+    }
+    /// Hash of display contents (to detect identical frames)
+    pub fn frame_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        self.panels.iter().for_each(|p| p.leds.hash(&mut hasher));
+        hasher.finish()
+    }
+    pub fn get(&self, (x, y): (i32, i32)) -> Option<Rgb> {
         if (0..HEIGHT as i32).contains(&y) && (0..(N * WIDTH) as i32).contains(&x) {
             let (x, y) = (x as usize, y as usize);
             let (i, x) = (x / WIDTH, x % WIDTH);
-            self.panels[i].leds[x + y * WIDTH] = rgb;
+            Some(self.panels[i].leds[x + y * WIDTH])
+        } else {
+            None
         }
     }
     pub fn transform(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32), t: &[RgbTransform]) {
         for x in x1..x2 {
             for y in y1..y2 {
-                if (0..HEIGHT as i32).contains(&y) && (0..(N * WIDTH) as i32).contains(&x) {
-                    let (x, y) = (x as usize, y as usize);
-                    let (i, x) = (x / WIDTH, x % WIDTH);
-                    let rgb = self.panels[i].leds[x + y * WIDTH];
-                    self.panels[i].leds[x + y * WIDTH] = rgb.transform(t);
+                if let Some(rgb) = self.get((x, y)) {
+                    self.set((x, y), rgb.transform(t));
                 }
             }
         }
     }
-    pub fn draw_char(&mut self, c: char, rgb: Rgb, (x1, y1): (i32, i32)) {
-        if let Some(glyph) = BASIC_FONTS.get(c) {
-            for (y, row) in glyph.into_iter().enumerate() {
-                for x in 0..8 {
-                    if row & (1 << x) != 0 {
-                        self.set((x1 + x, y1 + y as i32), rgb)
-                    }
+    // Glyph for grapheme cluster - combining marks are dropped as there is
+    // no room for them in the glyph cell
+    fn glyph(g: &str, font: &dyn Font) -> Option<Glyph> {
+        g.chars().next().and_then(|c| font.glyph(c))
+    }
+    fn draw_glyph(&mut self, glyph: &Glyph, rgb: Rgb, (x1, y1): (i32, i32)) {
+        for y in 0..glyph.height {
+            for x in 0..glyph.width {
+                if glyph.pixel(x, y) {
+                    self.set((x1 + x, y1 + y), rgb)
                 }
             }
         }
     }
-    pub fn draw_str(&mut self, s: &str, rgb: Rgb, (x1, y1): (i32, i32)) {
-        for (i, c) in s.chars().enumerate() {
-            if let Some(glyph) = BASIC_FONTS.get(c) {
-                for (y, row) in glyph.into_iter().enumerate() {
-                    for x in 0..8 {
-                        if row & (1 << x) != 0 {
-                            self.set((x1 + x + (i * CHAR_WIDTH) as i32, y1 + y as i32), rgb)
+    pub fn draw_char(&mut self, c: char, font: &dyn Font, rgb: Rgb, (x1, y1): (i32, i32)) {
+        if let Some(glyph) = font.glyph(c) {
+            self.draw_glyph(&glyph, rgb, (x1, y1));
+        }
+    }
+    // Returns (column offset, advance) for glyph using current text style
+    fn glyph_metrics(&self, glyph: Option<&Glyph>) -> (i32, i32) {
+        match glyph {
+            Some(glyph) if self.style.proportional => match glyph.columns() {
+                Some((first, width)) => (first, width + self.style.spacing),
+                None => (0, self.style.space_width + self.style.spacing),
+            },
+            Some(glyph) => (0, glyph.width + self.style.spacing),
+            None => (0, self.style.space_width + self.style.spacing),
+        }
+    }
+    pub fn draw_str(&mut self, s: &str, font: &dyn Font, rgb: Rgb, (x1, y1): (i32, i32)) {
+        let mut x = x1;
+        for g in s.graphemes(true) {
+            let glyph = Self::glyph(g, font);
+            let (first, advance) = self.glyph_metrics(glyph.as_ref());
+            if let Some(glyph) = glyph {
+                self.draw_glyph(&glyph, rgb, (x - first, y1));
+            }
+            x += advance;
+        }
+    }
+    // Draw string at fractional position - each text pixel is split between
+    // neighbouring LEDs by the fractional offset so slow scrolling is smooth
+    pub fn draw_str_smooth(&mut self, s: &str, font: &dyn Font, rgb: Rgb, (x1, y1): (f32, f32)) {
+        let (w, h) = ((N * WIDTH) as i32, HEIGHT as i32);
+        let (ix, iy) = (x1.floor() as i32, y1.floor() as i32);
+        let (fx, fy) = (x1 - ix as f32, y1 - iy as f32);
+        // Text mask at integer position with one pixel border at top/left
+        // (pixels just off display spill onto it)
+        let mut mask = std::mem::take(&mut self.smooth_mask);
+        mask.clear();
+        mask.resize(((w + 1) * (h + 1)) as usize, false);
+        let mut x = ix;
+        for g in s.graphemes(true) {
+            let glyph = Self::glyph(g, font);
+            let (first, advance) = self.glyph_metrics(glyph.as_ref());
+            if let Some(glyph) = glyph {
+                for gy in 0..glyph.height {
+                    for gx in 0..glyph.width {
+                        let (mx, my) = (x - first + gx + 1, iy + gy + 1);
+                        if glyph.pixel(gx, gy) && (0..=w).contains(&mx) && (0..=h).contains(&my) {
+                            mask[(mx + my * (w + 1)) as usize] = true;
                         }
                     }
                 }
             }
+            x += advance;
+            if x > w {
+                break;
+            }
+        }
+        let covered = |x: i32, y: i32| mask[(x + y * (w + 1)) as usize] as u8 as f32;
+        for y in 0..h {
+            for x in 0..w {
+                // Pixel (x, y) gets (1 - f) of mask pixel at same position and
+                // f of the one before
+                let (mx, my) = (x + 1, y + 1);
+                let top = covered(mx, my) * (1.0 - fx) + covered(mx - 1, my) * fx;
+                let above = covered(mx, my - 1) * (1.0 - fx) + covered(mx - 1, my - 1) * fx;
+                let coverage = top * (1.0 - fy) + above * fy;
+                if coverage > 0.0 {
+                    if let Some(dst) = self.get((x, y)) {
+                        self.set((x, y), dst.lerp(rgb, coverage));
+                    }
+                }
+            }
         }
+        self.smooth_mask = mask;
     }
-    // Returns iterator with x co-ordinates to scroll string of length len
-    pub fn scroll_iter(&self, len: usize) -> Rev<Range<i32>> {
-        let width = (len * CHAR_WIDTH) as i32;
+    // Draw string aligned within the display width
+    pub fn draw_str_aligned(&mut self, s: &str, font: &dyn Font, rgb: Rgb, y: i32, align: Align) {
+        let (width, display) = (self.measure_str(s, font), (N * WIDTH) as i32);
+        let x = match align {
+            Align::Left => 0,
+            Align::Center => (display - width) / 2,
+            Align::Right => display - width,
+        };
+        self.draw_str(s, font, rgb, (x, y));
+    }
+    // Returns width of string in pixels (excluding trailing letter spacing)
+    pub fn measure_str(&self, s: &str, font: &dyn Font) -> i32 {
+        if s.is_empty() {
+            0
+        } else {
+            s.graphemes(true)
+                .map(|g| self.glyph_metrics(Self::glyph(g, font).as_ref()).1)
+                .sum::<i32>()
+                - self.style.spacing
+        }
+    }
+    // Wrap text into lines/pages that fit the display
+    pub fn layout(&self, s: &str, font: &dyn Font) -> TextLayout {
+        TextLayout::new(
+            s,
+            ((N * WIDTH) as i32, HEIGHT as i32),
+            font.height(),
+            |line| self.measure_str(line, font),
+        )
+    }
+    // Draw lines starting at (x1, y1) - line height taken from font
+    pub fn draw_lines(
+        &mut self,
+        lines: &[String],
+        font: &dyn Font,
+        rgb: Rgb,
+        (x1, y1): (i32, i32),
+    ) {
+        for (i, line) in lines.iter().enumerate() {
+            let y = y1 + i as i32 * font.height();
+            if y < HEIGHT as i32 && y + font.height() > 0 {
+                self.draw_str(line, font, rgb, (x1, y));
+            }
+        }
+    }
+    // Returns iterator with x co-ordinates to scroll string across display
+    pub fn scroll_iter(&self, s: &str, font: &dyn Font) -> Rev<Range<i32>> {
+        let width = self.measure_str(s, font);
         (-width..(N * WIDTH) as i32).rev()
     }
+    // Returns iterator with (x, y) co-ordinates to scroll string in any direction
+    pub fn scroll_positions(&self, s: &str, font: &dyn Font, options: ScrollOptions) -> ScrollIter {
+        ScrollIter::new(
+            options,
+            ((N * WIDTH) as i32, HEIGHT as i32),
+            (self.measure_str(s, font), font.height()),
+        )
+    }
     pub fn iter(&mut self) -> Matrix1DIterator<'_, N> {
         Matrix1DIterator {
             panels: &mut self.panels,
@@ -1,29 +1,587 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, bail, Error, Result};
 use esp_idf_hal::rmt::{config::TransmitConfig, TxRmtDriver};
-use std::iter::Rev;
-use std::ops::Range;
+use std::collections::VecDeque;
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
-use crate::matrix_1d::{Matrix1D, Panel, PANEL_PIXELS};
-use crate::rgb::{Rgb, RgbLayout};
+use crate::animation::{AnimationSource, Player};
+use crate::chart::{Chart, Samples};
+use crate::clock::{self, ClockOptions, DateTime};
+use crate::draw::Canvas;
+use crate::effect::{Effect, EffectParams, EffectRegistry};
+use crate::font::FONT_8X8;
+use crate::game::{GameKind, GameRunner, Input};
+use crate::image::{self, Filter, ScaleMode};
+use crate::layer::{Layer, LayerId, LayerStack};
+use crate::layout::{PageMode, TextLayout};
+use crate::matrix_1d::{Matrix1D, Panel, TextStyle, PANEL_PIXELS};
+use crate::pattern::Pattern;
+use crate::playlist::{Entry, Playlist, PlaylistPlayer, SceneSpec};
+use crate::rgb::{Rgb, RgbLayout, OFF};
+use crate::scroll::{ScrollOptions, SmoothScroll};
+use crate::sprite::Sprite;
+use crate::timer::{self, Alert, Countdown, Stopwatch, TimerControl};
+use crate::transition::Transition;
+use crate::tween::{Clock, SystemClock};
+use crate::widget::Widget;
 
-use super::ws2812_rmt::Ws2812Rmt;
+use super::ws2812_rmt::{FrameStats, Ws2812Rmt};
 
 pub type MessageRmtChannel = esp_idf_hal::rmt::CHANNEL1;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Message {
     Off,
     Message(String, Rgb),
-    Scroll(String, Rgb, usize), // scroll rate
+    Scroll(String, Rgb, ScrollOptions, f32), // scroll speed (pixels per second)
+    Pages(String, Rgb, PageMode, usize),     // word wrapped pages (page hold ticks)
+    Animation(AnimationSource),
+    Image(String, ScaleMode), // built-in image (see image::BUILTIN_IMAGES)
+    Effect(String, EffectParams), // named effect (see EffectRegistry)
+    Pattern(String, EffectParams), // pattern source (see pattern module)
+    Layers(Vec<Message>),     // each message drawn in own layer (bottom to top)
+    Transition(Box<Message>, Transition), // transition from current message
+    Clock(ClockOptions, Rgb), // time from system clock (once synchronised)
+    Countdown(Countdown, Rgb, Alert), // alert shown when finished
+    Stopwatch(Rgb),
+    Counter(i64, Rgb),
+    Control(TimerControl), // control current countdown/stopwatch/counter
+    Chart(Chart, usize),   // chart of pushed samples (samples kept)
+    Samples(Vec<f32>),     // push samples to current chart
+    Widget(String, Widget, (i32, i32), (i32, i32)), // named widget at position with size
+    UpdateWidget(String, Widget), // update named widget in current scene
+    Playlist(Playlist),    // scenes shown in turn (see PlaylistPlayer)
+    Game(GameKind),        // game filling display (see game module)
+    Input(Input),          // input to current game
 }
 
-type MessageGuard = (Mutex<Message>, Condvar);
+impl TryFrom<&SceneSpec> for Message {
+    type Error = anyhow::Error;
+    fn try_from(spec: &SceneSpec) -> Result<Self> {
+        Ok(match spec {
+            SceneSpec::Message { text, colour } => Message::Message(text.clone(), colour.parse()?),
+            SceneSpec::Scroll {
+                text,
+                colour,
+                speed,
+            } => Message::Scroll(
+                text.clone(),
+                colour.parse()?,
+                ScrollOptions::default(),
+                *speed,
+            ),
+            SceneSpec::Clock {
+                colour,
+                format,
+                hour12,
+                utc_offset_min,
+            } => {
+                let options = ClockOptions {
+                    format: format.clone(),
+                    hour12: *hour12,
+                    utc_offset_min: *utc_offset_min,
+                    ..Default::default()
+                };
+                Message::Clock(options, colour.parse()?)
+            }
+            SceneSpec::Effect {
+                name,
+                palette,
+                speed,
+            } => {
+                let mut params = EffectParams::default();
+                if let Some(palette) = palette {
+                    params.palette = palette.parse()?;
+                }
+                if let Some(speed) = speed {
+                    params.speed = speed.max(0.0);
+                }
+                Message::Effect(name.clone(), params)
+            }
+            SceneSpec::Image { name } => {
+                // Check now so errors are reported when playlist is saved
+                if image::builtin(name).is_none() {
+                    bail!("Unknown image: {}", name);
+                }
+                Message::Image(name.clone(), ScaleMode::Fit)
+            }
+            SceneSpec::Pattern {
+                source,
+                palette,
+                speed,
+            } => {
+                // Compile now so errors are reported when playlist is saved
+                Pattern::compile(source)?;
+                let mut params = EffectParams::default();
+                if let Some(palette) = palette {
+                    params.palette = palette.parse()?;
+                }
+                if let Some(speed) = speed {
+                    params.speed = speed.max(0.0);
+                }
+                Message::Pattern(source.clone(), params)
+            }
+        })
+    }
+}
+
+// Message for playlist entry (Off if missing or invalid)
+fn playlist_message(entry: Option<&Entry>) -> Message {
+    match entry.map(|entry| Message::try_from(&entry.scene)) {
+        Some(Ok(message)) => message,
+        Some(Err(e)) => {
+            log::error!("Playlist error: {}", e);
+            Message::Off
+        }
+        None => Message::Off,
+    }
+}
+
+// Pending updates (queued so rapid updates from other threads aren't lost)
+type MessageGuard = (Mutex<VecDeque<Message>>, Condvar);
 static MESSAGE_GUARD: Mutex<Option<Arc<MessageGuard>>> = Mutex::new(None);
 
+static MESSAGE_STATS: Mutex<FrameStats> = Mutex::new(FrameStats {
+    sent: 0,
+    skipped: 0,
+});
+
 const MESSAGE_POLL_MS: u64 = 25;
+const MESSAGE_KEEP_ALIVE_MS: u64 = 1000;
+
+// Display state for a message drawn into its own layer
+struct Content {
+    message: Message,
+    ticks: usize,
+    last_ms: u64, // Clock time of last tick
+    scroll: Option<SmoothScroll>,
+    scroll_p: Option<(f32, f32)>,
+    layout: Option<TextLayout>,
+    page_iter: Box<dyn Iterator<Item = i32> + Send>,
+    page_y: Option<i32>,
+    player: Option<Player>,
+    image: Option<Sprite>,
+    effect: Option<Box<dyn Effect>>,
+    text: Option<(String, Rgb, bool)>,
+    clock: SystemClock,
+    stopwatch: Stopwatch,
+    counter: i64,
+    samples: Samples,
+    samples_changed: bool,
+    widget: Option<Widget>,
+    widget_changed: bool,
+    game: Option<GameRunner>,
+    cycles: u32, // Completed scroll/page passes
+}
+
+// Draw clock style text if changed - returns true if redrawn
+fn draw_text<const N: usize>(
+    drawn: &mut Option<(String, Rgb, bool)>,
+    matrix: &mut Matrix1D<N>,
+    text: (String, Rgb, bool),
+) -> bool {
+    if drawn.as_ref() == Some(&text) {
+        return false;
+    }
+    matrix.clear();
+    clock::draw_clock(matrix, &text.0, text.1, text.2);
+    *drawn = Some(text);
+    true
+}
+
+impl Content {
+    fn new<const N: usize>(message: Message, matrix: &Matrix1D<N>) -> Self {
+        let mut content = Self {
+            message: Message::Off,
+            ticks: 0,
+            last_ms: 0,
+            scroll: None,
+            scroll_p: None,
+            layout: None,
+            page_iter: Box::new(std::iter::empty()),
+            page_y: None,
+            player: None,
+            image: None,
+            effect: None,
+            text: None,
+            clock: SystemClock::new(),
+            stopwatch: Stopwatch::default(),
+            counter: 0,
+            samples: Samples::default(),
+            samples_changed: false,
+            widget: None,
+            widget_changed: false,
+            game: None,
+            cycles: 0,
+        };
+        match &message {
+            Message::Scroll(s, _, options, speed) => {
+                let path = matrix.scroll_positions(s, &FONT_8X8, *options);
+                content.scroll = Some(SmoothScroll::new(path, *speed));
+            }
+            Message::Pages(s, _, mode, hold) => {
+                let layout = matrix.layout(s, &FONT_8X8);
+                content.page_iter = Box::new(layout.page_offsets(*mode, *hold));
+                content.layout = Some(layout);
+            }
+            Message::Animation(source) => {
+                content.player = match source.open() {
+                    Ok(source) => Some(Player::new(source)),
+                    Err(e) => {
+                        log::error!("Animation error: {}", e);
+                        None
+                    }
+                };
+            }
+            Message::Image(name, _) => {
+                content.image = match image::builtin(name).map(image::decode) {
+                    Some(Ok(image)) => Some(image),
+                    Some(Err(e)) => {
+                        log::error!("Image error: {}", e);
+                        None
+                    }
+                    None => {
+                        log::error!("Unknown image: {}", name);
+                        None
+                    }
+                };
+            }
+            Message::Effect(name, _) => {
+                content.effect = EffectRegistry::default().create(name);
+                if content.effect.is_none() {
+                    log::error!("Unknown effect: {}", name);
+                }
+            }
+            Message::Pattern(source, _) => match Pattern::compile(source) {
+                Ok(pattern) => content.effect = Some(Box::new(pattern)),
+                Err(e) => log::error!("Pattern error: {}", e),
+            },
+            Message::Countdown(_, _, _) | Message::Stopwatch(_) => {
+                content.stopwatch = Stopwatch::started(content.clock.now_ms());
+            }
+            Message::Counter(n, _) => content.counter = *n,
+            Message::Chart(_, capacity) => content.samples = Samples::new(*capacity),
+            Message::Widget(_, widget, _, _) => content.widget = Some(*widget),
+            Message::Game(kind) => {
+                let seed = clock::unix_ms().map_or(1, |ms| ms as u32);
+                content.game = Some(GameRunner::new(*kind, matrix.size(), seed));
+            }
+            Message::Layers(_)
+            | Message::Transition(_, _)
+            | Message::Control(_)
+            | Message::Samples(_)
+            | Message::UpdateWidget(_, _)
+            | Message::Input(_)
+            | Message::Playlist(_) => {
+                log::error!("Nested layers/transitions/controls/playlists not supported");
+                return content;
+            }
+            Message::Off | Message::Message(_, _) | Message::Clock(_, _) => {}
+        }
+        content.message = message;
+        content
+    }
+    // Apply control to countdown/stopwatch/counter (others ignore controls)
+    fn control(&mut self, control: TimerControl) {
+        let now = self.clock.now_ms();
+        match (&self.message, control) {
+            (Message::Counter(initial, _), control) => match control {
+                TimerControl::Increment(n) => self.counter = self.counter.saturating_add(n),
+                TimerControl::Decrement(n) => self.counter = self.counter.saturating_sub(n),
+                TimerControl::Reset => self.counter = *initial,
+                TimerControl::Start | TimerControl::Pause => {}
+            },
+            (Message::Countdown(_, _, _) | Message::Stopwatch(_), control) => match control {
+                TimerControl::Start => self.stopwatch.start(now),
+                TimerControl::Pause => self.stopwatch.pause(now),
+                TimerControl::Reset => self.stopwatch.reset(),
+                TimerControl::Increment(_) | TimerControl::Decrement(_) => {}
+            },
+            _ => {}
+        }
+    }
+    fn push(&mut self, samples: &[f32]) {
+        if let Message::Chart(_, _) = self.message {
+            self.samples.extend(samples.iter().copied());
+            self.samples_changed = true;
+        }
+    }
+    fn update_widget(&mut self, name: &str, widget: Widget) {
+        if let Message::Widget(n, _, _, _) = &self.message {
+            if n == name {
+                self.widget = Some(widget);
+                self.widget_changed = true;
+            }
+        }
+    }
+    fn input(&mut self, input: Input) {
+        if let Some(game) = self.game.as_mut() {
+            game.input(input);
+        }
+    }
+    // Passes completed (finished animations count as done)
+    fn cycles(&self) -> u32 {
+        match &self.player {
+            Some(player) if player.is_finished() => u32::MAX,
+            Some(player) => player.played(),
+            None => self.cycles,
+        }
+    }
+    // Time since last tick - measured as updates wake the thread early and
+    // drawing/sending takes time
+    fn elapsed_ms(&mut self) -> u32 {
+        let now = self.clock.now_ms();
+        let elapsed = now.saturating_sub(self.last_ms);
+        self.last_ms = now;
+        elapsed as u32
+    }
+    // Advance one tick - returns true if layer was redrawn
+    fn tick<const N: usize>(&mut self, matrix: &mut Matrix1D<N>) -> bool {
+        let ticks = self.ticks;
+        self.ticks += 1;
+        let elapsed_ms = self.elapsed_ms();
+        match &self.message {
+            Message::Off
+            | Message::Layers(_)
+            | Message::Transition(_, _)
+            | Message::Control(_)
+            | Message::Samples(_)
+            | Message::UpdateWidget(_, _)
+            | Message::Input(_)
+            | Message::Playlist(_) => {
+                if ticks == 0 {
+                    matrix.clear();
+                }
+                ticks == 0
+            }
+            Message::Message(s, rgb) => {
+                // Static - refreshed by driver keep-alive
+                if ticks == 0 {
+                    matrix.clear();
+                    matrix.draw_str(s, &FONT_8X8, *rgb, (0, 0));
+                }
+                ticks == 0
+            }
+            Message::Scroll(s, rgb, options, speed) => {
+                let p = if ticks == 0 {
+                    self.scroll.as_ref().and_then(|scroll| scroll.position())
+                } else {
+                    self.scroll
+                        .as_mut()
+                        .and_then(|scroll| scroll.tick(elapsed_ms))
+                };
+                let p = p.unwrap_or_else(|| {
+                    // Restart scroll
+                    if ticks > 0 {
+                        self.cycles += 1;
+                    }
+                    let path = matrix.scroll_positions(s, &FONT_8X8, *options);
+                    let scroll = SmoothScroll::new(path, *speed);
+                    let p = scroll.position().unwrap_or((0.0, 0.0));
+                    self.scroll = Some(scroll);
+                    p
+                });
+                // Only redraw when text moves
+                if self.scroll_p != Some(p) {
+                    matrix.clear();
+                    matrix.draw_str_smooth(s, &FONT_8X8, *rgb, p);
+                    self.scroll_p = Some(p);
+                    true
+                } else {
+                    false
+                }
+            }
+            Message::Pages(_, rgb, mode, hold) => {
+                let layout = match &self.layout {
+                    Some(layout) => layout,
+                    None => return false,
+                };
+                let y = if let Some(y) = self.page_iter.next() {
+                    y
+                } else {
+                    // Reset iterator
+                    self.cycles += 1;
+                    self.page_iter = Box::new(layout.page_offsets(*mode, *hold));
+                    self.page_iter.next().unwrap_or(0)
+                };
+                // Only redraw when page moves
+                if self.page_y != Some(y) {
+                    matrix.clear();
+                    matrix.draw_lines(&layout.lines, &FONT_8X8, *rgb, (0, -y));
+                    self.page_y = Some(y);
+                    true
+                } else {
+                    false
+                }
+            }
+            Message::Animation(_) => match self.player.as_mut() {
+                Some(player) => match player.tick(elapsed_ms) {
+                    Ok(Some(frame)) => {
+                        matrix.clear();
+                        image::draw_image(matrix, frame, ScaleMode::Fit, Filter::Nearest, None);
+                        true
+                    }
+                    Ok(None) => false,
+                    Err(e) => {
+                        log::error!("Animation error: {}", e);
+                        self.player = None;
+                        false
+                    }
+                },
+                None => ticks == 0,
+            },
+            Message::Image(_, mode) => {
+                // Static - refreshed by driver keep-alive
+                if ticks == 0 {
+                    matrix.clear();
+                    if let Some(image) = &self.image {
+                        image::draw_image(matrix, image, *mode, Filter::Box, None);
+                    }
+                }
+                ticks == 0
+            }
+            Message::Effect(_, params) | Message::Pattern(_, params) => {
+                match self.effect.as_mut() {
+                    Some(effect) => {
+                        effect.render(self.clock.now_ms() as u32, params, matrix);
+                        true
+                    }
+                    None => ticks == 0,
+                }
+            }
+            Message::Game(_) => match self.game.as_mut() {
+                Some(game) => {
+                    // Game runs its own fixed step from elapsed time
+                    let changed = game.tick(elapsed_ms);
+                    if changed || ticks == 0 {
+                        game.draw(matrix);
+                    }
+                    changed || ticks == 0
+                }
+                None => ticks == 0,
+            },
+            Message::Clock(options, rgb) => {
+                let now = DateTime::now(options.utc_offset_min);
+                let (text, separators) = options.text(now.as_ref());
+                draw_text(&mut self.text, matrix, (text, *rgb, separators))
+            }
+            Message::Countdown(countdown, rgb, alert) => {
+                let now = self.clock.now_ms();
+                let text = match countdown.remaining_ms(&self.stopwatch, now, clock::unix_ms()) {
+                    Some(0) => match alert.colour(*rgb, now) {
+                        Some(rgb) => ("0:00".to_string(), rgb, true),
+                        None => (String::new(), *rgb, true),
+                    },
+                    Some(ms) => (timer::format_duration(ms, true), *rgb, true),
+                    None => ("--:--".to_string(), *rgb, true),
+                };
+                draw_text(&mut self.text, matrix, text)
+            }
+            Message::Stopwatch(rgb) => {
+                let ms = self.stopwatch.elapsed_ms(self.clock.now_ms());
+                let text = (timer::format_duration(ms, false), *rgb, true);
+                draw_text(&mut self.text, matrix, text)
+            }
+            Message::Counter(_, rgb) => {
+                let text = (self.counter.to_string(), *rgb, true);
+                draw_text(&mut self.text, matrix, text)
+            }
+            Message::Widget(_, _, p, size) => match self.widget {
+                // Only redraw when updated (or animated)
+                Some(widget)
+                    if ticks == 0
+                        || widget.is_animated()
+                        || std::mem::take(&mut self.widget_changed) =>
+                {
+                    matrix.clear();
+                    widget.draw(matrix, *p, *size, self.clock.now_ms() as u32);
+                    true
+                }
+                _ => false,
+            },
+            Message::Chart(chart, _) => {
+                // Only redraw when samples pushed
+                if ticks == 0 || std::mem::take(&mut self.samples_changed) {
+                    let size = matrix.size();
+                    chart.draw(&self.samples, matrix, (0, 0), size);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+// Message contents drawn into a stack of layers
+struct Scene<const N: usize> {
+    layers: LayerStack<N>,
+    contents: Vec<(LayerId, Content)>,
+}
+
+impl<const N: usize> Scene<N> {
+    // Each message gets a new layer
+    fn new(message: Message, style: TextStyle) -> Self {
+        let messages = match message {
+            Message::Layers(messages) => messages,
+            message => vec![message],
+        };
+        let mut scene = Self {
+            layers: LayerStack::new(),
+            contents: vec![],
+        };
+        for (z, message) in messages.into_iter().enumerate() {
+            let mut layer = Layer::new(z as i32);
+            layer.matrix.set_text_style(style);
+            // Unlit pixels show layers below
+            if z > 0 {
+                layer.key = Some(OFF);
+            }
+            let content = Content::new(message, &layer.matrix);
+            scene.contents.push((scene.layers.push(layer), content));
+        }
+        scene
+    }
+    fn control(&mut self, control: TimerControl) {
+        for (_, content) in self.contents.iter_mut() {
+            content.control(control);
+        }
+    }
+    fn push(&mut self, samples: &[f32]) {
+        for (_, content) in self.contents.iter_mut() {
+            content.push(samples);
+        }
+    }
+    fn update_widget(&mut self, name: &str, widget: Widget) {
+        for (_, content) in self.contents.iter_mut() {
+            content.update_widget(name, widget);
+        }
+    }
+    fn input(&mut self, input: Input) {
+        for (_, content) in self.contents.iter_mut() {
+            content.input(input);
+        }
+    }
+    // Passes completed by slowest content
+    fn cycles(&self) -> u32 {
+        self.contents
+            .iter()
+            .map(|(_, content)| content.cycles())
+            .min()
+            .unwrap_or(0)
+    }
+    // Advance all contents - returns true if any layer was redrawn
+    fn tick(&mut self) -> bool {
+        let mut changed = false;
+        for (id, content) in self.contents.iter_mut() {
+            if let Some(layer) = self.layers.get_mut(*id) {
+                changed |= content.tick(&mut layer.matrix);
+            }
+        }
+        changed
+    }
+}
 
 pub struct Ws2812Message<const N: usize> {
     message_thread: Option<JoinHandle<Result<(), Error>>>,
@@ -39,75 +597,105 @@ impl<const N: usize> Ws2812Message<N> {
         // (needs to be 'static for thread) so we create here
         let tx = TxRmtDriver::new(channel, pin, &TransmitConfig::new().clock_divider(1))?;
         let mut ws2812 = Ws2812Rmt::new(tx, PANEL_PIXELS * N, RgbLayout::Grb);
+        ws2812.set_keep_alive(Some(Duration::from_millis(MESSAGE_KEEP_ALIVE_MS)));
 
-        let guard = Arc::new((Mutex::new(Message::Off), Condvar::new()));
+        let guard = Arc::new((Mutex::new(VecDeque::new()), Condvar::new()));
         // Initialise static GUARD with clone (use for TX)
         {
             let mut guard_static = MESSAGE_GUARD.lock().unwrap();
             *guard_static = Some(guard.clone());
         }
+        let style = TextStyle {
+            proportional: true,
+            spacing: 1,
+            ..Default::default()
+        };
         let mut matrix = Matrix1D::<N>::from_panels(panels);
+        matrix.set_text_style(style);
 
         // Move into thread
         let rx = thread::spawn(move || {
             let (update, cvar) = &*guard;
-            let mut message = Message::Off;
-            let mut scroll_iter: Rev<Range<i32>> = (0..0).rev();
-            let mut ticks = 0_usize;
+            let mut scene = Scene::<N>::new(Message::Off, style);
+            // Time is measured as updates wake the thread early and frames
+            // take time to send
+            let clock = SystemClock::new();
+            let mut last_ms = 0;
+            // Outgoing scene with transition and start time
+            let mut outgoing: Option<(Scene<N>, Transition, u64)> = None;
+            let mut playlist: Option<PlaylistPlayer> = None;
+            let (mut from, mut to) = (Matrix1D::<N>::new(), Matrix1D::<N>::new());
             loop {
-                // Wait for CVAR timeout
-                let started = update.lock().unwrap();
-                let result = cvar
-                    .wait_timeout(started, Duration::from_millis(MESSAGE_POLL_MS))
+                // Wait for updates or CVAR timeout
+                let queue = update.lock().unwrap();
+                let (mut queue, _) = cvar
+                    .wait_timeout_while(queue, Duration::from_millis(MESSAGE_POLL_MS), |q| {
+                        q.is_empty()
+                    })
                     .unwrap();
-                if !result.1.timed_out() {
-                    log::info!("UPDATE:: {:?}", *result.0);
-                    // Update status
-                    message = result.0.clone();
-                    match &message {
-                        Message::Off => {
-                            matrix.clear();
-                            ws2812.set(matrix.iter())?;
+                let updates = queue.drain(..).collect::<Vec<_>>();
+                drop(queue);
+                let now = clock.now_ms();
+                let elapsed_ms = now.saturating_sub(last_ms) as u32;
+                last_ms = now;
+                let mut changed = false;
+                for message in updates {
+                    log::info!("UPDATE:: {:?}", message);
+                    // Update status - current scene keeps running during transition
+                    match message {
+                        Message::Transition(message, transition) => {
+                            let previous =
+                                std::mem::replace(&mut scene, Scene::new(*message, style));
+                            outgoing = Some((previous, transition, clock.now_ms()));
+                            playlist = None;
                         }
-                        Message::Message(s, rgb) => {
-                            matrix.clear();
-                            matrix.draw_str(s, *rgb, (0, 0));
-                            ws2812.set(matrix.iter())?;
+                        // Controls update running scene
+                        Message::Control(control) => scene.control(control),
+                        Message::Samples(samples) => scene.push(&samples),
+                        Message::UpdateWidget(name, widget) => scene.update_widget(&name, widget),
+                        Message::Input(input) => scene.input(input),
+                        Message::Playlist(p) => {
+                            // Seeded from wall clock so shuffles differ between boots
+                            let seed = clock::unix_ms().map_or(1, |ms| ms as u32);
+                            let player = PlaylistPlayer::new(p, seed);
+                            scene = Scene::new(playlist_message(player.current()), style);
+                            playlist = Some(player);
+                            outgoing = None;
                         }
-                        Message::Scroll(s, _, _) => {
-                            matrix.clear();
-                            scroll_iter = matrix.scroll_iter(s.len());
-                            ticks = 0;
+                        message => {
+                            scene = Scene::new(message, style);
+                            outgoing = None;
+                            playlist = None;
                         }
                     }
+                    changed = true;
                 }
-                match &message {
-                    Message::Off => {}
-                    // Message::Message(_, _) => {}
-                    Message::Message(s, rgb) => {
-                        // Refresh every 10 ticks
-                        if ticks % 10 == 0 {
-                            matrix.clear();
-                            matrix.draw_str(&s, *rgb, (0, 0));
-                            ws2812.set(matrix.iter())?;
-                        }
+                if let Some(player) = playlist.as_mut() {
+                    if let Some(entry) = player.tick(elapsed_ms, scene.cycles()) {
+                        scene = Scene::new(playlist_message(Some(entry)), style);
+                        changed = true;
                     }
-                    Message::Scroll(s, rgb, t) => {
-                        if *t == 0 || ticks % t == 0 {
-                            let x = if let Some(x) = scroll_iter.next() {
-                                x
-                            } else {
-                                // Reset iterator
-                                scroll_iter = matrix.scroll_iter(s.len());
-                                scroll_iter.next().unwrap_or(0)
-                            };
-                            matrix.clear();
-                            matrix.draw_str(s, *rgb, (x, 0));
-                            ws2812.set(matrix.iter())?;
-                        }
+                }
+                changed |= scene.tick();
+                if let Some((previous, transition, start)) = outgoing.as_mut() {
+                    let elapsed = clock.now_ms().saturating_sub(*start) as u32;
+                    previous.tick();
+                    previous.layers.flatten(&mut from);
+                    scene.layers.flatten(&mut to);
+                    transition.render(&from, &to, elapsed, &mut matrix);
+                    if transition.is_finished(elapsed) {
+                        outgoing = None;
                     }
+                } else if changed {
+                    scene.layers.flatten(&mut matrix);
+                }
+                // Only set pixels that differ are marked dirty - the driver
+                // then skips frames identical to the last one sent (checked
+                // by hash before the RMT signal is built)
+                if matrix.take_dirty().is_some() || ws2812.keep_alive_due() {
+                    ws2812.set_hashed(matrix.frame_hash(), matrix.iter())?;
+                    *MESSAGE_STATS.lock().unwrap() = ws2812.stats();
                 }
-                ticks += 1;
             }
         });
         Ok(Self {
@@ -125,6 +713,14 @@ impl<const N: usize> Ws2812Message<N> {
         }
     }
 
+    // Frames sent/skipped by message thread
+    pub fn stats() -> Result<FrameStats> {
+        MESSAGE_STATS
+            .lock()
+            .map(|stats| *stats)
+            .map_err(|_| anyhow::anyhow!("Cant lock MESSAGE_STATS"))
+    }
+
     pub fn update(message: Message) -> Result<()> {
         // Lock the GUARD to access its contents
         let guard = MESSAGE_GUARD
@@ -140,8 +736,8 @@ impl<const N: usize> Ws2812Message<N> {
         let mut s = update
             .lock()
             .map_err(|_| anyhow::anyhow!("Cant lock UPDATE"))?;
-        // Update status
-        *s = message;
+        // Queue update
+        s.push_back(message);
         // Notify the condvar that the value has changed.
         cvar.notify_one();
         Ok(())
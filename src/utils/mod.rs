@@ -1,10 +1,32 @@
+pub mod animation;
+pub mod bdf;
+pub mod chart;
+pub mod clock;
+pub mod draw;
+pub mod easing;
+pub mod effect;
+pub mod font;
+pub mod game;
 pub mod hash;
 pub mod httpd;
+pub mod image;
+pub mod layer;
+pub mod layout;
 pub mod matrix;
 pub mod matrix_1d;
 pub mod message;
 pub mod nvs;
+pub mod pattern;
+pub mod playlist;
+pub mod render;
 pub mod rgb;
+pub mod schedule;
+pub mod scroll;
+pub mod sprite;
 pub mod status;
+pub mod timer;
+pub mod transition;
+pub mod tween;
+pub mod widget;
 pub mod wifi;
 pub mod ws2812_rmt;
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 
 use crate::hash::hash_ssid;
+use crate::playlist::Playlist;
+use crate::schedule::Schedule;
 use crate::wifi::APConfig;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -145,3 +147,91 @@ impl APStore {
         }
     }
 }
+
+static PLAYLISTS_LEN: usize = 8192;
+static SCHEDULE_LEN: usize = 2048;
+pub static PLAYLISTS: Mutex<Vec<Playlist>> = Mutex::new(Vec::new());
+pub static SCHEDULE: Mutex<Schedule> = Mutex::new(Schedule {
+    utc_offset_min: 0,
+    rules: Vec::new(),
+});
+pub static PLAYLIST_NVS: Mutex<Option<EspNvs<NvsDefault>>> = Mutex::new(None);
+
+// Playlists and schedule saved as JSON (cached in PLAYLISTS/SCHEDULE statics)
+pub struct PlaylistStore(());
+
+impl PlaylistStore {
+    // Initialise static NVS and load saved playlists/schedule
+    pub fn init(nvs_default_partition: EspNvsPartition<NvsDefault>) -> anyhow::Result<()> {
+        let nvs = EspDefaultNvs::new(nvs_default_partition, "playlists", true)?;
+        {
+            // Stale or incompatible JSON (eg. from older firmware) is ignored
+            // so that it can't stop the device booting
+            let mut data = vec![0_u8; PLAYLISTS_LEN];
+            if let Ok(Some(data)) = nvs.get_raw("PLAYLISTS", &mut data) {
+                match serde_json::from_slice(data) {
+                    Ok(playlists) => *PLAYLISTS.lock().unwrap() = playlists,
+                    Err(e) => log::warn!("Ignoring saved PLAYLISTS: {}", e),
+                }
+            }
+            let mut data = vec![0_u8; SCHEDULE_LEN];
+            if let Ok(Some(data)) = nvs.get_raw("SCHEDULE", &mut data) {
+                match serde_json::from_slice(data) {
+                    Ok(schedule) => *SCHEDULE.lock().unwrap() = schedule,
+                    Err(e) => log::warn!("Ignoring saved SCHEDULE: {}", e),
+                }
+            }
+            log::info!(
+                "PLAYLISTS >> {} playlists / {} rules",
+                PLAYLISTS.lock().unwrap().len(),
+                SCHEDULE.lock().unwrap().rules.len()
+            );
+        }
+        let mut nvs_static = PLAYLIST_NVS.lock().unwrap();
+        *nvs_static = Some(nvs);
+        Ok(())
+    }
+
+    pub fn get_playlists() -> Vec<Playlist> {
+        PLAYLISTS.lock().unwrap().clone()
+    }
+
+    pub fn get_playlist(name: &str) -> Option<Playlist> {
+        PLAYLISTS
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+    }
+
+    pub fn set_playlists(playlists: Vec<Playlist>) -> anyhow::Result<()> {
+        let v = serde_json::to_vec(&playlists)?;
+        if v.len() > PLAYLISTS_LEN {
+            anyhow::bail!("Playlists too large: {} bytes", v.len());
+        }
+        let mut nvs = PLAYLIST_NVS.lock().unwrap();
+        let nvs = nvs.as_mut().ok_or(anyhow::anyhow!("NVS not initialized"))?;
+        nvs.set_raw("PLAYLISTS", v.as_slice())
+            .map_err(|e| anyhow::anyhow!("Error updating PLAYLISTS: [{}]", e))?;
+        *PLAYLISTS.lock().unwrap() = playlists;
+        Ok(())
+    }
+
+    pub fn get_schedule() -> Schedule {
+        SCHEDULE.lock().unwrap().clone()
+    }
+
+    pub fn set_schedule(schedule: Schedule) -> anyhow::Result<()> {
+        let v = serde_json::to_vec(&schedule)?;
+        if v.len() > SCHEDULE_LEN {
+            anyhow::bail!("Schedule too large: {} bytes", v.len());
+        }
+        let mut nvs = PLAYLIST_NVS.lock().unwrap();
+        let nvs = nvs.as_mut().ok_or(anyhow::anyhow!("NVS not initialized"))?;
+        nvs.set_raw("SCHEDULE", v.as_slice())
+            .map_err(|e| anyhow::anyhow!("Error updating SCHEDULE: [{}]", e))?;
+        *SCHEDULE.lock().unwrap() = schedule;
+        Ok(())
+    }
+}
@@ -0,0 +1,771 @@
+use anyhow::{anyhow, bail, Result};
+use std::f32::consts::{PI, TAU};
+
+use crate::draw::{Canvas, Paint};
+use crate::effect::{self, Clock, Effect, EffectParams, Palette, Rng};
+use crate::rgb::{Rgb, OFF};
+
+// User programmable pattern
+//
+// A small expression language compiled to bytecode and run once per pixel
+// each frame. Statements are separated by newlines or ';':
+//
+//   // Drifting noise coloured from the palette
+//   n = noise(x * 0.3, y * 0.3, t)
+//   if n > 0.7 { hsv(t * 0.1, 1, 1) } else { palette(n + t * 0.05) }
+//
+// Inputs are x, y (pixel), u, v (0.0..=1.0 across display), t (seconds,
+// scaled by effect speed), w, h (display size) and i (pixel index).
+// Variables are numbers which keep their values between pixels and frames
+// (so must be assigned before they are read).
+//
+// Colour comes from the last call to hsv(), rgb() or palette() - if there
+// is none the value of the last expression is looked up in the palette.
+//
+// Operators (lowest precedence first):
+//
+//   ?:  ||  &&  == !=  < <= > >=  + -  * / %  unary - !
+//
+// '%' wraps negative values (so hues and phases cycle smoothly). Control
+// flow is `if cond { } else { }` and `while cond { }` - patterns are
+// stopped if they exceed FRAME_STEP_LIMIT instructions in a frame.
+
+/// Instructions a pattern may run per frame (across all pixels)
+pub const FRAME_STEP_LIMIT: u32 = 50_000;
+
+const MAX_OPS: usize = 2048;
+const MAX_VARS: usize = 64;
+const MAX_DEPTH: usize = 32; // Nesting of expressions and blocks
+const STACK_SIZE: usize = 128;
+
+// Set before each pixel (first variable slots)
+const INPUTS: [&str; 8] = ["x", "y", "u", "v", "t", "w", "h", "i"];
+const KEYWORDS: [&str; 3] = ["if", "else", "while"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Builtin {
+    Sin,
+    Cos,
+    Tan,
+    Atan2,
+    Abs,
+    Floor,
+    Ceil,
+    Frac,
+    Sqrt,
+    Pow,
+    Min,
+    Max,
+    Clamp,
+    Mix,
+    Wave,     // Sine 0..1 with period 1
+    Triangle, // Triangle 0..1 with period 1
+    Square,   // 1 for first duty fraction of each period
+    Time,     // Sawtooth 0..1 with period in seconds
+    Noise,    // Smooth value noise 0..1
+    Random,
+    Palette,
+    Hsv,
+    Rgb,
+}
+
+// (name, function, arguments)
+const BUILTINS: [(&str, Builtin, usize); 23] = [
+    ("sin", Builtin::Sin, 1),
+    ("cos", Builtin::Cos, 1),
+    ("tan", Builtin::Tan, 1),
+    ("atan2", Builtin::Atan2, 2),
+    ("abs", Builtin::Abs, 1),
+    ("floor", Builtin::Floor, 1),
+    ("ceil", Builtin::Ceil, 1),
+    ("frac", Builtin::Frac, 1),
+    ("sqrt", Builtin::Sqrt, 1),
+    ("pow", Builtin::Pow, 2),
+    ("min", Builtin::Min, 2),
+    ("max", Builtin::Max, 2),
+    ("clamp", Builtin::Clamp, 3),
+    ("mix", Builtin::Mix, 3),
+    ("wave", Builtin::Wave, 1),
+    ("triangle", Builtin::Triangle, 1),
+    ("square", Builtin::Square, 2),
+    ("time", Builtin::Time, 1),
+    ("noise", Builtin::Noise, 3),
+    ("random", Builtin::Random, 0),
+    ("palette", Builtin::Palette, 1),
+    ("hsv", Builtin::Hsv, 3),
+    ("rgb", Builtin::Rgb, 3),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Num(f32),
+    Load(usize),
+    Store(usize),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Neg,
+    Not,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    Jump(usize),
+    JumpIfZero(usize),
+    Call(Builtin),
+    Result, // Pop value of expression statement
+}
+
+// Binary operators by precedence (lowest first)
+const BINARY: [&[(&str, Op)]; 4] = [
+    &[("==", Op::Eq), ("!=", Op::Ne)],
+    &[("<", Op::Lt), ("<=", Op::Le), (">", Op::Gt), (">=", Op::Ge)],
+    &[("+", Op::Add), ("-", Op::Sub)],
+    &[("*", Op::Mul), ("/", Op::Div), ("%", Op::Mod)],
+];
+const ASSIGN: [(&str, Option<Op>); 5] = [
+    ("=", None),
+    ("+=", Some(Op::Add)),
+    ("-=", Some(Op::Sub)),
+    ("*=", Some(Op::Mul)),
+    ("/=", Some(Op::Div)),
+];
+const SYMBOLS: [&str; 27] = [
+    "&&", "||", "==", "!=", "<=", ">=", "+=", "-=", "*=", "/=", "+", "-", "*", "/", "%", "<", ">",
+    "!", "=", "(", ")", "{", "}", ",", "?", ":", ";",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f32),
+    Ident(String),
+    Sym(&'static str),
+    End, // Newline or ';'
+}
+
+// Tokens with line numbers
+//
+// Newlines only end statements after a value (so long expressions can be
+// split after an operator) and are ignored inside parentheses
+fn tokenise(source: &str) -> Result<Vec<(Token, usize)>> {
+    let mut tokens: Vec<(Token, usize)> = vec![];
+    let (mut line, mut parens) = (1, 0);
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            let ends_value = matches!(
+                tokens.last(),
+                Some((
+                    Token::Num(_) | Token::Ident(_) | Token::Sym(")") | Token::Sym("}"),
+                    _
+                ))
+            );
+            if parens == 0 && ends_value {
+                tokens.push((Token::End, line));
+            }
+            line += 1;
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |i| &rest[i..]);
+        } else if c.is_ascii_digit() || c == '.' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let n = rest[..len]
+                .parse()
+                .map_err(|_| anyhow!("line {}: invalid number '{}'", line, &rest[..len]))?;
+            tokens.push((Token::Num(n), line));
+            rest = &rest[len..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push((Token::Ident(rest[..len].to_string()), line));
+            rest = &rest[len..];
+        } else if let Some(sym) = SYMBOLS.iter().find(|s| rest.starts_with(*s)) {
+            match *sym {
+                "(" => parens += 1,
+                ")" => parens -= 1,
+                _ => {}
+            }
+            let token = if *sym == ";" {
+                Token::End
+            } else {
+                Token::Sym(sym)
+            };
+            tokens.push((token, line));
+            rest = &rest[sym.len()..];
+        } else {
+            bail!("line {}: unexpected '{}'", line, c);
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    ops: Vec<Op>,
+    vars: Vec<String>,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|(t, _)| t)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+    fn error(&self, message: &str) -> anyhow::Error {
+        let line = self
+            .tokens
+            .get(self.pos.min(self.tokens.len().saturating_sub(1)))
+            .map_or(1, |(_, line)| *line);
+        anyhow!("line {}: {}", line, message)
+    }
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Token::Sym(s)) if *s == sym)
+    }
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(name)) if name == keyword)
+    }
+    fn eat(&mut self, sym: &str) -> bool {
+        let found = self.is_sym(sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+    fn expect(&mut self, sym: &str) -> Result<()> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", sym)))
+        }
+    }
+    fn emit(&mut self, op: Op) -> Result<usize> {
+        if self.ops.len() >= MAX_OPS {
+            return Err(self.error("pattern too long"));
+        }
+        self.ops.push(op);
+        Ok(self.ops.len() - 1)
+    }
+    // Point jump at next instruction
+    fn patch(&mut self, at: usize) {
+        let target = self.ops.len();
+        match &mut self.ops[at] {
+            Op::Jump(t) | Op::JumpIfZero(t) => *t = target,
+            _ => unreachable!(),
+        }
+    }
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        Ok(())
+    }
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+    fn slot(&self, name: &str) -> Option<usize> {
+        self.vars.iter().position(|v| v == name)
+    }
+    // Statements to end of source or closing brace
+    fn statements(&mut self, in_block: bool) -> Result<()> {
+        loop {
+            match self.peek() {
+                None if in_block => return Err(self.error("expected '}'")),
+                None => return Ok(()),
+                Some(Token::Sym("}")) if in_block => return Ok(()),
+                Some(Token::End) => self.pos += 1,
+                _ => {
+                    self.statement()?;
+                    match self.peek() {
+                        None | Some(Token::End) | Some(Token::Sym("}")) => {}
+                        _ => return Err(self.error("expected end of statement")),
+                    }
+                }
+            }
+        }
+    }
+    fn block(&mut self) -> Result<()> {
+        self.expect("{")?;
+        self.enter()?;
+        self.statements(true)?;
+        self.leave();
+        self.expect("}")
+    }
+    fn statement(&mut self) -> Result<()> {
+        if self.is_keyword("if") {
+            self.pos += 1;
+            self.expr()?;
+            let skip = self.emit(Op::JumpIfZero(0))?;
+            self.block()?;
+            // else may start on the next line
+            if self.peek() == Some(&Token::End)
+                && matches!(self.peek_at(1), Some(Token::Ident(name)) if name == "else")
+            {
+                self.pos += 1;
+            }
+            if self.is_keyword("else") {
+                self.pos += 1;
+                let end = self.emit(Op::Jump(0))?;
+                self.patch(skip);
+                if self.is_keyword("if") {
+                    self.enter()?;
+                    self.statement()?;
+                    self.leave();
+                } else {
+                    self.block()?;
+                }
+                self.patch(end);
+            } else {
+                self.patch(skip);
+            }
+            return Ok(());
+        }
+        if self.is_keyword("while") {
+            self.pos += 1;
+            let start = self.ops.len();
+            self.expr()?;
+            let exit = self.emit(Op::JumpIfZero(0))?;
+            self.block()?;
+            self.emit(Op::Jump(start))?;
+            self.patch(exit);
+            return Ok(());
+        }
+        let assign = match (self.peek(), self.peek_at(1)) {
+            (Some(Token::Ident(name)), Some(Token::Sym(sym))) => ASSIGN
+                .iter()
+                .find(|(s, _)| s == sym)
+                .map(|(_, op)| (name.clone(), *op)),
+            _ => None,
+        };
+        match assign {
+            Some((name, op)) => {
+                if KEYWORDS.contains(&name.as_str()) || BUILTINS.iter().any(|b| b.0 == name) {
+                    return Err(self.error(&format!("can't assign to '{}'", name)));
+                }
+                self.pos += 2;
+                let slot = match self.slot(&name) {
+                    Some(slot) => slot,
+                    None if op.is_some() => {
+                        return Err(self.error(&format!("unknown variable '{}'", name)))
+                    }
+                    None => {
+                        if self.vars.len() >= MAX_VARS {
+                            return Err(self.error("too many variables"));
+                        }
+                        self.vars.push(name);
+                        self.vars.len() - 1
+                    }
+                };
+                if let Some(op) = op {
+                    self.emit(Op::Load(slot))?;
+                    self.expr()?;
+                    self.emit(op)?;
+                } else {
+                    self.expr()?;
+                }
+                self.emit(Op::Store(slot))?;
+            }
+            None => {
+                self.expr()?;
+                self.emit(Op::Result)?;
+            }
+        }
+        Ok(())
+    }
+    // Conditional (lowest precedence)
+    fn expr(&mut self) -> Result<()> {
+        self.enter()?;
+        self.or()?;
+        if self.eat("?") {
+            let skip = self.emit(Op::JumpIfZero(0))?;
+            self.expr()?;
+            let end = self.emit(Op::Jump(0))?;
+            self.patch(skip);
+            self.expect(":")?;
+            self.expr()?;
+            self.patch(end);
+        }
+        self.leave();
+        Ok(())
+    }
+    // Logical operators short circuit and give 0 or 1
+    fn or(&mut self) -> Result<()> {
+        self.and()?;
+        while self.eat("||") {
+            let rhs = self.emit(Op::JumpIfZero(0))?;
+            self.emit(Op::Num(1.0))?;
+            let end = self.emit(Op::Jump(0))?;
+            self.patch(rhs);
+            self.and()?;
+            self.emit(Op::Not)?;
+            self.emit(Op::Not)?;
+            self.patch(end);
+        }
+        Ok(())
+    }
+    fn and(&mut self) -> Result<()> {
+        self.binary(0)?;
+        while self.eat("&&") {
+            let skip = self.emit(Op::JumpIfZero(0))?;
+            self.binary(0)?;
+            self.emit(Op::Not)?;
+            self.emit(Op::Not)?;
+            let end = self.emit(Op::Jump(0))?;
+            self.patch(skip);
+            self.emit(Op::Num(0.0))?;
+            self.patch(end);
+        }
+        Ok(())
+    }
+    fn binary(&mut self, level: usize) -> Result<()> {
+        let Some(ops) = BINARY.get(level) else {
+            return self.unary();
+        };
+        self.binary(level + 1)?;
+        while let Some(op) = ops.iter().find(|(sym, _)| self.is_sym(sym)).map(|o| o.1) {
+            self.pos += 1;
+            self.binary(level + 1)?;
+            self.emit(op)?;
+        }
+        Ok(())
+    }
+    fn unary(&mut self) -> Result<()> {
+        let op = if self.eat("-") {
+            Some(Op::Neg)
+        } else if self.eat("!") {
+            Some(Op::Not)
+        } else {
+            None
+        };
+        match op {
+            Some(op) => {
+                self.enter()?;
+                self.unary()?;
+                self.leave();
+                self.emit(op)?;
+                Ok(())
+            }
+            None => self.primary(),
+        }
+    }
+    fn primary(&mut self) -> Result<()> {
+        match self.next() {
+            Some(Token::Num(n)) => {
+                self.emit(Op::Num(n))?;
+            }
+            Some(Token::Sym("(")) => {
+                self.expr()?;
+                self.expect(")")?;
+            }
+            Some(Token::Ident(name)) if self.is_sym("(") => {
+                self.pos += 1;
+                let (_, builtin, arity) = *BUILTINS
+                    .iter()
+                    .find(|b| b.0 == name)
+                    .ok_or_else(|| self.error(&format!("unknown function '{}'", name)))?;
+                let mut args = 0;
+                while !self.eat(")") {
+                    if args > 0 {
+                        self.expect(",")?;
+                    }
+                    self.expr()?;
+                    args += 1;
+                }
+                if args != arity {
+                    let message = format!("{}() takes {} arguments", name, arity);
+                    return Err(self.error(&message));
+                }
+                self.emit(Op::Call(builtin))?;
+            }
+            Some(Token::Ident(name)) => {
+                let op = match name.as_str() {
+                    "pi" => Op::Num(PI),
+                    "tau" => Op::Num(TAU),
+                    name => Op::Load(
+                        self.slot(name)
+                            .ok_or_else(|| self.error(&format!("unknown variable '{}'", name)))?,
+                    ),
+                };
+                self.emit(op)?;
+            }
+            _ => {
+                self.pos = self.pos.saturating_sub(1);
+                return Err(self.error("expected value"));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Compiled pattern
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    ops: Vec<Op>,
+    vars: Vec<String>,
+}
+
+impl Program {
+    pub fn compile(source: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenise(source)?,
+            pos: 0,
+            ops: vec![],
+            vars: INPUTS.iter().map(|s| s.to_string()).collect(),
+            depth: 0,
+        };
+        parser.statements(false)?;
+        Ok(Self {
+            ops: parser.ops,
+            vars: parser.vars,
+        })
+    }
+    /// Number of bytecode instructions
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
+
+// Hue (wrapping), saturation and value 0.0..=1.0
+fn hsv(h: f32, s: f32, v: f32) -> Rgb {
+    let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+    let h = h.rem_euclid(1.0) * 6.0;
+    let f = |n: f32| {
+        let k = (n + h) % 6.0;
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    Rgb::from_f32((f(5.0), f(3.0), f(1.0)))
+}
+
+fn truth(b: bool) -> f32 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+// Pixel inputs
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pixel {
+    pub x: i32,
+    pub y: i32,
+    pub t: f32,
+    pub size: (i32, i32),
+}
+
+// Pattern interpreter (variables persist between pixels and frames)
+#[derive(Clone, Debug)]
+pub struct Vm {
+    program: Program,
+    globals: Vec<f32>,
+    stack: Vec<f32>,
+    rng: Rng,
+}
+
+impl Vm {
+    pub fn new(program: Program) -> Self {
+        Self {
+            globals: vec![0.0; program.vars.len()],
+            stack: Vec::with_capacity(STACK_SIZE),
+            program,
+            rng: Rng::default(),
+        }
+    }
+    /// Variable value by name
+    pub fn get(&self, name: &str) -> Option<f32> {
+        let slot = self.program.vars.iter().position(|v| v == name)?;
+        Some(self.globals[slot])
+    }
+    fn push(&mut self, v: f32) -> Result<()> {
+        if self.stack.len() >= STACK_SIZE {
+            bail!("Stack overflow");
+        }
+        self.stack.push(v);
+        Ok(())
+    }
+    fn pop(&mut self) -> f32 {
+        self.stack.pop().unwrap_or(0.0)
+    }
+    /// Run program for pixel - steps is decremented by instructions run
+    pub fn pixel(&mut self, pixel: Pixel, palette: &Palette, steps: &mut u32) -> Result<Rgb> {
+        let (w, h) = pixel.size;
+        let inputs = [
+            pixel.x as f32,
+            pixel.y as f32,
+            pixel.x as f32 / (w - 1).max(1) as f32,
+            pixel.y as f32 / (h - 1).max(1) as f32,
+            pixel.t,
+            w as f32,
+            h as f32,
+            (pixel.y * w + pixel.x) as f32,
+        ];
+        self.globals[..inputs.len()].copy_from_slice(&inputs);
+        self.stack.clear();
+        let (mut colour, mut result) = (None, None);
+        let mut pc = 0;
+        while let Some(&op) = self.program.ops.get(pc) {
+            if *steps == 0 {
+                bail!("Instruction limit exceeded");
+            }
+            *steps -= 1;
+            pc += 1;
+            let v = match op {
+                Op::Num(n) => n,
+                Op::Load(slot) => self.globals[slot],
+                Op::Store(slot) => {
+                    self.globals[slot] = self.pop();
+                    continue;
+                }
+                Op::Result => {
+                    result = Some(self.pop());
+                    continue;
+                }
+                Op::Jump(target) => {
+                    pc = target;
+                    continue;
+                }
+                Op::JumpIfZero(target) => {
+                    if self.pop() == 0.0 {
+                        pc = target;
+                    }
+                    continue;
+                }
+                Op::Neg => -self.pop(),
+                Op::Not => truth(self.pop() == 0.0),
+                Op::Call(builtin) => self.call(builtin, palette, &mut colour),
+                op => {
+                    let (b, a) = (self.pop(), self.pop());
+                    match op {
+                        Op::Add => a + b,
+                        Op::Sub => a - b,
+                        Op::Mul => a * b,
+                        Op::Div => a / b,
+                        Op::Mod => a.rem_euclid(b),
+                        Op::Lt => truth(a < b),
+                        Op::Le => truth(a <= b),
+                        Op::Gt => truth(a > b),
+                        Op::Ge => truth(a >= b),
+                        Op::Eq => truth(a == b),
+                        Op::Ne => truth(a != b),
+                        _ => unreachable!(),
+                    }
+                }
+            };
+            self.push(v)?;
+        }
+        Ok(colour.unwrap_or_else(|| result.map_or(OFF, |v| palette.cycle(v))))
+    }
+    fn call(&mut self, builtin: Builtin, palette: &Palette, colour: &mut Option<Rgb>) -> f32 {
+        let mut args = [0.0; 3];
+        let arity = BUILTINS.iter().find(|b| b.1 == builtin).map_or(0, |b| b.2);
+        for i in (0..arity).rev() {
+            args[i] = self.pop();
+        }
+        let [a, b, c] = args;
+        match builtin {
+            Builtin::Sin => a.sin(),
+            Builtin::Cos => a.cos(),
+            Builtin::Tan => a.tan(),
+            Builtin::Atan2 => a.atan2(b),
+            Builtin::Abs => a.abs(),
+            Builtin::Floor => a.floor(),
+            Builtin::Ceil => a.ceil(),
+            Builtin::Frac => a.rem_euclid(1.0),
+            Builtin::Sqrt => a.sqrt(),
+            Builtin::Pow => a.powf(b),
+            Builtin::Min => a.min(b),
+            Builtin::Max => a.max(b),
+            Builtin::Clamp => a.max(b).min(c),
+            Builtin::Mix => a + (b - a) * c,
+            Builtin::Wave => 0.5 - 0.5 * (a * TAU).cos(),
+            Builtin::Triangle => 1.0 - (2.0 * a.rem_euclid(1.0) - 1.0).abs(),
+            Builtin::Square => truth(a.rem_euclid(1.0) < b),
+            Builtin::Time => (self.globals[4] / a).rem_euclid(1.0),
+            Builtin::Noise => effect::value_noise(a, b, c),
+            Builtin::Random => self.rng.f32(),
+            Builtin::Palette => {
+                *colour = Some(palette.cycle(a));
+                0.0
+            }
+            Builtin::Hsv => {
+                *colour = Some(hsv(a, b, c));
+                0.0
+            }
+            Builtin::Rgb => {
+                *colour = Some(Rgb::from_f32((a, b, c)));
+                0.0
+            }
+        }
+    }
+    /// Draw frame at time t seconds (limited to FRAME_STEP_LIMIT instructions)
+    pub fn draw(&mut self, t: f32, palette: &Palette, canvas: &mut dyn Canvas) -> Result<()> {
+        let size = canvas.size();
+        let mut steps = FRAME_STEP_LIMIT;
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let rgb = self.pixel(Pixel { x, y, t, size }, palette, &mut steps)?;
+                canvas.set((x, y), rgb);
+            }
+        }
+        Ok(())
+    }
+}
+
+// Pattern run as an effect (stopped with display cleared on error)
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    vm: Vm,
+    clock: Clock,
+    failed: bool,
+}
+
+impl Pattern {
+    pub fn compile(source: &str) -> Result<Self> {
+        Ok(Self {
+            vm: Vm::new(Program::compile(source)?),
+            clock: Clock::default(),
+            failed: false,
+        })
+    }
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+}
+
+impl Effect for Pattern {
+    fn name(&self) -> &'static str {
+        "pattern"
+    }
+    fn render(&mut self, elapsed_ms: u32, params: &EffectParams, canvas: &mut dyn Canvas) {
+        if self.failed {
+            return;
+        }
+        self.clock.advance(elapsed_ms, params.speed);
+        if let Err(e) = self.vm.draw(self.clock.seconds(), &params.palette, canvas) {
+            log::error!("Pattern error: {}", e);
+            self.failed = true;
+            let (w, h) = canvas.size();
+            canvas.fill_rect((0, 0), (w, h), Paint::Solid(OFF));
+        }
+    }
+}
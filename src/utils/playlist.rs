@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+use crate::effect::Rng;
+
+// Shown for entries without a duration (or a repeat count the scene can't use)
+const DEFAULT_DURATION_S: u32 = 10;
+
+// Playlist scene (converted to a Message when shown)
+//
+// Colours are "#rrggbb" strings so playlists are easy to edit as JSON
+//
+//   {"type": "scroll", "text": "Hello", "colour": "#00ff00", "speed": 30}
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SceneSpec {
+    Message {
+        text: String,
+        colour: String,
+    },
+    Scroll {
+        text: String,
+        colour: String,
+        #[serde(default = "default_speed")]
+        speed: f32, // pixels per second
+    },
+    Clock {
+        colour: String,
+        #[serde(default)]
+        format: String,
+        #[serde(default)]
+        hour12: bool,
+        #[serde(default)]
+        utc_offset_min: i32,
+    },
+    Effect {
+        name: String,
+        #[serde(default)]
+        palette: Option<String>,
+        #[serde(default)]
+        speed: Option<f32>,
+    },
+    Image {
+        name: String, // Built-in image (see image::BUILTIN_IMAGES)
+    },
+    Pattern {
+        source: String, // See pattern module
+        #[serde(default)]
+        palette: Option<String>,
+        #[serde(default)]
+        speed: Option<f32>,
+    },
+}
+
+fn default_speed() -> f32 {
+    40.0
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl SceneSpec {
+    /// Scene has passes that a repeat count applies to
+    pub fn is_cyclic(&self) -> bool {
+        matches!(self, SceneSpec::Scroll { .. })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub scene: SceneSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_s: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<u32>, // Scroll passes
+}
+
+impl Entry {
+    /// Entry has been shown long enough (whichever limit is reached first)
+    pub fn is_done(&self, elapsed_ms: u64, cycles: u32) -> bool {
+        let repeat = self.repeat.filter(|_| self.scene.is_cyclic());
+        let duration_s = match (self.duration_s, repeat) {
+            (None, None) => Some(DEFAULT_DURATION_S),
+            (duration_s, _) => duration_s,
+        };
+        duration_s.is_some_and(|s| elapsed_ms >= s as u64 * 1000)
+            || repeat.is_some_and(|n| cycles >= n)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Playlist {
+    pub name: String,
+    pub entries: Vec<Entry>,
+    #[serde(rename = "loop", default = "default_true")]
+    pub looping: bool, // Otherwise last scene is held
+    #[serde(default)]
+    pub shuffle: bool, // Reshuffled on each pass
+}
+
+// Steps through playlist entries as time passes
+#[derive(Clone, Debug)]
+pub struct PlaylistPlayer {
+    playlist: Playlist,
+    order: Vec<usize>,
+    position: usize,
+    elapsed_ms: u64,
+    finished: bool,
+    rng: Rng,
+}
+
+impl PlaylistPlayer {
+    pub fn new(playlist: Playlist, seed: u32) -> Self {
+        let mut player = Self {
+            order: (0..playlist.entries.len()).collect(),
+            playlist,
+            position: 0,
+            elapsed_ms: 0,
+            finished: false,
+            rng: Rng::new(seed),
+        };
+        player.shuffle();
+        player
+    }
+    // Fisher-Yates shuffle (avoiding showing the same entry twice in a row)
+    fn shuffle(&mut self) {
+        if !self.playlist.shuffle {
+            return;
+        }
+        let last = self.order.last().copied();
+        for i in (1..self.order.len()).rev() {
+            let j = self.rng.next_u32() as usize % (i + 1);
+            self.order.swap(i, j);
+        }
+        if self.order.len() > 1 && self.order.first().copied() == last {
+            self.order.swap(0, 1);
+        }
+    }
+    pub fn playlist(&self) -> &Playlist {
+        &self.playlist
+    }
+    /// Index of current entry in playlist
+    pub fn index(&self) -> Option<usize> {
+        self.order.get(self.position).copied()
+    }
+    pub fn current(&self) -> Option<&Entry> {
+        self.index().map(|i| &self.playlist.entries[i])
+    }
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// Advance by elapsed_ms given passes the current scene has completed -
+    /// returns the next entry when it should be shown
+    pub fn tick(&mut self, elapsed_ms: u32, cycles: u32) -> Option<&Entry> {
+        if self.finished {
+            return None;
+        }
+        self.elapsed_ms += elapsed_ms as u64;
+        if !self.current()?.is_done(self.elapsed_ms, cycles) {
+            return None;
+        }
+        self.elapsed_ms = 0;
+        self.position += 1;
+        if self.position == self.order.len() {
+            if !self.playlist.looping {
+                self.finished = true;
+                return None;
+            }
+            self.position = 0;
+            self.shuffle();
+        }
+        self.current()
+    }
+}
@@ -0,0 +1,130 @@
+// Render framebuffers on the host (terminal/image files)
+//
+// Only depends on std/anyhow/png so is also used by the host crate for
+// snapshot tests (see host/)
+use anyhow::Result;
+use std::fmt::Write;
+
+use crate::draw::Canvas;
+use crate::matrix_1d::{HEIGHT, WIDTH};
+use crate::rgb::{Rgb, OFF};
+
+// Plain framebuffer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: i32,
+    height: i32,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    pub fn new((width, height): (i32, i32)) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Self {
+            width,
+            height,
+            pixels: vec![OFF; (width * height) as usize],
+        }
+    }
+    /// Copy of canvas contents
+    pub fn from_canvas(canvas: &(impl Canvas + ?Sized)) -> Self {
+        let mut frame = Self::new(canvas.size());
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                frame.set((x, y), canvas.get((x, y)).unwrap_or(OFF));
+            }
+        }
+        frame
+    }
+    /// LED data stream (eg. Matrix1D::iter) laid out as it appears on
+    /// panels that are all mounted North - shows the effect of Orientation
+    pub fn from_leds(leds: impl Iterator<Item = Rgb>, panels: usize) -> Self {
+        let mut frame = Self::new(((panels * WIDTH) as i32, HEIGHT as i32));
+        for (i, rgb) in leds.take(panels * WIDTH * HEIGHT).enumerate() {
+            let (panel, i) = (i / (WIDTH * HEIGHT), i % (WIDTH * HEIGHT));
+            let (x, y) = (panel * WIDTH + i % WIDTH, i / WIDTH);
+            frame.set((x as i32, y as i32), rgb);
+        }
+        frame
+    }
+}
+
+impl Canvas for Frame {
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+    fn get(&self, (x, y): (i32, i32)) -> Option<Rgb> {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            Some(self.pixels[(x + y * self.width) as usize])
+        } else {
+            None
+        }
+    }
+    fn set(&mut self, (x, y): (i32, i32), rgb: Rgb) {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            self.pixels[(x + y * self.width) as usize] = rgb;
+        }
+    }
+}
+
+/// Truecolor ANSI text using half blocks (two pixel rows per line)
+pub fn to_ansi(canvas: &(impl Canvas + ?Sized)) -> String {
+    let (w, h) = canvas.size();
+    let mut out = String::new();
+    for y in (0..h).step_by(2) {
+        for x in 0..w {
+            let top = canvas.get((x, y)).unwrap_or(OFF);
+            let bottom = canvas.get((x, y + 1)).unwrap_or(OFF);
+            let _ = write!(
+                out,
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                top.r(),
+                top.g(),
+                top.b(),
+                bottom.r(),
+                bottom.g(),
+                bottom.b()
+            );
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+// Packed RGB bytes with each pixel repeated scale x scale times
+fn to_rgb_bytes(canvas: &(impl Canvas + ?Sized), scale: usize) -> (usize, usize, Vec<u8>) {
+    let (w, h) = canvas.size();
+    let scale = scale.max(1);
+    let (sw, sh) = (w.max(0) as usize * scale, h.max(0) as usize * scale);
+    let mut data = Vec::with_capacity(sw * sh * 3);
+    for y in 0..sh {
+        for x in 0..sw {
+            let rgb = canvas
+                .get(((x / scale) as i32, (y / scale) as i32))
+                .unwrap_or(OFF);
+            data.extend_from_slice(&[rgb.r(), rgb.g(), rgb.b()]);
+        }
+    }
+    (sw, sh, data)
+}
+
+/// Binary PPM (P6) image - each pixel is drawn as scale x scale block
+pub fn to_ppm(canvas: &(impl Canvas + ?Sized), scale: usize) -> Vec<u8> {
+    let (w, h, data) = to_rgb_bytes(canvas, scale);
+    let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+    out.extend_from_slice(&data);
+    out
+}
+
+/// PNG image - each pixel is drawn as scale x scale block
+pub fn to_png(canvas: &(impl Canvas + ?Sized), scale: usize) -> Result<Vec<u8>> {
+    let (w, h, data) = to_rgb_bytes(canvas, scale);
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, w as u32, h as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&data)?;
+    }
+    Ok(out)
+}
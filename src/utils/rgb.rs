@@ -1,4 +1,5 @@
 use anyhow::{bail, Result};
+use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RgbLayout {
@@ -14,7 +15,19 @@ pub enum RgbTransform {
     FillThreshold(Rgb, f32),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    Normal,
+    Alpha(f32),
+    Add,
+    Subtract,
+    Multiply,
+    Screen,
+    Lighten,
+    Darken,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Rgb {
     r: u8,
     g: u8,
@@ -32,7 +45,7 @@ pub const WHITE: Rgb = Rgb {
 };
 
 impl Rgb {
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
     /// Converts hue, saturation, value to RGB
@@ -82,6 +95,53 @@ impl Rgb {
             RgbLayout::Grb => ((self.g as u32) << 16) | ((self.r as u32) << 8) | self.b as u32,
         }
     }
+    #[inline]
+    pub fn r(&self) -> u8 {
+        self.r
+    }
+    #[inline]
+    pub fn g(&self) -> u8 {
+        self.g
+    }
+    #[inline]
+    pub fn b(&self) -> u8 {
+        self.b
+    }
+    /// Linear interpolation between self (t=0.0) and other (t=1.0)
+    pub fn lerp(&self, other: Rgb, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Self {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+    /// Blend src onto self (dst) using the given mode
+    pub fn blend(&self, src: Rgb, mode: BlendMode) -> Self {
+        let channel = |d: u8, s: u8| -> u8 {
+            let (d16, s16) = (d as u16, s as u16);
+            match mode {
+                BlendMode::Normal => s,
+                BlendMode::Alpha(_) => s,
+                BlendMode::Add => (d16 + s16).min(255) as u8,
+                BlendMode::Subtract => d.saturating_sub(s),
+                BlendMode::Multiply => ((d16 * s16 + 127) / 255) as u8,
+                BlendMode::Screen => (255 - ((255 - d16) * (255 - s16) + 127) / 255) as u8,
+                BlendMode::Lighten => d.max(s),
+                BlendMode::Darken => d.min(s),
+            }
+        };
+        let out = Self {
+            r: channel(self.r, src.r),
+            g: channel(self.g, src.g),
+            b: channel(self.b, src.b),
+        };
+        match mode {
+            BlendMode::Alpha(a) => self.lerp(out, a),
+            _ => out,
+        }
+    }
     pub fn transform(&self, transforms: &[RgbTransform]) -> Self {
         let (mut r, mut g, mut b) = self.to_f32();
         for t in transforms {
@@ -111,3 +171,16 @@ impl Default for Rgb {
         OFF
     }
 }
+
+// Hex colour (#rrggbb)
+impl FromStr for Rgb {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid colour: {}", s);
+        }
+        let v = u32::from_str_radix(hex, 16)?;
+        Ok(Rgb::new((v >> 16) as u8, (v >> 8) as u8, v as u8))
+    }
+}
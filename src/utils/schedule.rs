@@ -0,0 +1,213 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::clock::DateTime;
+
+// How far back to look for the rule that last fired
+const LOOKBACK_MIN: i64 = 7 * 24 * 60;
+const DAY_MIN: i64 = 24 * 60;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+// Allowed values for one cron field as a bitmask
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Field {
+    mask: u64,
+    any: bool, // Field was '*' (matters for day/weekday)
+}
+
+impl Field {
+    fn parse(s: &str, (min, max): (u32, u32), names: &[&str], name_base: u32) -> Result<Self> {
+        let value = |v: &str| -> Result<u32> {
+            let v = v.to_lowercase();
+            let n = match names.iter().position(|name| *name == v) {
+                Some(i) => i as u32 + name_base,
+                None => v
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid cron value: {}", v))?,
+            };
+            if n < min || n > max {
+                bail!("Cron value out of range: {}", n);
+            }
+            Ok(n)
+        };
+        let mut mask = 0_u64;
+        for part in s.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>()?),
+                None => (part, 1),
+            };
+            if step == 0 {
+                bail!("Invalid cron step: {}", part);
+            }
+            let (lo, hi) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((lo, hi)) => (value(lo)?, value(hi)?),
+                    // Step from single value runs to end of range (eg. 5/15)
+                    None if step > 1 => (value(range)?, max),
+                    None => (value(range)?, value(range)?),
+                },
+            };
+            if lo > hi {
+                bail!("Invalid cron range: {}", part);
+            }
+            for n in (lo..=hi).step_by(step as usize) {
+                mask |= 1 << n;
+            }
+        }
+        Ok(Self {
+            mask,
+            any: s == "*",
+        })
+    }
+    fn matches(&self, n: u32) -> bool {
+        self.mask & (1 << n) != 0
+    }
+    // Highest allowed value <= n
+    fn latest(&self, n: u32) -> Option<u32> {
+        let mask = self.mask & (u64::MAX >> (63 - n.min(63)));
+        (mask != 0).then(|| 63 - mask.leading_zeros())
+    }
+}
+
+// Five field cron expression (minute hour day month weekday)
+//
+//   "0 7 * * mon-fri"   07:00 on weekdays
+//   "*/15 9-17 * * *"   every 15 minutes during the working day
+//
+// Weekday 0 or 7 is Sunday. As with cron, if both day and weekday are
+// restricted either matching will do.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Cron {
+    source: String,
+    minute: Field,
+    hour: Field,
+    day: Field,
+    month: Field,
+    weekday: Field,
+}
+
+impl FromStr for Cron {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let fields = s.split_whitespace().collect::<Vec<_>>();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!("Cron expression needs 5 fields: {}", s);
+        };
+        let mut weekday = Field::parse(weekday, (0, 7), &WEEKDAYS, 0)?;
+        if weekday.matches(7) {
+            weekday.mask |= 1;
+        }
+        Ok(Self {
+            source: fields.join(" "),
+            minute: Field::parse(minute, (0, 59), &[], 0)?,
+            hour: Field::parse(hour, (0, 23), &[], 0)?,
+            day: Field::parse(day, (1, 31), &[], 0)?,
+            month: Field::parse(month, (1, 12), &MONTHS, 1)?,
+            weekday,
+        })
+    }
+}
+
+impl TryFrom<String> for Cron {
+    type Error = anyhow::Error;
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Cron> for String {
+    fn from(cron: Cron) -> Self {
+        cron.source
+    }
+}
+
+impl Cron {
+    /// Expression fires during this minute
+    pub fn matches(&self, t: &DateTime) -> bool {
+        self.matches_day(t) && self.minute.matches(t.minute) && self.hour.matches(t.hour)
+    }
+    fn matches_day(&self, t: &DateTime) -> bool {
+        let day = match (self.day.any, self.weekday.any) {
+            (false, false) => self.day.matches(t.day) || self.weekday.matches(t.weekday),
+            _ => self.day.matches(t.day) && self.weekday.matches(t.weekday),
+        };
+        day && self.month.matches(t.month)
+    }
+    /// Most recent minute the expression fired at or before local_min
+    /// (minutes since epoch) within the lookback window
+    ///
+    /// Works back a day at a time taking the latest hour and minute from
+    /// each field rather than testing every minute.
+    pub fn latest(&self, local_min: i64) -> Option<i64> {
+        let today = local_min.div_euclid(DAY_MIN) * DAY_MIN;
+        let now = (local_min - today) as u32;
+        (0..=LOOKBACK_MIN / DAY_MIN)
+            .map(|back| today - back * DAY_MIN)
+            .filter(|day| self.matches_day(&DateTime::from_unix_ms(day * 60_000)))
+            .find_map(|day| {
+                let (hour, minute) = if day == today {
+                    (now / 60, now % 60)
+                } else {
+                    (23, 59)
+                };
+                // Latest minute this hour, otherwise last minute of an earlier hour
+                let time = match self.hour.latest(hour) {
+                    Some(h) if h == hour => match self.minute.latest(minute) {
+                        Some(m) => Some((h, m)),
+                        None => h
+                            .checked_sub(1)
+                            .and_then(|h| self.hour.latest(h))
+                            .zip(self.minute.latest(59)),
+                    },
+                    h => h.zip(self.minute.latest(59)),
+                };
+                time.map(|(h, m)| day + (h * 60 + m) as i64)
+            })
+            .filter(|&t| local_min - t < LOOKBACK_MIN)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Playlist(String), // Play named playlist
+    Off,
+}
+
+// {"cron": "30 22 * * *", "action": "off"}
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    pub cron: Cron,
+    pub action: Action,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub utc_offset_min: i32, // Rules are in local time
+    pub rules: Vec<Rule>,
+}
+
+impl Schedule {
+    /// Action of the rule that fired most recently (within the last week)
+    ///
+    /// This is what should be showing now - so the display is right after a
+    /// reboot or schedule change, not just when a rule next fires. Later
+    /// rules win if several fire in the same minute.
+    pub fn active_at(&self, unix_ms: i64) -> Option<&Action> {
+        let local_min = (unix_ms + self.utc_offset_min as i64 * 60_000).div_euclid(60_000);
+        self.rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| Some(((rule.cron.latest(local_min)?, i), &rule.action)))
+            .max_by_key(|(key, _)| *key)
+            .map(|(_, action)| action)
+    }
+}
@@ -0,0 +1,161 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ScrollMode {
+    #[default]
+    Left, // Right to left
+    Right,  // Left to right
+    Up,     // Bottom to top
+    Down,   // Top to bottom
+    Bounce, // Back and forth (for text slightly wider than display)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ScrollOptions {
+    pub mode: ScrollMode,
+    pub lead: i32,    // Blank pixels before text enters (margin for Bounce)
+    pub trail: i32,   // Blank pixels after text leaves (margin for Bounce)
+    pub pause: usize, // Extra steps held at each end of scroll
+}
+
+// Positions (x, y) to draw text at for each step of a scroll
+//
+// Text moves one pixel per step from start to end (and back to start
+// for Bounce) with pause steps held at each end
+#[derive(Clone, Debug)]
+pub struct ScrollIter {
+    start: i32,
+    end: i32,
+    cross: i32, // Fixed co-ordinate on other axis
+    vertical: bool,
+    bounce: bool,
+    pause: usize,
+    index: usize,
+}
+
+impl ScrollIter {
+    // Scroll text of size (tw, th) across display of size (dw, dh)
+    pub fn new(options: ScrollOptions, (dw, dh): (i32, i32), (tw, th): (i32, i32)) -> Self {
+        let ScrollOptions {
+            mode,
+            lead,
+            trail,
+            pause,
+        } = options;
+        // Centre text horizontally for vertical scroll if it fits
+        let centre = ((dw - tw) / 2).max(0);
+        let (start, end, cross, vertical) = match mode {
+            ScrollMode::Left => (dw - 1 + lead, -tw - trail, 0, false),
+            ScrollMode::Right => (1 - tw - lead, dw + trail, 0, false),
+            ScrollMode::Up => (dh - 1 + lead, -th - trail, centre, true),
+            ScrollMode::Down => (1 - th - lead, dh + trail, centre, true),
+            ScrollMode::Bounce => (lead, (dw - tw - trail).min(lead), 0, false),
+        };
+        Self {
+            start,
+            end,
+            cross,
+            vertical,
+            bounce: mode == ScrollMode::Bounce,
+            pause,
+            index: 0,
+        }
+    }
+    /// Number of steps in one complete scroll
+    pub fn steps(&self) -> usize {
+        let travel = self.travel();
+        let one_way = self.pause * 2 + travel + 1;
+        if self.bounce {
+            one_way + travel.saturating_sub(1)
+        } else {
+            one_way
+        }
+    }
+    fn travel(&self) -> usize {
+        (self.end - self.start).unsigned_abs() as usize
+    }
+    // Offset along scroll axis at step i
+    fn offset(&self, i: usize) -> i32 {
+        let dir = (self.end - self.start).signum();
+        let travel = self.travel();
+        if i < self.pause {
+            self.start
+        } else if i - self.pause <= travel {
+            self.start + dir * (i - self.pause) as i32
+        } else if i < self.pause * 2 + travel + 1 {
+            self.end
+        } else {
+            // Return leg (Bounce only)
+            self.end - dir * (i - (self.pause * 2 + travel)) as i32
+        }
+    }
+    // Position at step i
+    fn point(&self, i: usize) -> (i32, i32) {
+        let offset = self.offset(i);
+        if self.vertical {
+            (self.cross, offset)
+        } else {
+            (offset, self.cross)
+        }
+    }
+}
+
+impl Iterator for ScrollIter {
+    type Item = (i32, i32);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.steps() {
+            return None;
+        }
+        let point = self.point(self.index);
+        self.index += 1;
+        Some(point)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.steps().saturating_sub(self.index);
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for ScrollIter {}
+
+// Fractional scroll positions moving at speed pixels per second
+//
+// Follows the same path as ScrollIter (including pauses) but interpolates
+// between steps so text can be drawn with Matrix1D::draw_str_smooth
+#[derive(Clone, Debug)]
+pub struct SmoothScroll {
+    path: ScrollIter,
+    speed: f32,
+    step: f32, // Fractional step along path
+}
+
+impl SmoothScroll {
+    pub fn new(path: ScrollIter, speed: f32) -> Self {
+        Self {
+            path,
+            speed: speed.max(0.0),
+            step: 0.0,
+        }
+    }
+    /// Current position (None when scroll has finished)
+    pub fn position(&self) -> Option<(f32, f32)> {
+        let last = self.path.steps().saturating_sub(1);
+        let i = self.step as usize;
+        if i > last || (i == last && self.step.fract() > 0.0) {
+            return None;
+        }
+        let (x0, y0) = self.path.point(i);
+        let (x1, y1) = self.path.point((i + 1).min(last));
+        let t = self.step.fract();
+        Some((
+            x0 as f32 + (x1 - x0) as f32 * t,
+            y0 as f32 + (y1 - y0) as f32 * t,
+        ))
+    }
+    /// Advance by elapsed time and return new position
+    pub fn tick(&mut self, elapsed_ms: u32) -> Option<(f32, f32)> {
+        self.step += self.speed * elapsed_ms as f32 / 1000.0;
+        self.position()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.position().is_none()
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{bail, Result};
+
+use crate::draw::Canvas;
+use crate::rgb::{BlendMode, Rgb};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transparency {
+    Opaque,
+    Key(Rgb),       // Pixels matching colour are not drawn
+    Alpha(Vec<u8>), // Per pixel alpha (0 = transparent, 255 = opaque)
+}
+
+// Options applied when blitting sprite
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Blit {
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub tint: Option<Rgb>, // Multiplied with sprite colour
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprite {
+    width: i32,
+    height: i32,
+    pixels: Vec<Rgb>,
+    transparency: Transparency,
+}
+
+impl Sprite {
+    pub fn new(
+        (width, height): (i32, i32),
+        pixels: Vec<Rgb>,
+        transparency: Transparency,
+    ) -> Result<Self> {
+        let len = (width.max(0) * height.max(0)) as usize;
+        if pixels.len() != len {
+            bail!(
+                "Sprite {}x{} expects {} pixels (got {})",
+                width,
+                height,
+                len,
+                pixels.len()
+            );
+        }
+        if let Transparency::Alpha(alpha) = &transparency {
+            if alpha.len() != len {
+                bail!(
+                    "Sprite {}x{} expects {} alpha values (got {})",
+                    width,
+                    height,
+                    len,
+                    alpha.len()
+                );
+            }
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+            transparency,
+        })
+    }
+    /// Build sprite from function returning (colour, alpha) for each pixel
+    pub fn from_fn(
+        (width, height): (i32, i32),
+        mut f: impl FnMut((i32, i32)) -> (Rgb, u8),
+    ) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        let len = (width * height) as usize;
+        let (mut pixels, mut alpha) = (Vec::with_capacity(len), Vec::with_capacity(len));
+        for y in 0..height {
+            for x in 0..width {
+                let (rgb, a) = f((x, y));
+                pixels.push(rgb);
+                alpha.push(a);
+            }
+        }
+        let transparency = if alpha.iter().all(|&a| a == 255) {
+            Transparency::Opaque
+        } else {
+            Transparency::Alpha(alpha)
+        };
+        Self {
+            width,
+            height,
+            pixels,
+            transparency,
+        }
+    }
+    /// Parse char-art rows using colourmap - chars not in colourmap are
+    /// transparent (short rows are padded with transparent pixels)
+    pub fn from_char_art(rows: &[&str], colourmap: &[(char, Rgb)]) -> Self {
+        let rows: Vec<Vec<char>> = rows.iter().map(|r| r.chars().collect()).collect();
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        Self::from_fn((width as i32, rows.len() as i32), |(x, y)| {
+            match rows[y as usize]
+                .get(x as usize)
+                .and_then(|c| colourmap.iter().find(|(key, _)| key == c))
+            {
+                Some((_, rgb)) => (*rgb, 255),
+                None => (Rgb::default(), 0),
+            }
+        })
+    }
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+    pub fn transparency(&self) -> &Transparency {
+        &self.transparency
+    }
+    /// Returns (colour, alpha) for pixel
+    pub fn pixel(&self, (x, y): (i32, i32)) -> Option<(Rgb, u8)> {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            let i = (x + y * self.width) as usize;
+            let rgb = self.pixels[i];
+            let alpha = match &self.transparency {
+                Transparency::Opaque => 255,
+                Transparency::Key(key) if rgb == *key => 0,
+                Transparency::Key(_) => 255,
+                Transparency::Alpha(alpha) => alpha[i],
+            };
+            Some((rgb, alpha))
+        } else {
+            None
+        }
+    }
+    /// Set (colour, alpha) for pixel (switches to per pixel alpha if needed)
+    pub fn set_pixel(&mut self, (x, y): (i32, i32), (rgb, alpha): (Rgb, u8)) {
+        if !((0..self.width).contains(&x) && (0..self.height).contains(&y)) {
+            return;
+        }
+        let fits = match self.transparency {
+            Transparency::Opaque => alpha == 255,
+            Transparency::Key(key) => (alpha == 0 && rgb == key) || (alpha == 255 && rgb != key),
+            Transparency::Alpha(_) => true,
+        };
+        if !fits {
+            let w = self.width;
+            let alpha = (0..self.height)
+                .flat_map(|y| (0..w).map(move |x| (x, y)))
+                .map(|p| self.pixel(p).map_or(0, |(_, a)| a))
+                .collect();
+            self.transparency = Transparency::Alpha(alpha);
+        }
+        let i = (x + y * self.width) as usize;
+        self.pixels[i] = rgb;
+        if let Transparency::Alpha(a) = &mut self.transparency {
+            a[i] = alpha;
+        }
+    }
+    /// Draw sprite with top-left corner at (x1, y1) - clipped to canvas
+    pub fn blit(&self, canvas: &mut (impl Canvas + ?Sized), (x1, y1): (i32, i32), opts: Blit) {
+        let (w, h) = canvas.size();
+        // Only visit pixels that land on the canvas
+        for y in y1.saturating_neg().max(0)..self.height.min(h.saturating_sub(y1)) {
+            for x in x1.saturating_neg().max(0)..self.width.min(w.saturating_sub(x1)) {
+                let sx = if opts.flip_x { self.width - 1 - x } else { x };
+                let sy = if opts.flip_y { self.height - 1 - y } else { y };
+                let (rgb, alpha) = match self.pixel((sx, sy)) {
+                    Some((_, 0)) | None => continue,
+                    Some(p) => p,
+                };
+                let rgb = match opts.tint {
+                    Some(tint) => rgb.blend(tint, BlendMode::Multiply),
+                    None => rgb,
+                };
+                let p = (x1 + x, y1 + y);
+                if alpha == 255 {
+                    canvas.set(p, rgb);
+                } else if let Some(dst) = canvas.get(p) {
+                    canvas.set(p, dst.lerp(rgb, alpha as f32 / 255.0));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+use crate::rgb::{Rgb, RED};
+
+// Pausable elapsed time (times are monotonic ms from caller's clock)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stopwatch {
+    accumulated_ms: u64,
+    started_ms: Option<u64>,
+}
+
+impl Stopwatch {
+    pub fn started(now_ms: u64) -> Self {
+        Self {
+            accumulated_ms: 0,
+            started_ms: Some(now_ms),
+        }
+    }
+    pub fn start(&mut self, now_ms: u64) {
+        if self.started_ms.is_none() {
+            self.started_ms = Some(now_ms);
+        }
+    }
+    pub fn pause(&mut self, now_ms: u64) {
+        self.accumulated_ms = self.elapsed_ms(now_ms);
+        self.started_ms = None;
+    }
+    /// Stop and clear elapsed time
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+    pub fn is_running(&self) -> bool {
+        self.started_ms.is_some()
+    }
+    pub fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        self.accumulated_ms + self.started_ms.map_or(0, |s| now_ms.saturating_sub(s))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Countdown {
+    Duration(u64), // ms (pausable)
+    Until(i64),    // Unix time ms (needs synchronised system clock)
+}
+
+impl Countdown {
+    /// Remaining time (None if target time is unknown)
+    pub fn remaining_ms(
+        &self,
+        stopwatch: &Stopwatch,
+        now_ms: u64,
+        unix_ms: Option<i64>,
+    ) -> Option<u64> {
+        match self {
+            Countdown::Duration(ms) => Some(ms.saturating_sub(stopwatch.elapsed_ms(now_ms))),
+            Countdown::Until(target) => unix_ms.map(|now| (target - now).max(0) as u64),
+        }
+    }
+}
+
+// Shown when countdown reaches zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alert {
+    pub colour: Option<Rgb>, // None keeps display colour
+    pub flash: bool,
+}
+
+impl Default for Alert {
+    fn default() -> Self {
+        Self {
+            colour: Some(RED),
+            flash: true,
+        }
+    }
+}
+
+impl Alert {
+    /// Alert colour at now_ms (None when flashed off)
+    pub fn colour(&self, rgb: Rgb, now_ms: u64) -> Option<Rgb> {
+        if self.flash && (now_ms / 500) % 2 == 1 {
+            None
+        } else {
+            Some(self.colour.unwrap_or(rgb))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerControl {
+    Start,
+    Pause,
+    Reset,
+    Increment(i64),
+    Decrement(i64),
+}
+
+// Control name with optional step (eg. "start", "increment:5")
+impl FromStr for TimerControl {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, step) = match s.split_once(':') {
+            Some((name, step)) => (name, step.trim().parse::<i64>()?),
+            None => (s, 1),
+        };
+        match name.trim().to_lowercase().as_str() {
+            "start" => Ok(TimerControl::Start),
+            "pause" | "stop" => Ok(TimerControl::Pause),
+            "reset" => Ok(TimerControl::Reset),
+            "increment" | "inc" => Ok(TimerControl::Increment(step)),
+            "decrement" | "dec" => Ok(TimerControl::Decrement(step)),
+            _ => Err(anyhow!("Unknown control: {}", name)),
+        }
+    }
+}
+
+/// Format duration as M:SS, H:MM:SS or DdHHh
+///
+/// Countdowns round up so zero is only shown once finished
+pub fn format_duration(ms: u64, round_up: bool) -> String {
+    let s = if round_up {
+        ms.div_ceil(1000)
+    } else {
+        ms / 1000
+    };
+    let (d, h, m, s) = (s / 86_400, s / 3600 % 24, s / 60 % 60, s % 60);
+    if d > 0 {
+        format!("{d}d{h:02}h")
+    } else if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Parse "HH:MM" (24 hour) time of day
+pub fn parse_time_of_day(s: &str) -> Result<(u32, u32)> {
+    let (h, m) = s
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Invalid time: {}", s))?;
+    match (h.trim().parse()?, m.trim().parse()?) {
+        (hour @ 0..=23, minute @ 0..=59) => Ok((hour, minute)),
+        _ => Err(anyhow!("Invalid time: {}", s)),
+    }
+}
+
+/// Unix time ms of next hour:minute local time after now
+pub fn next_time_of_day(now_unix_ms: i64, utc_offset_min: i32, (hour, minute): (u32, u32)) -> i64 {
+    const DAY_MS: i64 = 86_400_000;
+    let local = now_unix_ms + utc_offset_min as i64 * 60_000;
+    let midnight = local - local.rem_euclid(DAY_MS);
+    let mut target = midnight + (hour as i64 * 60 + minute as i64) * 60_000;
+    if target <= local {
+        target += DAY_MS;
+    }
+    target - utc_offset_min as i64 * 60_000
+}
@@ -0,0 +1,186 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+use crate::draw::Canvas;
+use crate::easing::Easing;
+use crate::rgb::{Rgb, OFF};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TransitionKind {
+    #[default]
+    Cut, // Switch at end
+    Crossfade,
+    Wipe(Direction),  // Edge moves in direction uncovering new frame
+    Slide(Direction), // New frame pushes old frame out in direction
+    Dissolve,         // Pixels switch in random order
+    Pixelate,         // Old frame breaks into blocks which resolve into new frame
+}
+
+// Transition between two frames
+//
+// Frames are only read so sources (text, effects, images) can keep
+// animating while the transition runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub duration_ms: u32,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub fn new(kind: TransitionKind, duration_ms: u32, easing: Easing) -> Self {
+        Self {
+            kind,
+            duration_ms,
+            easing,
+        }
+    }
+    /// Eased progress after elapsed_ms
+    pub fn progress(&self, elapsed_ms: u32) -> f32 {
+        if self.duration_ms == 0 {
+            1.0
+        } else {
+            self.easing
+                .apply(elapsed_ms as f32 / self.duration_ms as f32)
+        }
+    }
+    pub fn is_finished(&self, elapsed_ms: u32) -> bool {
+        elapsed_ms >= self.duration_ms
+    }
+    /// Draw transition frame after elapsed_ms into out
+    pub fn render(
+        &self,
+        from: &dyn Canvas,
+        to: &dyn Canvas,
+        elapsed_ms: u32,
+        out: &mut dyn Canvas,
+    ) {
+        blend(self.kind, from, to, self.progress(elapsed_ms), out);
+    }
+}
+
+// Position in dissolve order for pixel (0.0..1.0)
+fn dissolve_threshold((x, y): (i32, i32)) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x9e3779b1) ^ (y as u32).wrapping_mul(0x85ebca77);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b3c6d);
+    h ^= h >> 12;
+    (h & 0xffff) as f32 / 65536.0
+}
+
+// Average colour of block containing (x, y)
+fn block_average(canvas: &dyn Canvas, (x, y): (i32, i32), block: i32) -> Rgb {
+    let (bx, by) = (x - x.rem_euclid(block), y - y.rem_euclid(block));
+    let (mut sum, mut n) = ((0_u32, 0_u32, 0_u32), 0_u32);
+    for y in by..by + block {
+        for x in bx..bx + block {
+            if let Some(rgb) = canvas.get((x, y)) {
+                sum = (
+                    sum.0 + rgb.r() as u32,
+                    sum.1 + rgb.g() as u32,
+                    sum.2 + rgb.b() as u32,
+                );
+                n += 1;
+            }
+        }
+    }
+    match n {
+        0 => OFF,
+        n => Rgb::new((sum.0 / n) as u8, (sum.1 / n) as u8, (sum.2 / n) as u8),
+    }
+}
+
+/// Draw transition at eased progress t (0.0 = from, 1.0 = to) into out
+pub fn blend(
+    kind: TransitionKind,
+    from: &dyn Canvas,
+    to: &dyn Canvas,
+    t: f32,
+    out: &mut dyn Canvas,
+) {
+    let (w, h) = out.size();
+    let src = |canvas: &dyn Canvas, p: (i32, i32)| canvas.get(p).unwrap_or(OFF);
+    // Pixelate block size (grows to size of display then shrinks)
+    let block = 1 + ((1.0 - (2.0 * t - 1.0).abs()) * (h.max(1) - 1) as f32).round() as i32;
+    for y in 0..h {
+        for x in 0..w {
+            let (xf, yf) = (x as f32, y as f32);
+            let (wf, hf) = (w as f32, h as f32);
+            let rgb = match kind {
+                TransitionKind::Cut if t < 1.0 => src(from, (x, y)),
+                TransitionKind::Cut => src(to, (x, y)),
+                TransitionKind::Crossfade => src(from, (x, y)).lerp(src(to, (x, y)), t),
+                TransitionKind::Wipe(direction) => {
+                    // Fraction of pixel uncovered (gives soft edge)
+                    let covered = match direction {
+                        Direction::Left => xf + 1.0 - wf * (1.0 - t),
+                        Direction::Right => wf * t - xf,
+                        Direction::Up => yf + 1.0 - hf * (1.0 - t),
+                        Direction::Down => hf * t - yf,
+                    };
+                    src(from, (x, y)).lerp(src(to, (x, y)), covered.clamp(0.0, 1.0))
+                }
+                TransitionKind::Slide(direction) => {
+                    let (dx, dy) = match direction {
+                        Direction::Left => ((wf * t).round() as i32, 0),
+                        Direction::Right => (-(wf * t).round() as i32, 0),
+                        Direction::Up => (0, (hf * t).round() as i32),
+                        Direction::Down => (0, -(hf * t).round() as i32),
+                    };
+                    let (sx, sy) = (x + dx, y + dy);
+                    if (0..w).contains(&sx) && (0..h).contains(&sy) {
+                        src(from, (sx, sy))
+                    } else {
+                        // New frame follows on from edge of old frame
+                        src(to, (sx - dx.signum() * w, sy - dy.signum() * h))
+                    }
+                }
+                TransitionKind::Dissolve => {
+                    if dissolve_threshold((x, y)) < t {
+                        src(to, (x, y))
+                    } else {
+                        src(from, (x, y))
+                    }
+                }
+                TransitionKind::Pixelate => {
+                    let source = if t < 0.5 { from } else { to };
+                    block_average(source, (x, y), block)
+                }
+            };
+            out.set((x, y), rgb);
+        }
+    }
+}
+
+// Transition name (eg. "wipe-left", "crossfade")
+impl FromStr for TransitionKind {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.to_lowercase();
+        let (name, direction) = s.split_once('-').unwrap_or((&s, "left"));
+        let direction = match direction {
+            "left" => Direction::Left,
+            "right" => Direction::Right,
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            _ => return Err(anyhow!("Unknown direction: {}", direction)),
+        };
+        match name {
+            "cut" => Ok(TransitionKind::Cut),
+            "crossfade" => Ok(TransitionKind::Crossfade),
+            "wipe" => Ok(TransitionKind::Wipe(direction)),
+            "slide" => Ok(TransitionKind::Slide(direction)),
+            "dissolve" => Ok(TransitionKind::Dissolve),
+            "pixelate" => Ok(TransitionKind::Pixelate),
+            _ => Err(anyhow!("Unknown transition: {}", name)),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::cell::Cell;
+use std::time::Instant;
+
+use crate::draw::Canvas;
+use crate::easing::Easing;
+use crate::rgb::{Rgb, WHITE};
+use crate::sprite::{Blit, Sprite};
+
+// Monotonic time source for animations
+//
+// Animations only see time through this so they can be driven by a
+// FakeClock in tests
+pub trait Clock {
+    fn now_ms(&self) -> u64;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+}
+
+// Manually advanced clock
+#[derive(Clone, Debug, Default)]
+pub struct FakeClock {
+    now: Cell<u64>,
+}
+
+impl FakeClock {
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            now: Cell::new(now_ms),
+        }
+    }
+    pub fn advance(&self, ms: u64) {
+        self.now.set(self.now.get() + ms);
+    }
+    pub fn set(&self, now_ms: u64) {
+        self.now.set(now_ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now_ms(&self) -> u64 {
+        self.now.get()
+    }
+}
+
+// Value which can be interpolated between keyframes
+//
+// t is eased progress so may be outside 0.0..=1.0 for overshooting easings
+pub trait Tweenable: Copy {
+    fn tween(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Tweenable for (f32, f32) {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        (self.0.tween(&to.0, t), self.1.tween(&to.1, t))
+    }
+}
+
+impl Tweenable for (i32, i32) {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        let (x, y) = (self.0 as f32, self.1 as f32).tween(&(to.0 as f32, to.1 as f32), t);
+        (x.round() as i32, y.round() as i32)
+    }
+}
+
+impl Tweenable for Rgb {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        self.lerp(*to, t)
+    }
+}
+
+// Common animated properties of a drawn item
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Props {
+    pub position: (f32, f32),
+    pub colour: Rgb,
+    pub brightness: f32,
+    pub scale: f32, // Size multiplier (see draw_sprite)
+}
+
+impl Props {
+    /// Position rounded to nearest pixel
+    pub fn point(&self) -> (i32, i32) {
+        (
+            self.position.0.round() as i32,
+            self.position.1.round() as i32,
+        )
+    }
+    /// Colour scaled by brightness
+    pub fn rgb(&self) -> Rgb {
+        let (r, g, b) = self.colour.to_f32();
+        let v = self.brightness.clamp(0.0, 1.0);
+        Rgb::from_f32((r * v, g * v, b * v))
+    }
+    /// Size of item after scaling (rounded to nearest pixel)
+    pub fn scaled_size(&self, (w, h): (i32, i32)) -> (i32, i32) {
+        let scale = self.scale.max(0.0);
+        (
+            (w as f32 * scale).round() as i32,
+            (h as f32 * scale).round() as i32,
+        )
+    }
+    /// Draw sprite at position (top left) scaled by scale and tinted by
+    /// colour and brightness
+    pub fn draw_sprite(&self, canvas: &mut (impl Canvas + ?Sized), sprite: &Sprite) {
+        let ((x0, y0), (w, h)) = (self.point(), self.scaled_size(sprite.size()));
+        let (cw, ch) = canvas.size();
+        // Only resample the visible part so large scales stay cheap
+        let (vx, vy) = (x0.max(0), y0.max(0));
+        let (vw, vh) = (
+            x0.saturating_add(w).min(cw) - vx,
+            y0.saturating_add(h).min(ch) - vy,
+        );
+        if vw <= 0 || vh <= 0 {
+            return;
+        }
+        let (sw, sh) = sprite.size();
+        let (fx, fy) = (sw as f32 / w as f32, sh as f32 / h as f32);
+        let visible = Sprite::from_fn((vw, vh), |(x, y)| {
+            let sx = ((x + vx) as f32 - x0 as f32 + 0.5) * fx;
+            let sy = ((y + vy) as f32 - y0 as f32 + 0.5) * fy;
+            sprite.pixel((sx as i32, sy as i32)).unwrap_or_default()
+        });
+        let opts = Blit {
+            tint: Some(self.rgb()),
+            ..Default::default()
+        };
+        visible.blit(canvas, (vx, vy), opts);
+    }
+}
+
+impl Default for Props {
+    fn default() -> Self {
+        Self {
+            position: (0.0, 0.0),
+            colour: WHITE,
+            brightness: 1.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Tweenable for Props {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        Self {
+            position: self.position.tween(&to.position, t),
+            colour: self.colour.tween(&to.colour, t),
+            brightness: self.brightness.tween(&to.brightness, t).max(0.0),
+            scale: self.scale.tween(&to.scale, t).max(0.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Repeat {
+    #[default]
+    Once,
+    Loop, // Restart from first keyframe
+    Yoyo, // Alternate forwards and backwards
+}
+
+// Value reached at_ms into the tween (easing applies to the segment
+// leading to this keyframe)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe<T> {
+    pub at_ms: u32,
+    pub value: T,
+    pub easing: Easing,
+}
+
+// Keyframe animation
+//
+//   Tween::new(0.0).to(8.0, 400, Easing::EaseOut).hold(200).repeat(Repeat::Yoyo)
+#[derive(Clone, Debug)]
+pub struct Tween<T> {
+    keyframes: Vec<Keyframe<T>>,
+    repeat: Repeat,
+    cycles: Option<u32>, // Passes for Loop/Yoyo (None = forever)
+}
+
+impl<T: Tweenable> Tween<T> {
+    pub fn new(start: T) -> Self {
+        Self {
+            keyframes: vec![Keyframe {
+                at_ms: 0,
+                value: start,
+                easing: Easing::Linear,
+            }],
+            repeat: Repeat::Once,
+            cycles: None,
+        }
+    }
+    pub fn between(from: T, to: T, duration_ms: u32, easing: Easing) -> Self {
+        Self::new(from).to(to, duration_ms, easing)
+    }
+    /// Add keyframe duration_ms after the previous one
+    pub fn to(mut self, value: T, duration_ms: u32, easing: Easing) -> Self {
+        let at_ms = self.duration_ms().saturating_add(duration_ms);
+        self.keyframes.push(Keyframe {
+            at_ms,
+            value,
+            easing,
+        });
+        self
+    }
+    /// Stay at the current value for duration_ms
+    pub fn hold(self, duration_ms: u32) -> Self {
+        let value = self.end();
+        self.to(value, duration_ms, Easing::Linear)
+    }
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+    /// Limit Loop/Yoyo to n passes (a Yoyo there and back is 2)
+    pub fn cycles(mut self, n: u32) -> Self {
+        self.cycles = Some(n);
+        self
+    }
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+    /// Length of one pass through the keyframes
+    pub fn duration_ms(&self) -> u32 {
+        self.keyframes.last().map(|k| k.at_ms).unwrap_or(0)
+    }
+    /// Total length (None if repeating forever)
+    pub fn total_ms(&self) -> Option<u64> {
+        match (self.repeat, self.cycles) {
+            (Repeat::Once, _) => Some(self.duration_ms() as u64),
+            (_, Some(n)) => Some(self.duration_ms() as u64 * n as u64),
+            (_, None) => None,
+        }
+    }
+    pub fn is_finished(&self, elapsed_ms: u64) -> bool {
+        self.total_ms().is_some_and(|total| elapsed_ms >= total)
+    }
+    fn start(&self) -> T {
+        self.keyframes[0].value
+    }
+    fn end(&self) -> T {
+        self.keyframes[self.keyframes.len() - 1].value
+    }
+    // Value at time within a single forward pass
+    fn sample(&self, t_ms: u32) -> T {
+        match self.keyframes.iter().position(|k| k.at_ms > t_ms) {
+            None => self.end(),
+            Some(0) => self.start(),
+            Some(i) => {
+                let (k0, k1) = (&self.keyframes[i - 1], &self.keyframes[i]);
+                let p = (t_ms - k0.at_ms) as f32 / (k1.at_ms - k0.at_ms) as f32;
+                k0.value.tween(&k1.value, k1.easing.apply(p))
+            }
+        }
+    }
+    /// Value elapsed_ms after start
+    pub fn value_at(&self, elapsed_ms: u64) -> T {
+        let duration = self.duration_ms() as u64;
+        if duration == 0 {
+            return self.end();
+        }
+        if self.is_finished(elapsed_ms) {
+            // Yoyo with even number of passes finishes where it started
+            return match (self.repeat, self.cycles) {
+                (Repeat::Yoyo, Some(n)) if n % 2 == 0 => self.start(),
+                _ => self.end(),
+            };
+        }
+        let (pass, t) = (elapsed_ms / duration, (elapsed_ms % duration) as u32);
+        match self.repeat {
+            Repeat::Yoyo if pass % 2 == 1 => self.sample(duration as u32 - t),
+            _ => self.sample(t),
+        }
+    }
+}
+
+// Tween bound to a start time on a clock
+#[derive(Clone, Debug)]
+pub struct Animator<T> {
+    tween: Tween<T>,
+    start_ms: u64,
+}
+
+impl<T: Tweenable> Animator<T> {
+    pub fn start(tween: Tween<T>, clock: &dyn Clock) -> Self {
+        Self {
+            tween,
+            start_ms: clock.now_ms(),
+        }
+    }
+    pub fn restart(&mut self, clock: &dyn Clock) {
+        self.start_ms = clock.now_ms();
+    }
+    pub fn elapsed_ms(&self, clock: &dyn Clock) -> u64 {
+        clock.now_ms().saturating_sub(self.start_ms)
+    }
+    pub fn value(&self, clock: &dyn Clock) -> T {
+        self.tween.value_at(self.elapsed_ms(clock))
+    }
+    pub fn is_finished(&self, clock: &dyn Clock) -> bool {
+        self.tween.is_finished(self.elapsed_ms(clock))
+    }
+}
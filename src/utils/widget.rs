@@ -0,0 +1,107 @@
+use crate::draw::{Canvas, Paint};
+use crate::rgb::{Rgb, RgbTransform};
+
+const SPINNER_STEP_MS: u32 = 80;
+const SPINNER_TAIL: usize = 4;
+
+// Status widgets drawn into a region of the display
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Widget {
+    Progress(f32, Rgb),     // Fraction complete (0.0..=1.0)
+    Spinner(Rgb),           // Indeterminate - dot chasing round region edge
+    Wifi(Option<i32>, Rgb), // Signal strength in dBm (None if disconnected)
+}
+
+/// Signal strength bars (0..=4) for RSSI in dBm
+pub fn wifi_bars(rssi: i32) -> i32 {
+    match rssi {
+        r if r >= -55 => 4,
+        r if r >= -67 => 3,
+        r if r >= -75 => 2,
+        r if r >= -85 => 1,
+        _ => 0,
+    }
+}
+
+// Unlit parts - dim but not black so they show over other layers
+fn dim(rgb: Rgb) -> Rgb {
+    let dim = rgb.transform(&[RgbTransform::Intensity(0.15)]);
+    if dim == Rgb::default() {
+        Rgb::new(1, 1, 1)
+    } else {
+        dim
+    }
+}
+
+// Points round edge of region clockwise from top left
+fn perimeter((x, y): (i32, i32), (w, h): (i32, i32)) -> Vec<(i32, i32)> {
+    if w == 1 || h == 1 {
+        return (0..w)
+            .flat_map(|dx| (0..h).map(move |dy| (x + dx, y + dy)))
+            .collect();
+    }
+    let top = (0..w).map(|dx| (x + dx, y));
+    let right = (1..h).map(|dy| (x + w - 1, y + dy));
+    let bottom = (0..w - 1).rev().map(|dx| (x + dx, y + h - 1));
+    let left = (1..h - 1).rev().map(|dy| (x, y + dy));
+    top.chain(right).chain(bottom).chain(left).collect()
+}
+
+impl Widget {
+    /// Widget changes without updates
+    pub fn is_animated(&self) -> bool {
+        matches!(self, Widget::Spinner(_))
+    }
+    /// Draw into region at (x, y) of size (w, h) - now_ms (monotonic clock
+    /// time) animates spinner
+    pub fn draw(
+        &self,
+        canvas: &mut dyn Canvas,
+        (x, y): (i32, i32),
+        (w, h): (i32, i32),
+        now_ms: u32,
+    ) {
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        match *self {
+            Widget::Progress(fraction, rgb) => {
+                // Partial column blended so progress moves smoothly
+                let filled = fraction.clamp(0.0, 1.0) * w as f32;
+                for col in 0..w {
+                    let coverage = (filled - col as f32).clamp(0.0, 1.0);
+                    let paint = Paint::Solid(dim(rgb).lerp(rgb, coverage));
+                    canvas.fill_rect((x + col, y), (x + col + 1, y + h), paint);
+                }
+            }
+            Widget::Spinner(rgb) => {
+                let points = perimeter((x, y), (w, h));
+                let head = (now_ms / SPINNER_STEP_MS) as usize % points.len();
+                for (i, p) in points.iter().enumerate() {
+                    // Distance behind head (wrapping)
+                    let behind = (head + points.len() - i) % points.len();
+                    let rgb = if behind < SPINNER_TAIL {
+                        dim(rgb).lerp(rgb, 1.0 - behind as f32 / SPINNER_TAIL as f32)
+                    } else {
+                        dim(rgb)
+                    };
+                    canvas.set(*p, rgb);
+                }
+            }
+            Widget::Wifi(rssi, rgb) => {
+                // Four bars rising left to right (no gaps if region is
+                // narrow, fewer bars if narrower than four pixels)
+                let count = w.min(4);
+                let bars = (rssi.map_or(0, wifi_bars) * count + 3) / 4;
+                let gap = if w >= 7 { 1 } else { 0 };
+                let bar_w = ((w - (count - 1) * gap) / count).max(1);
+                for i in 0..count {
+                    let bar_h = (h * (i + 1) / count).max(1);
+                    let bx = x + i * (bar_w + gap);
+                    let paint = Paint::Solid(if i < bars { rgb } else { dim(rgb) });
+                    canvas.fill_rect((bx, y + h - bar_h), (bx + bar_w, y + h), paint);
+                }
+            }
+        }
+    }
+}
@@ -130,6 +130,15 @@ pub fn start_access_point(wifi: &mut EspWifi) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Strongest signal (dBm) seen for SSID in last scan
+pub fn scan_rssi(ssid: &str) -> Option<i32> {
+    let aps = WIFI_SCAN.lock().unwrap();
+    aps.iter()
+        .filter(|ap| ap.ssid.as_str() == ssid)
+        .map(|ap| ap.signal_strength as i32)
+        .max()
+}
+
 pub fn find_known_aps() -> Vec<APConfig> {
     let mut known = Vec::new();
     let mut seen = Vec::new(); // We can see same SSID on multiple bands
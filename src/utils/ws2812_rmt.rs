@@ -1,7 +1,10 @@
 use anyhow::Result;
 use esp_idf_hal::rmt::{config::TransmitConfig, FixedLengthSignal, PinState, Pulse, TxRmtDriver};
-use std::time::Duration;
+use serde::Serialize;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
 
+use crate::hash::Fnv1a;
 use crate::rgb::{Rgb, RgbLayout};
 
 // ws2812 timings
@@ -53,10 +56,20 @@ impl Ws2812RmtSingle<'_> {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FrameStats {
+    pub sent: u32,
+    pub skipped: u32, // Identical to previous frame
+}
+
 pub struct Ws2812Rmt<'a> {
     tx: esp_idf_hal::rmt::TxRmtDriver<'a>,
     signal: esp_idf_hal::rmt::VariableLengthSignal,
     format: RgbLayout,
+    last_hash: Option<u64>,
+    last_sent: Option<Instant>,
+    keep_alive: Option<Duration>,
+    stats: FrameStats,
 }
 
 impl<'a> Ws2812Rmt<'a> {
@@ -70,9 +83,67 @@ impl<'a> Ws2812Rmt<'a> {
     pub fn new(tx: TxRmtDriver<'a>, n: usize, format: RgbLayout) -> Self {
         // 2 pulses / led + reset
         let signal = esp_idf_hal::rmt::VariableLengthSignal::with_capacity(2 * n + 1);
-        Self { tx, signal, format }
+        Self {
+            tx,
+            signal,
+            format,
+            last_hash: None,
+            last_sent: None,
+            keep_alive: None,
+            stats: FrameStats::default(),
+        }
+    }
+    // Resend identical frames after interval (None to never resend)
+    pub fn set_keep_alive(&mut self, interval: Option<Duration>) {
+        self.keep_alive = interval;
+    }
+    // True if keep-alive interval has elapsed since last frame was sent
+    pub fn keep_alive_due(&self) -> bool {
+        match (self.keep_alive, self.last_sent) {
+            (Some(interval), Some(sent)) => sent.elapsed() >= interval,
+            _ => false,
+        }
     }
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+    // Force next frame to be sent
+    pub fn invalidate(&mut self) {
+        self.last_hash = None;
+    }
+    // Send frame - skipped if identical to previous frame (unless keep-alive is due)
     pub fn set<T>(&mut self, colours: T) -> Result<()>
+    where
+        T: IntoIterator<Item = Rgb>,
+    {
+        let hash = self.build_signal(colours)?;
+        if self.is_duplicate(hash) {
+            return Ok(());
+        }
+        self.send(hash)
+    }
+    // Send frame with precomputed hash (eg. Matrix1D::frame_hash) - duplicates
+    // are skipped before the signal is built
+    pub fn set_hashed<T>(&mut self, hash: u64, colours: T) -> Result<()>
+    where
+        T: IntoIterator<Item = Rgb>,
+    {
+        if self.is_duplicate(hash) {
+            return Ok(());
+        }
+        self.build_signal(colours)?;
+        self.send(hash)
+    }
+    // Frame matches last sent (and keep-alive isn't due) - counted as skipped
+    fn is_duplicate(&mut self, hash: u64) -> bool {
+        let duplicate = self.last_hash == Some(hash) && !self.keep_alive_due();
+        if duplicate {
+            self.stats.skipped = self.stats.skipped.wrapping_add(1);
+        }
+        duplicate
+    }
+    // Build RMT signal for colours - returns hash of colours
+    fn build_signal<T>(&mut self, colours: T) -> Result<u64>
     where
         T: IntoIterator<Item = Rgb>,
     {
@@ -85,7 +156,9 @@ impl<'a> Ws2812Rmt<'a> {
             Pulse::new_with_duration(ticks_hz, PinState::Low, &Duration::from_nanos(T1L))?,
             Pulse::new_with_duration(ticks_hz, PinState::Low, &Duration::from_nanos(RESET))?,
         );
+        let mut hasher = Fnv1a::new();
         for rgb in colours {
+            rgb.hash(&mut hasher);
             let colour: u32 = rgb.to_u32(self.format);
             for i in (0..24).rev() {
                 if (colour >> i) & 1 == 0 {
@@ -96,7 +169,13 @@ impl<'a> Ws2812Rmt<'a> {
             }
         }
         self.signal.push([&reset])?;
+        Ok(hasher.finish())
+    }
+    fn send(&mut self, hash: u64) -> Result<()> {
         self.tx.start_blocking(&self.signal)?;
+        self.last_hash = Some(hash);
+        self.last_sent = Some(Instant::now());
+        self.stats.sent = self.stats.sent.wrapping_add(1);
         Ok(())
     }
 }